@@ -2,13 +2,15 @@ pub mod presence;
 pub mod usage;
 
 pub use presence::{
-    LabelStyle, PresenceFieldConfig, PresenceFieldId, PresenceLayoutConfig, PresenceLines,
-    PresencePreset, PresenceValues, PresenceZone, compose_presence,
+    DEFAULT_STATE_SEPARATOR, LabelStyle, PresenceFieldConfig, PresenceFieldId,
+    PresenceLayoutConfig, PresenceLines, PresencePreset, PresenceValues, PresenceZone,
+    compose_presence, compose_presence_with_separator,
 };
 pub use usage::{
-    CreditBalance, EffectiveLimitSelection, QuotaScope, QuotaWindow, RateLimitEnvelope,
-    RateLimitScope, RateLimits, SessionLimitCandidate, UsageSnapshot, UsageWindow,
-    classify_limit_scope, format_window_label, limits_present, parse_rate_limit_envelope,
-    select_credits_global_first, select_effective_limits_global_first,
-    select_session_envelope_global_first, usage_snapshot_from_envelopes,
+    CreditBalance, EffectiveLimitSelection, LimitWindowSource, MinRemainingLimits, QuotaScope,
+    QuotaWindow, RateLimitEnvelope, RateLimitScope, RateLimits, SessionLimitCandidate,
+    UsageSnapshot, UsageWindow, classify_limit_scope, format_window_label, limits_present,
+    min_remaining_limits, parse_rate_limit_envelope, select_credits_global_first,
+    select_effective_limits_global_first, select_session_envelope_global_first,
+    usage_snapshot_from_envelopes,
 };
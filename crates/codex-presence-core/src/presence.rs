@@ -223,11 +223,29 @@ pub struct PresenceLines {
     pub state: String,
 }
 
+pub const DEFAULT_STATE_SEPARATOR: &str = " • ";
+
 pub fn compose_presence(
     layout: &PresenceLayoutConfig,
     values: &PresenceValues,
     details_fallback: &str,
     state_fallback: &str,
+) -> PresenceLines {
+    compose_presence_with_separator(
+        layout,
+        values,
+        details_fallback,
+        state_fallback,
+        DEFAULT_STATE_SEPARATOR,
+    )
+}
+
+pub fn compose_presence_with_separator(
+    layout: &PresenceLayoutConfig,
+    values: &PresenceValues,
+    details_fallback: &str,
+    state_fallback: &str,
+    state_separator: &str,
 ) -> PresenceLines {
     let mut details = Vec::new();
     let mut state = Vec::new();
@@ -246,7 +264,7 @@ pub fn compose_presence(
     }
     PresenceLines {
         details: compact_join(&details, details_fallback, " · ", 128),
-        state: compact_join(&state, state_fallback, " • ", 128),
+        state: compact_join(&state, state_fallback, state_separator, 128),
     }
 }
 
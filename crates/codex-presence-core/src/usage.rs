@@ -134,6 +134,20 @@ pub struct EffectiveLimitSelection {
     pub observed_at: Option<DateTime<Utc>>,
     pub limits: RateLimits,
     pub credits: Option<CreditBalance>,
+    pub window_sources: Vec<LimitWindowSource>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitWindowSource {
+    pub window_minutes: u64,
+    pub session_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinRemainingLimits<K> {
+    pub limits: RateLimits,
+    pub primary_source: Option<K>,
+    pub secondary_source: Option<K>,
 }
 
 impl EffectiveLimitSelection {
@@ -156,6 +170,37 @@ pub fn limits_present(limits: &RateLimits) -> bool {
     limits.primary.is_some() || limits.secondary.is_some()
 }
 
+pub fn min_remaining_limits<'a, K: Clone + 'a>(
+    limits: impl IntoIterator<Item = (K, &'a RateLimits)>,
+    now: DateTime<Utc>,
+) -> MinRemainingLimits<K> {
+    let mut tightest: BTreeMap<u64, (K, &UsageWindow)> = BTreeMap::new();
+    for (source, item) in limits {
+        for window in [&item.primary, &item.secondary].into_iter().flatten() {
+            if window.resets_at.is_some_and(|resets_at| resets_at <= now) {
+                continue;
+            }
+            let replace = tightest
+                .get(&window.window_minutes)
+                .is_none_or(|(_, current)| window.remaining_percent < current.remaining_percent);
+            if replace {
+                tightest.insert(window.window_minutes, (source.clone(), window));
+            }
+        }
+    }
+    let mut by_duration = tightest.into_values();
+    let primary = by_duration.next();
+    let secondary = by_duration.next();
+    MinRemainingLimits {
+        limits: RateLimits {
+            primary: primary.as_ref().map(|(_, window)| (*window).clone()),
+            secondary: secondary.as_ref().map(|(_, window)| (*window).clone()),
+        },
+        primary_source: primary.map(|(source, _)| source),
+        secondary_source: secondary.map(|(source, _)| source),
+    }
+}
+
 pub fn classify_limit_scope(limit_id: Option<&str>) -> RateLimitScope {
     let normalized = limit_id
         .map(str::trim)
@@ -320,6 +365,7 @@ pub fn select_effective_limits_global_first(
         observed_at: selected.envelope.observed_at,
         limits: selected.envelope.limits.clone(),
         credits,
+        window_sources: Vec::new(),
     })
 }
 
@@ -444,6 +490,50 @@ fn system_time_rank(time: SystemTime) -> i64 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn min_remaining_limits_matches_windows_by_duration_and_keeps_sources() {
+        let now = Utc.timestamp_opt(1_000_000, 0).unwrap();
+        let window = |remaining: f64, minutes: u64, resets_in: i64| UsageWindow {
+            used_percent: 100.0 - remaining,
+            remaining_percent: remaining,
+            window_minutes: minutes,
+            resets_at: Some(now + chrono::Duration::seconds(resets_in)),
+        };
+        let limits = [
+            RateLimits {
+                primary: Some(window(80.0, 300, 600)),
+                secondary: Some(window(15.0, 10_080, 6_000)),
+            },
+            RateLimits {
+                primary: Some(window(5.0, 10_080, 6_000)),
+                secondary: None,
+            },
+            RateLimits {
+                primary: Some(window(25.0, 300, 600)),
+                secondary: Some(window(40.0, 10_080, 6_000)),
+            },
+            RateLimits {
+                primary: Some(window(1.0, 300, -60)),
+                secondary: None,
+            },
+        ];
+
+        let aggregate = min_remaining_limits(
+            ["a", "weekly-only", "c", "expired"]
+                .into_iter()
+                .zip(&limits),
+            now,
+        );
+        assert_eq!(aggregate.limits.primary, Some(window(25.0, 300, 600)));
+        assert_eq!(aggregate.limits.secondary, Some(window(5.0, 10_080, 6_000)));
+        assert_eq!(aggregate.primary_source, Some("c"));
+        assert_eq!(aggregate.secondary_source, Some("weekly-only"));
+
+        let empty = min_remaining_limits(std::iter::empty::<(&str, &RateLimits)>(), now);
+        assert_eq!(empty.limits, RateLimits::default());
+        assert_eq!(empty.primary_source, None);
+    }
+
     #[test]
     fn weekly_only_is_semantic_and_keeps_credits() {
         let payload = serde_json::json!({
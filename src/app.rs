@@ -1,52 +1,71 @@
+use std::collections::{BTreeMap, HashMap};
 use std::env;
-use std::path::PathBuf;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 use std::time::{Duration, Instant, SystemTime};
-use std::{io, io::IsTerminal};
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use tracing::debug;
+use codex_presence_core::{LimitWindowSource, min_remaining_limits};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind,
+};
+use serde::Serialize;
+use tracing::{debug, warn};
 
 use crate::config::{
-    self, OpenAiPlanDisplayConfig, PresenceConfig, PresenceSurface, PrivacyField, RuntimeSettings,
-    apply_plan_preset, plan_preset_index, plan_presets,
+    self, LimitsAggregate, OpenAiPlanDisplayConfig, OpenAiPlanMode, OpenAiPlanTier, PresenceConfig,
+    PresenceSurface, PrivacyConfig, PrivacyField, RuntimeSettings, apply_plan_preset,
+    plan_preset_index, plan_presets,
+};
+use crate::cost::{
+    CostAttribution, PricingSource, PricingStatus, TokenCostBreakdown, TokenUsage,
+    compute_total_cost, format_presentable_cost, resolve_model_pricing,
 };
-use crate::cost::format_presentable_cost;
-use crate::discord::DiscordPresence;
-use crate::metrics::MetricsTracker;
+use crate::discord::{self, DiscordPresence};
+use crate::engine::PresenceEngine;
+use crate::metrics::{MetricsSnapshot, MetricsTracker, format_metrics_cost_in};
 use crate::opencode::collect_opencode_sessions;
 use crate::process_guard::{self, RunningState};
 use crate::session::{
-    CodexSessionSnapshot, EffectiveLimitSelection, GitBranchCache, RateLimitEnvelope, RateLimits,
-    SessionParseCache, SpeedMode, collect_active_sessions_multi,
-    collect_active_sessions_multi_with_diagnostics, latest_limits_source, preferred_active_session,
+    CodexSessionSnapshot, EffectiveLimitSelection, GitBranchCache, IDLE_SESSION_LIST_WINDOW,
+    ParseCacheStats, RateLimitEnvelope, RateLimits, SessionActivityKind, SessionActivitySnapshot,
+    SessionCollectionDiagnostics, SessionParseCache, SessionSpeed, SpeedMode, UsageWindow,
+    collect_active_sessions_multi, collect_active_sessions_multi_with_diagnostics,
+    latest_limits_source, pinned_presence_session, preferred_active_session, session_limits_source,
 };
 use crate::telemetry::plan::{PlanDetector, ResolvedPlan, is_model_allowed_for_plan};
 use crate::telemetry::service_tier::{ResolvedServiceTier, ServiceTier, resolve_service_tier};
 use crate::ui::{self, RenderData};
 use crate::util::{
-    format_model_display, format_since, format_time_until, format_token_triplet, silent_command,
+    format_cost, format_model_display, format_since, format_time_until, format_token_triplet,
+    now_local, silent_command,
 };
+use crate::web::{self, WebState};
 
 const RELAUNCH_GUARD_ENV: &str = "CODEX_PRESENCE_TERMINAL_RELAUNCHED";
+#[cfg(any(windows, test))]
+const TOAST_APP_ID: &str =
+    r"{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\WindowsPowerShell\v1.0\powershell.exe";
 
 #[derive(Debug, Clone)]
 pub enum AppMode {
-    SmartForeground,
+    SmartForeground { log_lines: bool },
     CodexChild { args: Vec<String> },
 }
 
 #[derive(Debug, Default)]
-struct RuntimeSnapshot {
-    sessions: Vec<CodexSessionSnapshot>,
+pub(crate) struct RuntimeSnapshot {
+    pub(crate) sessions: Vec<CodexSessionSnapshot>,
+    idle_sessions: Vec<CodexSessionSnapshot>,
     limits_source: Option<EffectiveLimitSelection>,
     resolved_plan: ResolvedPlan,
     resolved_service_tier: ResolvedServiceTier,
+    pinned_session_id: Option<String>,
 }
 
 impl RuntimeSnapshot {
@@ -57,7 +76,8 @@ impl RuntimeSnapshot {
         plan_detector: &mut PlanDetector,
         plan_config: &OpenAiPlanDisplayConfig,
     ) -> Self {
-        let limits_source = cached_limits.or_else(|| latest_limits_source(&sessions));
+        let limits_source = cached_limits
+            .or_else(|| latest_limits_source(&sessions, preferred_active_session(&sessions)));
         let resolved_plan = if cached_envelopes.is_empty() {
             plan_detector.resolve_from_sessions(&sessions, plan_config)
         } else {
@@ -78,9 +98,11 @@ impl RuntimeSnapshot {
 
         Self {
             sessions,
+            idle_sessions: Vec::new(),
             limits_source,
             resolved_plan,
             resolved_service_tier,
+            pinned_session_id: None,
         }
     }
 
@@ -88,6 +110,13 @@ impl RuntimeSnapshot {
         preferred_active_session(&self.sessions)
     }
 
+    pub(crate) fn presence_session(
+        &self,
+        privacy: &PrivacyConfig,
+    ) -> Option<&CodexSessionSnapshot> {
+        pinned_presence_session(&self.sessions, privacy, self.pinned_session_id.as_deref())
+    }
+
     fn effective_limits(&self) -> Option<&RateLimits> {
         self.limits_source.as_ref().map(|source| &source.limits)
     }
@@ -99,24 +128,299 @@ impl RuntimeSnapshot {
     }
 }
 
+#[derive(Debug, Default)]
+pub(crate) struct LimitWarningTracker {
+    warned: HashMap<u64, Option<DateTime<Utc>>>,
+}
+
+impl LimitWarningTracker {
+    fn observe(&mut self, limits: &RateLimits, threshold_percent: f64) -> Vec<UsageWindow> {
+        let mut crossed = Vec::new();
+        for window in [&limits.primary, &limits.secondary].into_iter().flatten() {
+            if window.remaining_percent >= threshold_percent {
+                self.warned.remove(&window.window_minutes);
+                continue;
+            }
+            if self.warned.get(&window.window_minutes) == Some(&window.resets_at) {
+                continue;
+            }
+            self.warned.insert(window.window_minutes, window.resets_at);
+            crossed.push(window.clone());
+        }
+        crossed
+    }
+
+    pub(crate) fn warn_crossings(&mut self, snapshot: &RuntimeSnapshot, threshold_percent: f64) {
+        let Some(limits) = snapshot
+            .effective_limits()
+            .or_else(|| snapshot.active_session().map(|session| &session.limits))
+        else {
+            return;
+        };
+        for window in self.observe(limits, threshold_percent) {
+            warn!(
+                window = %codex_presence_core::format_window_label(window.window_minutes),
+                remaining_percent = window.remaining_percent,
+                resets_in = %format_time_until(window.resets_at),
+                "usage window dropped below {threshold_percent:.0}% remaining"
+            );
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct WaitingInputTracker {
+    waiting_since: HashMap<String, DateTime<Utc>>,
+}
+
+impl WaitingInputTracker {
+    fn observe(&mut self, sessions: &[CodexSessionSnapshot], now: DateTime<Utc>) {
+        self.waiting_since.retain(|session_id, _| {
+            sessions.iter().any(|session| {
+                &session.session_id == session_id && session_is_waiting_input(session)
+            })
+        });
+        for session in sessions
+            .iter()
+            .filter(|session| session_is_waiting_input(session))
+        {
+            self.waiting_since
+                .entry(session.session_id.clone())
+                .or_insert_with(|| {
+                    session
+                        .activity
+                        .as_ref()
+                        .and_then(|activity| activity.observed_at)
+                        .unwrap_or(now)
+                });
+        }
+    }
+
+    fn unread_since(&self, last_interaction: DateTime<Utc>) -> Vec<String> {
+        let mut unread = self
+            .waiting_since
+            .iter()
+            .filter(|(_, waiting_since)| **waiting_since > last_interaction)
+            .map(|(session_id, _)| session_id.clone())
+            .collect::<Vec<_>>();
+        unread.sort();
+        unread
+    }
+}
+
+const TOKEN_HISTORY_LEN: usize = 12;
+const SESSION_ROOT_SCAN_DEPTH: usize = 4;
+
+#[derive(Debug, Default)]
+struct TokenDeltaHistory {
+    last_event_at: HashMap<String, Option<DateTime<Utc>>>,
+    deltas: BTreeMap<String, Vec<u64>>,
+}
+
+impl TokenDeltaHistory {
+    fn observe(&mut self, sessions: &[CodexSessionSnapshot]) {
+        self.deltas.retain(|session_id, _| {
+            sessions
+                .iter()
+                .any(|session| &session.session_id == session_id)
+        });
+        self.last_event_at
+            .retain(|session_id, _| self.deltas.contains_key(session_id));
+        for session in sessions {
+            let Some(delta) = session.session_delta_tokens else {
+                continue;
+            };
+            let last_event_at = self
+                .last_event_at
+                .entry(session.session_id.clone())
+                .or_insert(None);
+            let history = self.deltas.entry(session.session_id.clone()).or_default();
+            if !history.is_empty() && *last_event_at == session.last_token_event_at {
+                continue;
+            }
+            *last_event_at = session.last_token_event_at;
+            history.push(delta);
+            if history.len() > TOKEN_HISTORY_LEN {
+                history.remove(0);
+            }
+        }
+    }
+}
+
+fn session_is_waiting_input(session: &CodexSessionSnapshot) -> bool {
+    session.activity.as_ref().is_some_and(|activity| {
+        matches!(
+            activity.kind,
+            SessionActivityKind::WaitingInput | SessionActivityKind::AwaitingApproval
+        )
+    })
+}
+
 pub fn run(config: PresenceConfig, mode: AppMode, runtime: RuntimeSettings) -> Result<()> {
     match mode {
-        AppMode::SmartForeground => run_foreground_tui(config, runtime),
+        AppMode::SmartForeground { log_lines } => run_foreground_tui(config, runtime, log_lines),
         AppMode::CodexChild { args } => run_codex_wrapper(config, runtime, args),
     }
 }
 
-pub fn print_status(config: &PresenceConfig) -> Result<()> {
-    let runtime = config::runtime_settings();
+pub fn print_status(
+    config: &PresenceConfig,
+    debug_cache: bool,
+    json: bool,
+    roots: bool,
+    stale_secs: Option<u64>,
+) -> Result<()> {
+    if roots {
+        let reports: Vec<SessionRootReport> = config::sessions_paths()
+            .iter()
+            .map(|path| inspect_session_root(path))
+            .collect();
+        return write_session_root_reports(&mut io::stdout().lock(), &reports, json);
+    }
+    let runtime = status_runtime(stale_secs);
     let session_roots = config::sessions_paths();
-    let mut cache = GitBranchCache::new(Duration::from_secs(30));
-    let mut parse_cache = SessionParseCache::default();
-    let (mut sessions, diagnostics) = collect_active_sessions_multi_with_diagnostics(
+    if json {
+        return write_status_json(&mut io::stdout().lock(), config, &runtime, &session_roots);
+    }
+    write_status(
+        &mut io::stdout().lock(),
+        config,
+        &runtime,
         &session_roots,
+        debug_cache,
+    )
+}
+
+fn status_runtime(stale_secs: Option<u64>) -> RuntimeSettings {
+    config::runtime_settings().with_overrides(None, stale_secs)
+}
+
+pub fn watch_status(
+    mut config: PresenceConfig,
+    interval: Duration,
+    runtime: RuntimeSettings,
+) -> Result<()> {
+    let stop = install_stop_signal()?;
+    while !stop.load(Ordering::Relaxed) {
+        reload_runtime_config(&mut config);
+        let mut stdout = io::stdout().lock();
+        write_watch_frame(&mut stdout, &config, &runtime, &config::sessions_paths())?;
+        stdout.flush()?;
+        drop(stdout);
+
+        let deadline = Instant::now() + interval;
+        while !stop.load(Ordering::Relaxed) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(100));
+        }
+    }
+    Ok(())
+}
+
+fn write_watch_frame(
+    out: &mut impl Write,
+    config: &PresenceConfig,
+    runtime: &RuntimeSettings,
+    session_roots: &[PathBuf],
+) -> Result<()> {
+    crossterm::queue!(
+        out,
+        crossterm::terminal::Clear(crossterm::terminal::ClearType::All),
+        crossterm::cursor::MoveTo(0, 0)
+    )?;
+    write_status(out, config, runtime, session_roots, false)
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct SessionRootReport {
+    path: PathBuf,
+    exists: bool,
+    readable: bool,
+    jsonl_files: usize,
+    error: Option<String>,
+}
+
+fn inspect_session_root(path: &Path) -> SessionRootReport {
+    let mut report = SessionRootReport {
+        path: path.to_path_buf(),
+        exists: path.exists(),
+        readable: false,
+        jsonl_files: 0,
+        error: None,
+    };
+    if !report.exists {
+        return report;
+    }
+    if let Err(err) = std::fs::read_dir(path) {
+        report.error = Some(err.to_string());
+        return report;
+    }
+    report.readable = true;
+    for entry in walkdir::WalkDir::new(path).max_depth(SESSION_ROOT_SCAN_DEPTH) {
+        match entry {
+            Ok(entry) => {
+                if entry.file_type().is_file()
+                    && entry.path().extension().and_then(|ext| ext.to_str()) == Some("jsonl")
+                {
+                    report.jsonl_files += 1;
+                }
+            }
+            Err(err) => {
+                report.error.get_or_insert_with(|| err.to_string());
+            }
+        }
+    }
+    report
+}
+
+fn write_session_root_reports(
+    out: &mut impl Write,
+    reports: &[SessionRootReport],
+    json: bool,
+) -> Result<()> {
+    if json {
+        serde_json::to_writer_pretty(&mut *out, reports)?;
+        writeln!(out)?;
+        return Ok(());
+    }
+    writeln!(out, "sessions_roots:")?;
+    for report in reports {
+        let verdict = if !report.exists {
+            "missing".to_string()
+        } else if !report.readable {
+            "unreadable".to_string()
+        } else {
+            format!("ok, {} jsonl file(s)", report.jsonl_files)
+        };
+        write!(out, "  - {}: {verdict}", report.path.display())?;
+        if let Some(error) = &report.error {
+            write!(out, " ({error})")?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+struct StatusContext {
+    session_roots: Vec<PathBuf>,
+    diagnostics: SessionCollectionDiagnostics,
+    is_running: bool,
+    running_pid: Option<u32>,
+    snapshot: RuntimeSnapshot,
+}
+
+fn collect_status(
+    config: &PresenceConfig,
+    runtime: &RuntimeSettings,
+    session_roots: &[PathBuf],
+    cache: &mut GitBranchCache,
+    parse_cache: &mut SessionParseCache,
+) -> Result<StatusContext> {
+    let (mut sessions, diagnostics) = collect_active_sessions_multi_with_diagnostics(
+        session_roots,
         runtime.stale_threshold,
         runtime.active_sticky_window,
-        &mut cache,
-        &mut parse_cache,
+        cache,
+        parse_cache,
         &config.pricing,
     )?;
     sessions.extend(collect_opencode_sessions(
@@ -129,80 +433,523 @@ pub fn print_status(config: &PresenceConfig) -> Result<()> {
         RunningState::NotRunning => (false, None),
         RunningState::Running { pid } => (true, pid),
     };
+    let mut plan_detector = PlanDetector::new();
+    let active = preferred_active_session(&sessions);
+    let cached_limits = aggregated_limits_source(
+        followed_limits_source(active, config).or_else(|| parse_cache.latest_limits_source(active)),
+        &sessions,
+        config.display.limits_aggregate,
+    );
+    let cached_envelopes = parse_cache.rate_limit_envelopes();
+    let snapshot = RuntimeSnapshot::from_sessions(
+        sessions,
+        cached_limits,
+        &cached_envelopes,
+        &mut plan_detector,
+        &config.openai_plan,
+    );
+    Ok(StatusContext {
+        session_roots: session_roots.to_vec(),
+        diagnostics,
+        is_running,
+        running_pid,
+        snapshot,
+    })
+}
+
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    running: bool,
+    pid: Option<u32>,
+    config_path: PathBuf,
+    presence_enabled: bool,
+    session_roots: Vec<PathBuf>,
+    discord_client_id_default: bool,
+    discord_client_id_desktop: bool,
+    active_sessions: usize,
+    active_session: Option<StatusSessionSummary>,
+    limits_source: Option<StatusLimitsSource>,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusSessionSummary {
+    session_id: String,
+    project: String,
+    path: PathBuf,
+    model: Option<String>,
+    git_branch: Option<String>,
+    activity: Option<String>,
+    total_tokens: Option<u64>,
+    known_cost_usd: Option<f64>,
+    started_at: Option<DateTime<Utc>>,
+    limits: RateLimits,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusLimitsSource {
+    session_id: String,
+    source: String,
+    observed_at: Option<DateTime<Utc>>,
+}
+
+fn status_report(config: &PresenceConfig, status: &StatusContext) -> StatusReport {
+    let snapshot = &status.snapshot;
+    let active_session = snapshot
+        .active_session()
+        .map(|active| StatusSessionSummary {
+            session_id: active.session_id.clone(),
+            project: active.project_name.clone(),
+            path: active.cwd.clone(),
+            model: active.model.clone(),
+            git_branch: active.git_branch.clone(),
+            activity: active
+                .activity
+                .as_ref()
+                .filter(|_| config.privacy.show_activity)
+                .map(|activity| activity.to_text(config.privacy.show_activity_target)),
+            total_tokens: active.session_total_tokens,
+            known_cost_usd: active.known_cost_usd,
+            started_at: active.started_at,
+            limits: snapshot
+                .effective_limits()
+                .unwrap_or(&active.limits)
+                .clone(),
+        });
+    let limits_source = snapshot
+        .limits_source
+        .as_ref()
+        .map(|source| StatusLimitsSource {
+            session_id: source.source_session_id.clone(),
+            source: source.source_label(),
+            observed_at: source.observed_at,
+        });
+    StatusReport {
+        running: status.is_running,
+        pid: status.running_pid,
+        config_path: config::config_path(),
+        presence_enabled: config.presence_enabled,
+        session_roots: status.session_roots.clone(),
+        discord_client_id_default: config
+            .effective_client_id_for_surface(PresenceSurface::Cli)
+            .is_some(),
+        discord_client_id_desktop: config
+            .effective_client_id_for_surface(PresenceSurface::Desktop)
+            .is_some(),
+        active_sessions: snapshot.sessions.len(),
+        active_session,
+        limits_source,
+    }
+}
+
+fn write_status_json(
+    out: &mut impl Write,
+    config: &PresenceConfig,
+    runtime: &RuntimeSettings,
+    session_roots: &[PathBuf],
+) -> Result<()> {
+    let mut cache = GitBranchCache::new(Duration::from_secs(30));
+    let mut parse_cache = SessionParseCache::default();
+    let status = collect_status(config, runtime, session_roots, &mut cache, &mut parse_cache)?;
+    serde_json::to_writer_pretty(&mut *out, &status_report(config, &status))?;
+    writeln!(out)?;
+    Ok(())
+}
 
-    println!("codex-discord-presence status");
-    println!("running: {is_running}");
+fn write_status(
+    out: &mut impl Write,
+    config: &PresenceConfig,
+    runtime: &RuntimeSettings,
+    session_roots: &[PathBuf],
+    debug_cache: bool,
+) -> Result<()> {
+    let mut cache = GitBranchCache::new(Duration::from_secs(30));
+    let mut parse_cache = SessionParseCache::default();
+    let StatusContext {
+        session_roots,
+        diagnostics,
+        is_running,
+        running_pid,
+        snapshot,
+    } = collect_status(config, runtime, session_roots, &mut cache, &mut parse_cache)?;
+
+    writeln!(out, "codex-discord-presence status")?;
+    writeln!(out, "running: {is_running}")?;
     if let Some(pid) = running_pid {
-        println!("pid: {pid}");
+        writeln!(out, "pid: {pid}")?;
     }
-    println!("config: {}", config::config_path().display());
-    println!("presence_enabled: {}", config.presence_enabled);
-    print_session_roots("sessions_dirs", &session_roots);
-    println!(
+    writeln!(out, "config: {}", config::config_path().display())?;
+    writeln!(out, "presence_enabled: {}", config.presence_enabled)?;
+    write_session_roots(out, "sessions_dirs", &session_roots)?;
+    writeln!(
+        out,
         "runtime_surface: {}",
         runtime_surface_hint().label(config.display.desktop_presence_design)
-    );
+    )?;
     let default_client_id = config.effective_client_id_for_surface(PresenceSurface::Cli);
     let desktop_client_id = config.effective_client_id_for_surface(PresenceSurface::Desktop);
-    println!(
+    writeln!(
+        out,
         "discord_client_id_default: {}",
         if default_client_id.is_some() {
             "configured"
         } else {
             "missing"
         }
-    );
-    println!(
+    )?;
+    writeln!(
+        out,
         "discord_client_id_desktop: {}",
         if desktop_client_id.is_some() {
             "configured"
         } else {
             "missing"
         }
-    );
-    println!("active_sessions: {}", sessions.len());
-    println!("session_files_seen: {}", diagnostics.session_files_seen);
-    println!("discarded_stale: {}", diagnostics.dropped_stale);
-    println!(
+    )?;
+    writeln!(out, "active_sessions: {}", snapshot.sessions.len())?;
+    writeln!(
+        out,
+        "stale_threshold_secs: {}",
+        runtime.stale_threshold.as_secs()
+    )?;
+    writeln!(
+        out,
+        "session_files_seen: {}",
+        diagnostics.session_files_seen
+    )?;
+    writeln!(out, "discarded_stale: {}", diagnostics.dropped_stale)?;
+    writeln!(
+        out,
         "discarded_outside_sticky: {}",
         diagnostics.dropped_outside_sticky
-    );
-    let mut plan_detector = PlanDetector::new();
-    let cached_limits = parse_cache.latest_limits_source();
-    let cached_envelopes = parse_cache.rate_limit_envelopes();
-    let snapshot = RuntimeSnapshot::from_sessions(
-        sessions,
-        cached_limits,
-        &cached_envelopes,
-        &mut plan_detector,
-        &config.openai_plan,
-    );
+    )?;
+    writeln!(
+        out,
+        "discarded_dead_process: {}",
+        diagnostics.dropped_dead_process
+    )?;
+    if debug_cache {
+        write_parse_cache_stats(out, "parse_cache_cold", parse_cache.stats())?;
+        collect_active_sessions_multi(
+            &session_roots,
+            runtime.stale_threshold,
+            runtime.active_sticky_window,
+            &mut cache,
+            &mut parse_cache,
+            &config.pricing,
+        )?;
+        write_parse_cache_stats(out, "parse_cache_warm", parse_cache.stats())?;
+    }
     if let Some(active) = snapshot.active_session() {
         if let Some(source) = &snapshot.limits_source {
-            println!("limits_source_session: {}", source.source_session_id);
-            println!("limits_source: {}", source.source_label());
-            println!("limits_updated: {}", format_since(source.observed_at));
+            writeln!(out, "limits_source_session: {}", source.source_session_id)?;
+            writeln!(out, "limits_source: {}", source.source_label())?;
+            writeln!(out, "limits_updated: {}", format_since(source.observed_at))?;
         }
-        print_active_summary(
+        write_active_summary(
+            out,
             active,
             snapshot.effective_limits(),
             snapshot.limits_source.as_ref(),
             &snapshot.resolved_plan,
             &snapshot.resolved_service_tier,
             config,
-        );
+        )?;
+    }
+    Ok(())
+}
+
+fn write_parse_cache_stats(
+    out: &mut impl Write,
+    label: &str,
+    stats: ParseCacheStats,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "{label}: entries={} hits={} reparses={} bytes_read={}",
+        stats.entries, stats.cache_hits, stats.reparses, stats.bytes_read
+    )
+}
+
+#[derive(Debug, Serialize)]
+struct PricingReport {
+    model: String,
+    resolved_model: String,
+    source: PricingSource,
+    status: PricingStatus,
+    input_per_million: Option<f64>,
+    cached_input_per_million: Option<f64>,
+    output_per_million: Option<f64>,
+    usage: TokenUsage,
+    breakdown: TokenCostBreakdown,
+    known_total_cost_usd: Option<f64>,
+}
+
+fn pricing_report(config: &PresenceConfig, model: &str, usage: TokenUsage) -> PricingReport {
+    let resolution = resolve_model_pricing(model, &config.pricing);
+    let computed = compute_total_cost(
+        model,
+        usage.input_tokens,
+        usage.cached_input_tokens,
+        usage.output_tokens,
+        &config.pricing,
+    );
+    PricingReport {
+        model: model.to_string(),
+        resolved_model: resolution.resolved_model,
+        source: resolution.source,
+        status: computed.status,
+        input_per_million: resolution.pricing.map(|pricing| pricing.input_per_million),
+        cached_input_per_million: resolution
+            .pricing
+            .map(|pricing| pricing.cached_input_per_million),
+        output_per_million: resolution.pricing.map(|pricing| pricing.output_per_million),
+        usage,
+        breakdown: computed.breakdown,
+        known_total_cost_usd: computed.known_total_cost_usd,
+    }
+}
+
+pub fn print_pricing(
+    config: &PresenceConfig,
+    model: &str,
+    usage: TokenUsage,
+    json: bool,
+) -> Result<()> {
+    let report = pricing_report(config, model, usage);
+    let mut stdout = io::stdout().lock();
+    if json {
+        serde_json::to_writer_pretty(&mut stdout, &report)?;
+        writeln!(stdout)?;
+        return Ok(());
     }
+    write_pricing_report(&mut stdout, &report)?;
+    Ok(())
+}
+
+fn write_pricing_report(out: &mut impl Write, report: &PricingReport) -> io::Result<()> {
+    let rate = |value: Option<f64>| {
+        value
+            .map(|rate| format!("${rate:.4}/1M"))
+            .unwrap_or_else(|| "n/a".to_string())
+    };
+    writeln!(out, "codex-discord-presence pricing")?;
+    writeln!(out, "model: {}", report.model)?;
+    writeln!(out, "resolved_model: {}", report.resolved_model)?;
+    writeln!(out, "pricing_source: {:?}", report.source)?;
+    writeln!(out, "pricing_status: {:?}", report.status)?;
+    writeln!(out, "input_rate: {}", rate(report.input_per_million))?;
+    writeln!(
+        out,
+        "cached_input_rate: {}",
+        rate(report.cached_input_per_million)
+    )?;
+    writeln!(out, "output_rate: {}", rate(report.output_per_million))?;
+    writeln!(
+        out,
+        "tokens: in {} | cached {} | out {}",
+        report.usage.input_tokens, report.usage.cached_input_tokens, report.usage.output_tokens
+    )?;
+    writeln!(out, "breakdown:")?;
+    writeln!(
+        out,
+        "  input: {}",
+        format_cost(report.breakdown.input_cost_usd)
+    )?;
+    writeln!(
+        out,
+        "  cached_input: {}",
+        format_cost(report.breakdown.cached_input_cost_usd)
+    )?;
+    writeln!(
+        out,
+        "  output: {}",
+        format_cost(report.breakdown.output_cost_usd)
+    )?;
+    writeln!(
+        out,
+        "  cached_input_savings: {}",
+        format_cost(report.breakdown.cached_input_savings_usd)
+    )?;
+    writeln!(
+        out,
+        "total: {}",
+        format_presentable_cost(report.known_total_cost_usd, report.status)
+            .unwrap_or_else(|| "unavailable".to_string())
+    )
+}
+
+pub fn print_config(config: &PresenceConfig, effective: bool) -> Result<()> {
+    let shown = if effective {
+        config.effective()
+    } else {
+        config.clone()
+    };
+    println!("{}", serde_json::to_string_pretty(&shown)?);
     Ok(())
 }
 
-pub fn doctor(config: &PresenceConfig) -> Result<u8> {
+pub fn validate_config() -> Result<u8> {
+    let path = config::config_path();
+    println!("config: {}", path.display());
+    if !path.exists() {
+        println!("[OK] No config file yet; defaults apply on first run.");
+        return Ok(0);
+    }
+    let findings = config::validate_config_file(&path)?;
+    if findings.is_empty() {
+        println!("[OK] Config is valid.");
+        return Ok(0);
+    }
+    for finding in &findings {
+        println!("[WARN] {finding}");
+    }
+    println!("{} finding(s).", findings.len());
+    Ok(1)
+}
+
+pub fn simulate_session(config: &PresenceConfig, hold: Duration) -> Result<u8> {
+    let root = tempfile::tempdir().context("failed to create simulated sessions directory")?;
+    let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let path = write_simulated_session(root.path(), &cwd, Utc::now())?;
+    println!("simulated session: {}", path.display());
+
+    let runtime = config::runtime_settings();
+    let snapshot = simulated_runtime_snapshot(root.path(), &runtime, config)?;
+    let Some(session) = snapshot.presence_session(&config.privacy) else {
+        println!("[WARN] Simulated session was not picked up; check privacy allow/deny lists.");
+        return Ok(1);
+    };
+    let activity = session
+        .activity
+        .as_ref()
+        .map(|activity| activity.to_text(true))
+        .unwrap_or_else(|| "no activity".to_string());
+    println!(
+        "[OK] Preferred session {} ({}) is {activity}.",
+        session.session_id, session.project_name
+    );
+
+    let mut discord = DiscordPresence::new(config.effective_client_id());
+    publish_runtime_snapshot(&mut discord, &snapshot, config, PresenceSurface::Cli);
+    println!("discord: {}", discord.status());
+    thread::sleep(hold);
+    discord.shutdown();
+    Ok(0)
+}
+
+pub(crate) fn write_simulated_session(
+    root: &Path,
+    cwd: &Path,
+    now: DateTime<Utc>,
+) -> Result<PathBuf> {
+    let at = |offset_secs: i64| (now - chrono::Duration::seconds(offset_secs)).to_rfc3339();
+    let cwd = cwd.display().to_string();
+    let session_id = format!("simulated-{}", now.timestamp());
+    let events = [
+        serde_json::json!({
+            "timestamp": at(40),
+            "type": "session_meta",
+            "payload": { "id": session_id, "cwd": cwd },
+        }),
+        serde_json::json!({
+            "timestamp": at(35),
+            "type": "turn_context",
+            "payload": { "cwd": cwd, "model": "gpt-5.4", "effort": "medium" },
+        }),
+        serde_json::json!({
+            "timestamp": at(30),
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "shell_command",
+                "arguments": r#"{"command":"cat README.md"}"#,
+                "call_id": "call_read",
+            },
+        }),
+        serde_json::json!({
+            "timestamp": at(25),
+            "type": "response_item",
+            "payload": { "type": "function_call_output", "call_id": "call_read" },
+        }),
+        serde_json::json!({
+            "timestamp": at(20),
+            "type": "event_msg",
+            "payload": {
+                "type": "token_count",
+                "info": {
+                    "total_token_usage": {
+                        "input_tokens": 12_000,
+                        "cached_input_tokens": 8_000,
+                        "output_tokens": 900,
+                        "total_tokens": 12_900,
+                    },
+                    "last_token_usage": {
+                        "input_tokens": 1_200,
+                        "cached_input_tokens": 800,
+                        "output_tokens": 90,
+                        "total_tokens": 1_290,
+                    },
+                },
+            },
+        }),
+        serde_json::json!({
+            "timestamp": at(1),
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "shell_command",
+                "arguments": r#"{"command":"cargo test"}"#,
+                "call_id": "call_test",
+            },
+        }),
+    ];
+    let content = events
+        .iter()
+        .map(serde_json::Value::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let path = root.join(format!("rollout-{session_id}.jsonl"));
+    std::fs::write(&path, content + "\n")
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+fn simulated_runtime_snapshot(
+    root: &Path,
+    runtime: &RuntimeSettings,
+    config: &PresenceConfig,
+) -> Result<RuntimeSnapshot> {
+    let mut sessions = collect_active_sessions_multi(
+        &[root.to_path_buf()],
+        runtime.stale_threshold,
+        runtime.active_sticky_window,
+        &mut GitBranchCache::new(Duration::from_secs(30)),
+        &mut SessionParseCache::default(),
+        &config.pricing,
+    )?;
+    for activity in sessions
+        .iter_mut()
+        .filter_map(|session| session.activity.as_mut())
+    {
+        activity.apply_tool_activity_map(&config.display.tool_activity_map);
+    }
+    Ok(RuntimeSnapshot::from_sessions(
+        sessions,
+        None,
+        &[],
+        &mut PlanDetector::new(),
+        &config.openai_plan,
+    ))
+}
+
+pub fn doctor(config: &PresenceConfig, stale_secs: Option<u64>) -> Result<u8> {
     let mut issues = 0u8;
     let session_roots = config::sessions_paths();
     let existing_roots: Vec<&PathBuf> = session_roots.iter().filter(|path| path.exists()).collect();
 
     println!("codex-discord-presence doctor");
     println!("config_path: {}", config::config_path().display());
-    print_session_roots("sessions_paths", &session_roots);
+    write_session_roots(&mut io::stdout(), "sessions_paths", &session_roots)?;
 
     if existing_roots.is_empty() {
         issues += 1;
@@ -212,6 +959,20 @@ pub fn doctor(config: &PresenceConfig) -> Result<u8> {
             "[OK] Discovered {} accessible sessions root(s).",
             existing_roots.len()
         );
+        let runtime = status_runtime(stale_secs);
+        let sessions = collect_active_sessions_multi(
+            &session_roots,
+            runtime.stale_threshold,
+            runtime.active_sticky_window,
+            &mut GitBranchCache::new(Duration::from_secs(30)),
+            &mut SessionParseCache::default(),
+            &config.pricing,
+        )?;
+        println!(
+            "[INFO] {} active session(s) with a {}s stale threshold.",
+            sessions.len(),
+            runtime.stale_threshold.as_secs()
+        );
     }
 
     let default_client_id = config.effective_client_id_for_surface(PresenceSurface::Cli);
@@ -262,34 +1023,56 @@ pub fn doctor(config: &PresenceConfig) -> Result<u8> {
     }
 }
 
-fn run_foreground_tui(mut config: PresenceConfig, runtime: RuntimeSettings) -> Result<()> {
+fn run_foreground_tui(
+    config: PresenceConfig,
+    runtime: RuntimeSettings,
+    log_lines: bool,
+) -> Result<()> {
     let stop = install_stop_signal()?;
+    if log_lines {
+        return run_headless_foreground(config, runtime, stop, true);
+    }
     if !io::stdout().is_terminal() {
         if maybe_relaunch_in_terminal()? {
             return Ok(());
         }
-        return run_headless_foreground(config, runtime, stop);
+        return run_headless_foreground(config, runtime, stop, false);
     }
 
-    let mut git_cache = GitBranchCache::new(Duration::from_secs(30));
-    let mut parse_cache = SessionParseCache::default();
-    let mut discord = DiscordPresence::new(config.effective_client_id());
-    let mut metrics_tracker = MetricsTracker::new();
-    let mut plan_detector = PlanDetector::new();
-    let sessions_roots = config::sessions_paths();
-    let runtime_surface = runtime_surface_hint();
+    let mouse = config.display.mouse;
+    let mut plan_picker_selected = plan_preset_index(&config.openai_plan);
+    let mut engine = PresenceEngine::builder(config)
+        .runtime(runtime.clone())
+        .surface(runtime_surface_hint())
+        .build();
     let started = Instant::now();
     let mut last_tick = Instant::now() - runtime.poll_interval;
-    let mut snapshot = RuntimeSnapshot::default();
     let mut last_render_signature = String::new();
     let mut last_render_at = Instant::now() - Duration::from_secs(31);
     let mut force_redraw = true;
     let mut plan_picker_open = false;
-    let mut plan_picker_selected = plan_preset_index(&config.openai_plan);
     let mut privacy_picker_open = false;
     let mut privacy_picker_selected = 0;
-
-    ui::enter_terminal()?;
+    let ui_state_path = ui::UiState::path();
+    let ui::UiState {
+        mut recent_cursor,
+        mut pinned_session_id,
+    } = ui::UiState::load(&ui_state_path);
+    let mut waiting_tracker = WaitingInputTracker::default();
+    let mut banner_file = ui::BannerFile::default();
+    let mut last_interaction = Utc::now();
+    let mut unread_waiting = Vec::new();
+    let mut token_history = TokenDeltaHistory::default();
+    let mut updates_frozen = false;
+    let mut help_open = false;
+    let show_all_sessions = config::show_all_sessions_requested();
+    let mut resume_publish = false;
+    let mut runtime_overrides = RuntimeOverrides::default();
+    let mut recent_filter = String::new();
+    let mut recent_filter_open = false;
+    let mut iterm_badge = ui::ITermBadge::detect();
+
+    ui::enter_terminal(mouse)?;
 
     let mut run = || -> Result<()> {
         loop {
@@ -298,26 +1081,42 @@ fn run_foreground_tui(mut config: PresenceConfig, runtime: RuntimeSettings) -> R
             }
 
             if last_tick.elapsed() >= runtime.poll_interval {
-                if reload_runtime_config(&mut config) {
+                if engine.reload_config() {
                     force_redraw = true;
                     if !plan_picker_open {
-                        plan_picker_selected = plan_preset_index(&config.openai_plan);
+                        plan_picker_selected = plan_preset_index(&engine.config().openai_plan);
+                    }
+                }
+                runtime_overrides.apply(engine.config_mut());
+                engine.collect()?;
+                engine.snapshot_mut().pinned_session_id = pinned_session_id.clone();
+                match presence_update_gate(updates_frozen, resume_publish) {
+                    PresenceUpdateGate::Skip => {}
+                    PresenceUpdateGate::Publish => engine.publish(),
+                    PresenceUpdateGate::ForcePublish => {
+                        engine.force_publish();
+                        resume_publish = false;
                     }
                 }
-                snapshot = collect_runtime_snapshot(
-                    &sessions_roots,
-                    &runtime,
-                    &config,
-                    &mut git_cache,
-                    &mut parse_cache,
-                    &mut metrics_tracker,
-                    &mut plan_detector,
-                )?;
-                publish_runtime_snapshot(&mut discord, &snapshot, &config, runtime_surface);
+                engine.publish_web();
+                let listed_idle_sessions = if show_all_sessions {
+                    engine.inactive_sessions_within(Duration::MAX)
+                } else {
+                    Vec::new()
+                };
+
+                let config = engine.config();
+                let snapshot = engine.snapshot();
+                waiting_tracker.observe(&snapshot.sessions, Utc::now());
+                unread_waiting = waiting_tracker.unread_since(last_interaction);
+                token_history.observe(&snapshot.sessions);
+                iterm_badge.sync(
+                    config.display.iterm_badge,
+                    &iterm_badge_text(snapshot, &config.privacy),
+                );
 
                 let active = snapshot.active_session();
-                let plan_display_label =
-                    snapshot.resolved_plan.label(config.openai_plan.show_price);
+                let plan_display_label = snapshot.resolved_plan.display_label(&config.openai_plan);
                 let plan_status_label = snapshot.resolved_plan.status_label();
                 let fast_mode_label = snapshot.resolved_service_tier.fast_mode_label();
                 let limits_source_label = snapshot
@@ -339,7 +1138,11 @@ fn run_foreground_tui(mut config: PresenceConfig, runtime: RuntimeSettings) -> R
                 let render = RenderData {
                     running_for: started.elapsed(),
                     mode_label: "Smart Foreground",
-                    discord_status: discord.status(),
+                    discord_status: if updates_frozen {
+                        "Paused"
+                    } else {
+                        engine.discord_status()
+                    },
                     client_id_configured: config
                         .effective_client_id_for_surface(PresenceSurface::Cli)
                         .is_some()
@@ -351,7 +1154,11 @@ fn run_foreground_tui(mut config: PresenceConfig, runtime: RuntimeSettings) -> R
                     show_activity: config.privacy.show_activity,
                     show_activity_target: config.privacy.show_activity_target,
                     presence_enabled: config.presence_enabled,
+                    updates_frozen,
+                    help_open,
+                    show_all_sessions,
                     privacy: &config.privacy,
+                    pricing: &config.pricing,
                     presence_layout: &config.display.presence_layout,
                     plan_display_label: plan_display_label.as_str(),
                     plan_status_label: plan_status_label.as_str(),
@@ -359,16 +1166,41 @@ fn run_foreground_tui(mut config: PresenceConfig, runtime: RuntimeSettings) -> R
                     fast_active: snapshot.resolved_service_tier.is_fast(),
                     limits_source_label: limits_source_label.as_str(),
                     limits_updated_label: limits_updated_label.as_str(),
+                    limits_source: snapshot.limits_source.as_ref(),
                     spark_plan_warning,
                     logo_mode: config.display.terminal_logo_mode.clone(),
                     logo_path: config.display.terminal_logo_path.as_deref(),
+                    banner_lines: banner_file.lines_for(config.display.banner_path.as_deref()),
                     desktop_design_label: config.display.desktop_presence_design.label(),
                     banner_phase: ((started.elapsed().as_millis() / 450) % 8) as u8,
                     active,
                     effective_limits: snapshot.effective_limits(),
                     effective_credits: snapshot.effective_credits(),
-                    metrics: metrics_tracker.snapshot(),
+                    metrics: engine.metrics(),
                     sessions: &snapshot.sessions,
+                    idle_sessions: if show_all_sessions {
+                        &listed_idle_sessions
+                    } else {
+                        &snapshot.idle_sessions
+                    },
+                    recent_active_marker: &config.display.recent_active_marker,
+                    recent_line_template: &config.display.recent_line_template,
+                    max_models_shown: config.display.max_models_shown,
+                    show_aggregate_cost: config.display.show_aggregate_cost,
+                    activity_emoji: config.display.activity_emoji,
+                    show_model_rates: config.display.show_model_rates,
+                    min_bar_width: config.display.min_bar_width,
+                    recent_cursor,
+                    pinned_session_id: pinned_session_id.as_deref(),
+                    unread_waiting: &unread_waiting,
+                    token_history: &token_history.deltas,
+                    no_color: no_color_requested(),
+                    theme: config.display.theme,
+                    recent_sort: config.display.recent_sort,
+                    bar_style: config.display.bar_style,
+                    bar_chars: config.display.effective_bar_chars(),
+                    recent_filter: recent_filter.as_str(),
+                    recent_filter_open,
                     plan_picker: plan_picker_open.then_some(ui::PlanPickerView {
                         selected_index: plan_picker_selected,
                         current_index: plan_preset_index(&config.openai_plan),
@@ -405,9 +1237,30 @@ fn run_foreground_tui(mut config: PresenceConfig, runtime: RuntimeSettings) -> R
                         if matches!(key.kind, KeyEventKind::Release) {
                             continue;
                         }
-
-                        if is_master_presence_toggle_key(&key) {
-                            config.toggle_presence()?;
+                        last_interaction = Utc::now();
+                        unread_waiting.clear();
+                        let (config, snapshot) = engine.config_and_snapshot_mut();
+
+                        let action = route_key(
+                            KeyRouteState {
+                                help_open,
+                                recent_filter_open,
+                                picker_open: plan_picker_open || privacy_picker_open,
+                            },
+                            &key,
+                        );
+                        match action {
+                            KeyAction::CloseHelp => help_open = false,
+                            KeyAction::EditRecentFilter => {
+                                recent_filter_open =
+                                    edit_recent_filter(&mut recent_filter, key.code);
+                                recent_cursor = None;
+                            }
+                            KeyAction::OpenHelp => help_open = true,
+                            KeyAction::OpenRecentFilter => recent_filter_open = true,
+                            KeyAction::Other => {}
+                        }
+                        if action != KeyAction::Other {
                             request_redraw(
                                 &mut force_redraw,
                                 &mut last_tick,
@@ -416,11 +1269,75 @@ fn run_foreground_tui(mut config: PresenceConfig, runtime: RuntimeSettings) -> R
                             continue;
                         }
 
-                        if plan_picker_open {
-                            if is_plan_picker_toggle_key(&key)
-                                || (key.code == KeyCode::Esc && key.modifiers.is_empty())
-                            {
-                                plan_picker_open = false;
+                        if is_freeze_toggle_key(&key) && !plan_picker_open && !privacy_picker_open {
+                            updates_frozen = !updates_frozen;
+                            resume_publish = !updates_frozen;
+                            request_redraw(
+                                &mut force_redraw,
+                                &mut last_tick,
+                                runtime.poll_interval,
+                            );
+                            continue;
+                        }
+
+                        if is_privacy_mode_toggle_key(&key)
+                            && !plan_picker_open
+                            && !privacy_picker_open
+                        {
+                            runtime_overrides.privacy_enabled = Some(!config.privacy.enabled);
+                            runtime_overrides.apply(config);
+                            resume_publish = true;
+                            request_redraw(
+                                &mut force_redraw,
+                                &mut last_tick,
+                                runtime.poll_interval,
+                            );
+                            continue;
+                        }
+
+                        if is_activity_target_toggle_key(&key)
+                            && !plan_picker_open
+                            && !privacy_picker_open
+                        {
+                            runtime_overrides.show_activity_target =
+                                Some(!config.privacy.show_activity_target);
+                            runtime_overrides.apply(config);
+                            resume_publish = true;
+                            request_redraw(
+                                &mut force_redraw,
+                                &mut last_tick,
+                                runtime.poll_interval,
+                            );
+                            continue;
+                        }
+
+                        if is_plan_cycle_key(&key) && !plan_picker_open && !privacy_picker_open {
+                            runtime_overrides.plan_tier = Some(config.openai_plan.tier.cycled());
+                            runtime_overrides.apply(config);
+                            resume_publish = true;
+                            request_redraw(
+                                &mut force_redraw,
+                                &mut last_tick,
+                                runtime.poll_interval,
+                            );
+                            continue;
+                        }
+
+                        if is_master_presence_toggle_key(&key) {
+                            config.toggle_presence()?;
+                            request_redraw(
+                                &mut force_redraw,
+                                &mut last_tick,
+                                runtime.poll_interval,
+                            );
+                            continue;
+                        }
+
+                        if plan_picker_open {
+                            if is_plan_picker_toggle_key(&key)
+                                || (key.code == KeyCode::Esc && key.modifiers.is_empty())
+                            {
+                                plan_picker_open = false;
                                 request_redraw(
                                     &mut force_redraw,
                                     &mut last_tick,
@@ -507,6 +1424,7 @@ fn run_foreground_tui(mut config: PresenceConfig, runtime: RuntimeSettings) -> R
                                             plan_picker_selected,
                                         );
                                         config.save()?;
+                                        runtime_overrides.plan_tier = None;
                                         plan_picker_open = false;
                                         request_redraw(
                                             &mut force_redraw,
@@ -613,6 +1531,40 @@ fn run_foreground_tui(mut config: PresenceConfig, runtime: RuntimeSettings) -> R
                                 &mut last_tick,
                                 runtime.poll_interval,
                             );
+                        } else if let Some(delta) = recent_scroll_delta(&key) {
+                            recent_cursor = ui::step_recent_cursor(
+                                recent_cursor,
+                                delta,
+                                ui::visible_recent_sessions(
+                                    &snapshot.sessions,
+                                    config.display.recent_sort,
+                                    &recent_filter,
+                                )
+                                .len(),
+                            );
+                            request_redraw(
+                                &mut force_redraw,
+                                &mut last_tick,
+                                runtime.poll_interval,
+                            );
+                        } else if key.code == KeyCode::Enter && key.modifiers.is_empty() {
+                            let selected = recent_cursor
+                                .and_then(|index| {
+                                    ui::visible_recent_sessions(
+                                        &snapshot.sessions,
+                                        config.display.recent_sort,
+                                        &recent_filter,
+                                    )
+                                    .get(index)
+                                    .copied()
+                                })
+                                .map(|session| session.session_id.clone());
+                            pinned_session_id = toggle_pin(pinned_session_id.take(), selected);
+                            request_redraw(
+                                &mut force_redraw,
+                                &mut last_tick,
+                                runtime.poll_interval,
+                            );
                         } else if key.code == KeyCode::Char('q')
                             || (key.code == KeyCode::Char('c')
                                 && key.modifiers.contains(KeyModifiers::CONTROL))
@@ -620,7 +1572,27 @@ fn run_foreground_tui(mut config: PresenceConfig, runtime: RuntimeSettings) -> R
                             break;
                         }
                     }
+                    Event::Mouse(mouse) => {
+                        if let Some(delta) = mouse_scroll_delta(&mouse) {
+                            recent_cursor = ui::step_recent_cursor(
+                                recent_cursor,
+                                delta,
+                                ui::visible_recent_sessions(
+                                    &engine.snapshot().sessions,
+                                    engine.config().display.recent_sort,
+                                    &recent_filter,
+                                )
+                                .len(),
+                            );
+                            request_redraw(
+                                &mut force_redraw,
+                                &mut last_tick,
+                                runtime.poll_interval,
+                            );
+                        }
+                    }
                     Event::Resize(_, _) => {
+                        recent_cursor = None;
                         request_redraw(&mut force_redraw, &mut last_tick, runtime.poll_interval);
                     }
                     _ => {}
@@ -631,45 +1603,96 @@ fn run_foreground_tui(mut config: PresenceConfig, runtime: RuntimeSettings) -> R
     };
 
     let run_result = run();
-    discord.shutdown();
+    engine.shutdown();
+    iterm_badge.clear();
     let _ = ui::leave_terminal();
+    let ui_state = ui::UiState {
+        recent_cursor,
+        pinned_session_id,
+    };
+    if let Err(err) = ui_state.save(&ui_state_path) {
+        warn!(error = %err, "failed to persist UI state");
+    }
     run_result
 }
 
 fn run_headless_foreground(
-    mut config: PresenceConfig,
+    config: PresenceConfig,
     runtime: RuntimeSettings,
     stop: Arc<AtomicBool>,
+    log_lines: bool,
 ) -> Result<()> {
-    let mut git_cache = GitBranchCache::new(Duration::from_secs(30));
-    let mut parse_cache = SessionParseCache::default();
-    let mut discord = DiscordPresence::new(config.effective_client_id());
-    let mut metrics_tracker = MetricsTracker::new();
-    let mut plan_detector = PlanDetector::new();
-    let sessions_roots = config::sessions_paths();
-    let runtime_surface = runtime_surface_hint();
-    println!("No interactive terminal detected; running in headless foreground mode.");
-    println!("Press Ctrl+C to stop.");
+    let mut engine = PresenceEngine::builder(config)
+        .runtime(runtime)
+        .surface(runtime_surface_hint())
+        .build();
+    if !log_lines {
+        println!("No interactive terminal detected; running in headless foreground mode.");
+        println!("Press Ctrl+C to stop.");
+    }
 
     while !stop.load(Ordering::Relaxed) {
-        reload_runtime_config(&mut config);
-        let snapshot = collect_runtime_snapshot(
-            &sessions_roots,
-            &runtime,
-            &config,
-            &mut git_cache,
-            &mut parse_cache,
-            &mut metrics_tracker,
-            &mut plan_detector,
-        )?;
-        publish_runtime_snapshot(&mut discord, &snapshot, &config, runtime_surface);
-        thread::sleep(runtime.poll_interval);
+        engine.tick()?;
+        if log_lines {
+            println!(
+                "{}",
+                headless_log_line(engine.snapshot(), &engine.config().privacy, &now_local())
+            );
+        }
+        thread::sleep(engine.poll_interval());
     }
 
-    discord.shutdown();
+    engine.shutdown();
     Ok(())
 }
 
+fn headless_log_line(
+    snapshot: &RuntimeSnapshot,
+    privacy: &PrivacyConfig,
+    timestamp: &str,
+) -> String {
+    let Some(active) = snapshot.presence_session(privacy) else {
+        return format!("{timestamp} idle: no active Codex session");
+    };
+    let mut parts = Vec::new();
+    if privacy.show_project_name && privacy.allows_project(&active.project_name) {
+        parts.push(active.project_name.clone());
+    }
+    if privacy.show_activity
+        && let Some(activity) = &active.activity
+    {
+        parts.push(activity.to_text(privacy.show_activity_target));
+    }
+    let limits = snapshot.effective_limits().unwrap_or(&active.limits);
+    for window in [&limits.primary, &limits.secondary].into_iter().flatten() {
+        parts.push(format!(
+            "{} {:.0}% left",
+            codex_presence_core::format_window_label(window.window_minutes),
+            window.remaining_percent
+        ));
+    }
+    if parts.is_empty() {
+        return format!("{timestamp} active Codex session");
+    }
+    format!("{timestamp} {}", parts.join(" | "))
+}
+
+fn iterm_badge_text(snapshot: &RuntimeSnapshot, privacy: &PrivacyConfig) -> String {
+    let Some(active) = snapshot.presence_session(privacy) else {
+        return String::new();
+    };
+    let mut parts = Vec::new();
+    if privacy.show_project_name {
+        parts.push(active.project_name.clone());
+    }
+    if privacy.show_activity
+        && let Some(activity) = &active.activity
+    {
+        parts.push(activity.to_text(privacy.show_activity_target));
+    }
+    parts.join(" · ")
+}
+
 fn maybe_relaunch_in_terminal() -> Result<bool> {
     if env::var_os(RELAUNCH_GUARD_ENV).is_some() {
         return Ok(false);
@@ -714,15 +1737,7 @@ fn relaunch_windows(exe: &str, args: &[String]) -> Result<bool> {
     let command = format!(
         "$env:{RELAUNCH_GUARD_ENV}='1'; Start-Process -FilePath '{escaped_exe}' -ArgumentList {argument_list}"
     );
-    let status = silent_command("powershell")
-        .arg("-NoProfile")
-        .arg("-ExecutionPolicy")
-        .arg("Bypass")
-        .arg("-Command")
-        .arg(command)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status();
+    let status = powershell_command(&command).status();
     Ok(status.map(|s| s.success()).unwrap_or(false))
 }
 
@@ -812,25 +1827,73 @@ fn shell_escape_single(input: &str) -> String {
     format!("'{}'", input.replace('\'', "'\\''"))
 }
 
-#[cfg(windows)]
+pub fn notify_takeover(config: &PresenceConfig, pid: u32) {
+    if !config.display.takeover_toast {
+        return;
+    }
+    #[cfg(windows)]
+    {
+        let spawned = powershell_command(&takeover_toast_command(pid)).spawn();
+        if let Err(err) = spawned {
+            warn!(error = %err, "failed to show takeover notification");
+        }
+    }
+    #[cfg(not(windows))]
+    let _ = pid;
+}
+
+#[cfg(any(windows, test))]
+fn powershell_command(script: &str) -> Command {
+    let mut command = silent_command("powershell");
+    command
+        .args([
+            "-NoProfile",
+            "-ExecutionPolicy",
+            "Bypass",
+            "-Command",
+            script,
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    command
+}
+
+#[cfg(any(windows, test))]
+fn takeover_toast_command(pid: u32) -> String {
+    let title = escape_powershell_single_quoted("Codex Discord Presence");
+    let message = escape_powershell_single_quoted(&format!("Presence taken over from PID {pid}"));
+    let app_id = escape_powershell_single_quoted(TOAST_APP_ID);
+    [
+        "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null".to_string(),
+        "$template = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02)".to_string(),
+        "$text = $template.GetElementsByTagName('text')".to_string(),
+        format!("$text.Item(0).AppendChild($template.CreateTextNode('{title}')) > $null"),
+        format!("$text.Item(1).AppendChild($template.CreateTextNode('{message}')) > $null"),
+        format!(
+            "[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{app_id}').Show([Windows.UI.Notifications.ToastNotification]::new($template))"
+        ),
+    ]
+    .join("; ")
+}
+
+#[cfg(any(windows, test))]
 fn escape_powershell_single_quoted(input: &str) -> String {
     input.replace('\'', "''")
 }
 
 fn run_codex_wrapper(
-    mut config: PresenceConfig,
+    config: PresenceConfig,
     runtime: RuntimeSettings,
     args: Vec<String>,
 ) -> Result<()> {
     let stop = install_stop_signal()?;
+    let child_cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let child_started_at = Utc::now();
     let mut child = spawn_codex_child(args)?;
-    let mut git_cache = GitBranchCache::new(Duration::from_secs(30));
-    let mut parse_cache = SessionParseCache::default();
-    let mut discord = DiscordPresence::new(config.effective_client_id());
-    let mut metrics_tracker = MetricsTracker::new();
-    let mut plan_detector = PlanDetector::new();
-    let sessions_roots = config::sessions_paths();
-    let runtime_surface = PresenceSurface::Desktop;
+    let mut engine = PresenceEngine::builder(config)
+        .runtime(runtime)
+        .surface(PresenceSurface::Desktop)
+        .build();
 
     println!("codex child started; Discord presence tracking is active.");
 
@@ -840,17 +1903,26 @@ fn run_codex_wrapper(
             break;
         }
 
-        reload_runtime_config(&mut config);
-        let snapshot = collect_runtime_snapshot(
-            &sessions_roots,
-            &runtime,
-            &config,
-            &mut git_cache,
-            &mut parse_cache,
-            &mut metrics_tracker,
-            &mut plan_detector,
-        )?;
-        publish_runtime_snapshot(&mut discord, &snapshot, &config, runtime_surface);
+        engine.reload_config();
+        engine.collect()?;
+        let config = engine.config();
+        let fallback = (engine.snapshot().active_session().is_none()
+            && config.display.codex_child_fallback)
+            .then(|| {
+                codex_child_fallback_session(
+                    child.id(),
+                    &child_cwd,
+                    child_started_at,
+                    &config.privacy,
+                )
+            })
+            .filter(|session| config.privacy.allows_project(&session.project_name));
+        if let Some(fallback) = fallback {
+            engine.publish_session(&fallback, PresenceSurface::Cli);
+        } else {
+            engine.publish();
+        }
+        engine.publish_web();
 
         if let Some(status) = child
             .try_wait()
@@ -860,13 +1932,99 @@ fn run_codex_wrapper(
             break;
         }
 
-        thread::sleep(runtime.poll_interval);
+        thread::sleep(engine.poll_interval());
     }
 
-    discord.shutdown();
+    engine.shutdown();
     Ok(())
 }
 
+fn codex_child_fallback_session(
+    pid: u32,
+    cwd: &Path,
+    started_at: DateTime<Utc>,
+    privacy: &PrivacyConfig,
+) -> CodexSessionSnapshot {
+    let project_name = cwd
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| !name.is_empty())
+        .map(ToString::to_string)
+        .unwrap_or_else(|| cwd.display().to_string());
+    let target = if privacy.show_project_name {
+        format!("codex in {project_name}")
+    } else {
+        "codex".to_string()
+    };
+    CodexSessionSnapshot {
+        session_id: format!("codex-child:{pid}"),
+        cwd: cwd.to_path_buf(),
+        project_name: project_name.clone(),
+        git_branch: None,
+        originator: Some("codex cli".to_string()),
+        source: Some("cli".to_string()),
+        model: None,
+        reasoning_effort: None,
+        speed: SessionSpeed::default(),
+        approval_policy: None,
+        sandbox_policy: None,
+        session_total_tokens: None,
+        last_turn_tokens: None,
+        session_delta_tokens: None,
+        resume_count: 0,
+        input_tokens_total: 0,
+        cached_input_tokens_total: 0,
+        output_tokens_total: 0,
+        reasoning_output_tokens_total: 0,
+        last_input_tokens: None,
+        last_cached_input_tokens: None,
+        last_output_tokens: None,
+        total_cost_usd: 0.0,
+        known_cost_usd: None,
+        cost_breakdown: TokenCostBreakdown::default(),
+        pricing_source: PricingSource::Unavailable,
+        pricing_status: PricingStatus::Unavailable,
+        cost_attribution: CostAttribution::default(),
+        cost_breakdown_reconciled: false,
+        context_window: None,
+        limits: RateLimits::default(),
+        rate_limit_envelopes: Vec::new(),
+        activity: Some(SessionActivitySnapshot {
+            kind: SessionActivityKind::RunningCommand,
+            target: Some(target),
+            tool: Some("codex".to_string()),
+            observed_at: Some(Utc::now()),
+            last_active_at: Some(Utc::now()),
+            ..SessionActivitySnapshot::default()
+        }),
+        started_at: Some(started_at),
+        last_token_event_at: None,
+        last_activity: SystemTime::now(),
+        source_file: PathBuf::new(),
+        pid: None,
+        edit_counts: BTreeMap::new(),
+    }
+}
+
+pub(crate) fn publish_session(
+    discord: &mut DiscordPresence,
+    snapshot: &RuntimeSnapshot,
+    session: &CodexSessionSnapshot,
+    config: &PresenceConfig,
+    surface: PresenceSurface,
+) {
+    if let Err(err) = discord.update(
+        Some(session),
+        snapshot.limits_source.as_ref(),
+        &snapshot.resolved_plan,
+        &snapshot.resolved_service_tier,
+        config,
+        surface,
+    ) {
+        debug!(error = %err, "discord presence update failed");
+    }
+}
+
 fn spawn_codex_child(args: Vec<String>) -> Result<Child> {
     let mut command = Command::new("codex");
     command
@@ -879,7 +2037,7 @@ fn spawn_codex_child(args: Vec<String>) -> Result<Child> {
         .context("failed to spawn `codex` child process")
 }
 
-fn collect_runtime_snapshot(
+pub(crate) fn collect_runtime_snapshot(
     sessions_roots: &[PathBuf],
     runtime: &RuntimeSettings,
     config: &PresenceConfig,
@@ -902,27 +2060,72 @@ fn collect_runtime_snapshot(
         runtime.active_sticky_window,
         &config.pricing,
     ));
+    for activity in sessions
+        .iter_mut()
+        .filter_map(|session| session.activity.as_mut())
+    {
+        activity.apply_tool_activity_map(&config.display.tool_activity_map);
+    }
+    metrics_tracker.set_monthly_budget_usd(config.pricing.monthly_budget_usd);
+    metrics_tracker.set_window(config.metrics.window);
     metrics_tracker.update(&sessions);
-    metrics_tracker.persist_if_due();
-    let cached_limits = parse_cache.latest_limits_source();
+    metrics_tracker.persist_if_due(config.display.max_models_shown);
+    let active = preferred_active_session(&sessions);
+    let cached_limits = aggregated_limits_source(
+        followed_limits_source(active, config).or_else(|| parse_cache.latest_limits_source(active)),
+        &sessions,
+        config.display.limits_aggregate,
+    );
     let cached_envelopes = parse_cache.rate_limit_envelopes();
-    Ok(RuntimeSnapshot::from_sessions(
-        sessions,
-        cached_limits,
-        &cached_envelopes,
-        plan_detector,
-        &config.openai_plan,
-    ))
+    let idle_sessions = idle_sessions_for_display(config, parse_cache, &sessions);
+    Ok(RuntimeSnapshot {
+        idle_sessions,
+        ..RuntimeSnapshot::from_sessions(
+            sessions,
+            cached_limits,
+            &cached_envelopes,
+            plan_detector,
+            &config.openai_plan,
+        )
+    })
+}
+
+fn idle_sessions_for_display(
+    config: &PresenceConfig,
+    parse_cache: &SessionParseCache,
+    sessions: &[CodexSessionSnapshot],
+) -> Vec<CodexSessionSnapshot> {
+    if !config.display.show_idle_sessions {
+        return Vec::new();
+    }
+    inactive_sessions_within(config, parse_cache, sessions, IDLE_SESSION_LIST_WINDOW)
 }
 
-fn publish_runtime_snapshot(
+pub(crate) fn inactive_sessions_within(
+    config: &PresenceConfig,
+    parse_cache: &SessionParseCache,
+    sessions: &[CodexSessionSnapshot],
+    window: Duration,
+) -> Vec<CodexSessionSnapshot> {
+    let mut idle = parse_cache.idle_sessions(sessions, window);
+    for activity in idle
+        .iter_mut()
+        .filter_map(|session| session.activity.as_mut())
+    {
+        activity.apply_tool_activity_map(&config.display.tool_activity_map);
+    }
+    idle
+}
+
+pub(crate) fn publish_runtime_snapshot(
     discord: &mut DiscordPresence,
     snapshot: &RuntimeSnapshot,
     config: &PresenceConfig,
     fallback_surface: PresenceSurface,
 ) {
+    discord.set_sessions(&snapshot.sessions, &config.privacy);
     if let Err(err) = discord.update(
-        snapshot.active_session(),
+        snapshot.presence_session(&config.privacy),
         snapshot.limits_source.as_ref(),
         &snapshot.resolved_plan,
         &snapshot.resolved_service_tier,
@@ -933,6 +2136,102 @@ fn publish_runtime_snapshot(
     }
 }
 
+pub(crate) fn publish_web_state(
+    snapshot: &RuntimeSnapshot,
+    metrics: Option<&MetricsSnapshot>,
+    config: &PresenceConfig,
+    fallback_surface: PresenceSurface,
+) {
+    if !web::is_enabled() {
+        return;
+    }
+    let privacy = &config.privacy;
+    let visible = |flag: bool| !privacy.enabled && flag;
+    let session = snapshot.presence_session(privacy);
+    let presence = session.map(|session| {
+        discord::active_presence_presentation(
+            session.detected_surface().unwrap_or(fallback_surface),
+            session,
+            snapshot.effective_limits(),
+            &snapshot.resolved_plan,
+            &snapshot.resolved_service_tier,
+            config,
+        )
+    });
+    web::publish(WebState {
+        updated_at: Some(Utc::now()),
+        presence_enabled: config.presence_enabled,
+        project: session
+            .filter(|_| visible(privacy.show_project_name))
+            .map(|session| session.project_name.clone()),
+        model: session
+            .filter(|_| visible(privacy.show_model))
+            .and_then(|session| session.model.clone()),
+        presence,
+        limits: snapshot
+            .effective_limits()
+            .filter(|_| visible(privacy.show_limits))
+            .cloned(),
+        metrics: metrics.filter(|_| visible(privacy.show_cost)).cloned(),
+        cost: metrics
+            .filter(|_| visible(privacy.show_cost))
+            .map(|metrics| {
+                format_metrics_cost_in(
+                    &metrics.totals,
+                    config.pricing.display_currency,
+                    config.pricing.usd_rate,
+                )
+            }),
+    });
+}
+
+fn aggregated_limits_source(
+    selection: Option<EffectiveLimitSelection>,
+    sessions: &[CodexSessionSnapshot],
+    aggregate: LimitsAggregate,
+) -> Option<EffectiveLimitSelection> {
+    if aggregate == LimitsAggregate::Latest {
+        return selection;
+    }
+    let mut selection =
+        selection.or_else(|| latest_limits_source(sessions, preferred_active_session(sessions)))?;
+    let aggregate = min_remaining_limits(
+        sessions
+            .iter()
+            .map(|session| (session.session_id.as_str(), &session.limits))
+            .chain([(selection.source_session_id.as_str(), &selection.limits)]),
+        Utc::now(),
+    );
+    let window_sources: Vec<LimitWindowSource> = [
+        (&aggregate.limits.primary, aggregate.primary_source),
+        (&aggregate.limits.secondary, aggregate.secondary_source),
+    ]
+    .into_iter()
+    .filter_map(|(window, source)| {
+        Some(LimitWindowSource {
+            window_minutes: window.as_ref()?.window_minutes,
+            session_id: source?.to_string(),
+        })
+    })
+    .collect();
+    let limits = aggregate.limits.clone();
+    if let Some(first) = window_sources.first() {
+        selection.source_session_id = first.session_id.clone();
+    }
+    selection.limits = limits;
+    selection.window_sources = window_sources;
+    Some(selection)
+}
+
+fn followed_limits_source(
+    active: Option<&CodexSessionSnapshot>,
+    config: &PresenceConfig,
+) -> Option<EffectiveLimitSelection> {
+    active
+        .filter(|_| config.display.limits_follow_active)
+        .and_then(session_limits_source)
+}
+
 fn reload_runtime_config(config: &mut PresenceConfig) -> bool {
     config.reload_from_disk()
 }
@@ -1079,32 +2378,37 @@ fn process_lineage_text() -> Option<String> {
     (!lineage.is_empty()).then(|| lineage.join("\n"))
 }
 
-fn print_active_summary(
+fn write_active_summary(
+    out: &mut impl Write,
     active: &CodexSessionSnapshot,
     effective_limits: Option<&RateLimits>,
     limits_source: Option<&EffectiveLimitSelection>,
     resolved_plan: &ResolvedPlan,
     resolved_service_tier: &ResolvedServiceTier,
     config: &PresenceConfig,
-) {
-    let plan_display_label = resolved_plan.label(config.openai_plan.show_price);
-    println!("active_session:");
-    println!("  session_id: {}", active.session_id);
-    println!("  project: {}", active.project_name);
-    println!("  path: {}", active.cwd.display());
+) -> io::Result<()> {
+    let plan_display_label = resolved_plan.display_label(&config.openai_plan);
+    writeln!(out, "active_session:")?;
+    writeln!(out, "  session_id: {}", active.session_id)?;
+    writeln!(out, "  project: {}", active.project_name)?;
+    writeln!(out, "  path: {}", active.cwd.display())?;
     if let Some(started_at) = active.started_at.as_ref() {
         let started_at_iso = started_at.to_rfc3339();
         let started_at_since = format_since(Some(started_at.to_owned()));
-        println!("  started_at: {started_at_iso} ({started_at_since})");
+        writeln!(out, "  started_at: {started_at_iso} ({started_at_since})")?;
     } else {
-        println!("  started_at: n/a");
+        writeln!(out, "  started_at: n/a")?;
     }
     let last_activity_dt: DateTime<Utc> = DateTime::<Utc>::from(active.last_activity);
     let last_activity_iso = last_activity_dt.to_rfc3339();
     let last_activity_since = format_since(Some(last_activity_dt));
-    println!("  last_activity: {last_activity_iso} ({last_activity_since})");
-    println!("  recency_source: {}", recency_source_label(active));
-    println!(
+    writeln!(
+        out,
+        "  last_activity: {last_activity_iso} ({last_activity_since})"
+    )?;
+    writeln!(out, "  recency_source: {}", recency_source_label(active))?;
+    writeln!(
+        out,
         "  model: {} | {}",
         format_model_display(
             active.model.as_deref().unwrap_or("unknown"),
@@ -1112,105 +2416,125 @@ fn print_active_summary(
             resolved_service_tier.is_fast(),
         ),
         plan_display_label
-    );
-    println!("  plan: {}", resolved_plan.status_label());
-    println!("  fast_mode: {}", resolved_service_tier.fast_mode_label());
-    println!("  speed_known: {}", active.speed.known);
-    println!("  speed_source: {:?}", active.speed.source);
+    )?;
+    writeln!(out, "  plan: {}", resolved_plan.status_label())?;
+    writeln!(
+        out,
+        "  fast_mode: {}",
+        resolved_service_tier.fast_mode_label()
+    )?;
+    writeln!(out, "  speed_known: {}", active.speed.known)?;
+    writeln!(out, "  speed_source: {:?}", active.speed.source)?;
     if let Some(raw_tier) = resolved_service_tier.raw_tier.as_deref() {
-        println!("  service_tier: {raw_tier}");
+        writeln!(out, "  service_tier: {raw_tier}")?;
     }
     if let Some(reasoning_effort) = active.reasoning_effort {
-        println!("  reasoning_effort: {}", reasoning_effort.label());
+        writeln!(out, "  reasoning_effort: {}", reasoning_effort.label())?;
     } else {
-        println!("  reasoning_effort: n/a");
+        writeln!(out, "  reasoning_effort: n/a")?;
     }
-    println!(
+    writeln!(
+        out,
         "  branch: {}",
         active.git_branch.as_deref().unwrap_or("n/a")
-    );
+    )?;
     if config.privacy.show_activity
         && let Some(activity) = &active.activity
     {
-        println!(
+        writeln!(
+            out,
             "  activity: {}",
             activity.to_text(config.privacy.show_activity_target)
-        );
+        )?;
     }
-    println!("  pricing_status: {:?}", active.pricing_status);
-    println!("  pricing_source: {:?}", active.pricing_source);
-    println!("  cost_attribution: {:?}", active.cost_attribution);
-    println!(
+    writeln!(out, "  pricing_status: {:?}", active.pricing_status)?;
+    writeln!(out, "  pricing_source: {:?}", active.pricing_source)?;
+    writeln!(out, "  cost_attribution: {:?}", active.cost_attribution)?;
+    writeln!(
+        out,
         "  incomplete_cost: {}",
         active.pricing_status != crate::cost::PricingStatus::Exact
-    );
+    )?;
     if let Some(cost) = format_presentable_cost(active.known_cost_usd, active.pricing_status) {
-        println!("  cost: {cost}");
+        writeln!(out, "  cost: {cost}")?;
     }
-    println!(
+    writeln!(
+        out,
         "  tokens io: in {} | cached {} | out {}",
         crate::util::format_tokens(active.input_tokens_total),
         crate::util::format_tokens(active.cached_input_tokens_total),
         crate::util::format_tokens(active.output_tokens_total),
-    );
-    println!(
+    )?;
+    writeln!(
+        out,
         "  {}",
         format_token_triplet(
             active.session_delta_tokens,
             active.last_turn_tokens,
             active.session_total_tokens
         )
-    );
+    )?;
+    writeln!(out, "  resume_count: {}", active.resume_count)?;
     if let Some(context) = &active.context_window {
-        println!(
+        writeln!(
+            out,
             "  context: {}/{} used ({:.0}% used)",
             crate::util::format_tokens(context.used_tokens),
             crate::util::format_tokens(context.window_tokens),
             (100.0 - context.remaining_percent).clamp(0.0, 100.0)
-        );
-        println!(
+        )?;
+        writeln!(
+            out,
             "  context_window_usable: {}",
             crate::util::format_tokens(context.window_tokens)
-        );
-        println!(
+        )?;
+        writeln!(
+            out,
             "  context_window_raw: {}",
             crate::util::format_tokens(context.raw_window_tokens)
-        );
-        println!("  context_source: {}", context.source.label());
-        println!("  context_raw_source: {}", context.raw_source.label());
+        )?;
+        writeln!(out, "  context_source: {}", context.source.label())?;
+        writeln!(out, "  context_raw_source: {}", context.raw_source.label())?;
         if let Some(percent) = context.effective_percent {
-            println!("  context_effective_percent: {percent}%");
+            writeln!(out, "  context_effective_percent: {percent}%")?;
         }
     } else {
-        println!("  context: n/a");
+        writeln!(out, "  context: n/a")?;
     }
     if let Some(source) = limits_source {
-        println!("  limits source: {}", source.source_label());
-        println!("  limits updated: {}", format_since(source.observed_at));
+        writeln!(out, "  limits source: {}", source.source_label())?;
+        writeln!(
+            out,
+            "  limits updated: {}",
+            format_since(source.observed_at)
+        )?;
     }
 
     let limits = effective_limits.unwrap_or(&active.limits);
     if let Some(primary) = &limits.primary {
-        println!(
+        writeln!(
+            out,
             "  {} remaining: {:.0}% (reset {})",
             codex_presence_core::format_window_label(primary.window_minutes),
             primary.remaining_percent,
             format_time_until(primary.resets_at)
-        );
+        )?;
     }
     if let Some(secondary) = &limits.secondary {
-        println!(
+        writeln!(
+            out,
             "  {} remaining: {:.0}% (reset {})",
             codex_presence_core::format_window_label(secondary.window_minutes),
             secondary.remaining_percent,
             format_time_until(secondary.resets_at)
-        );
+        )?;
     }
     if let Some(model) = active.model.as_deref()
         && !is_model_allowed_for_plan(model, resolved_plan.tier)
     {
-        println!("  model gate: Spark is Pro-only (telemetry anomaly)");
+        writeln!(out, "  model gate: Spark is Pro-only (telemetry anomaly)")?;
     }
+    Ok(())
 }
 
 fn command_available(program: &str) -> bool {
@@ -1223,11 +2547,12 @@ fn command_available(program: &str) -> bool {
         .unwrap_or(false)
 }
 
-fn print_session_roots(label: &str, paths: &[PathBuf]) {
-    println!("{label}:");
+fn write_session_roots(out: &mut impl Write, label: &str, paths: &[PathBuf]) -> io::Result<()> {
+    writeln!(out, "{label}:")?;
     for path in paths {
-        println!("  - {}", path.display());
+        writeln!(out, "  - {}", path.display())?;
     }
+    Ok(())
 }
 
 fn recency_source_label(active: &CodexSessionSnapshot) -> &'static str {
@@ -1264,6 +2589,23 @@ fn datetime_to_system_time(ts: DateTime<Utc>) -> Option<SystemTime> {
         .checked_add(Duration::from_nanos(nanos))
 }
 
+static NO_COLOR_FLAG: AtomicBool = AtomicBool::new(false);
+
+pub fn set_no_color_flag(enabled: bool) {
+    NO_COLOR_FLAG.store(enabled, Ordering::Relaxed);
+}
+
+fn no_color_requested() -> bool {
+    no_color_from(
+        NO_COLOR_FLAG.load(Ordering::Relaxed),
+        env::var_os("NO_COLOR").as_deref(),
+    )
+}
+
+fn no_color_from(flag: bool, env_value: Option<&std::ffi::OsStr>) -> bool {
+    flag || env_value.is_some_and(|value| !value.is_empty())
+}
+
 fn install_stop_signal() -> Result<Arc<AtomicBool>> {
     let stop = Arc::new(AtomicBool::new(false));
     let flag = Arc::clone(&stop);
@@ -1274,6 +2616,36 @@ fn install_stop_signal() -> Result<Arc<AtomicBool>> {
     Ok(stop)
 }
 
+fn toggle_pin(current: Option<String>, selected: Option<String>) -> Option<String> {
+    match selected {
+        Some(selected) if current.as_deref() == Some(selected.as_str()) => None,
+        Some(selected) => Some(selected),
+        None => current,
+    }
+}
+
+fn recent_scroll_delta(key: &KeyEvent) -> Option<isize> {
+    if !matches!(key.kind, KeyEventKind::Press) || !key.modifiers.is_empty() {
+        return None;
+    }
+    let page = ui::RECENT_PAGE_ROWS as isize;
+    match key.code {
+        KeyCode::Up => Some(-1),
+        KeyCode::Down => Some(1),
+        KeyCode::PageUp => Some(-page),
+        KeyCode::PageDown => Some(page),
+        _ => None,
+    }
+}
+
+fn mouse_scroll_delta(mouse: &MouseEvent) -> Option<isize> {
+    match mouse.kind {
+        MouseEventKind::ScrollUp => Some(-1),
+        MouseEventKind::ScrollDown => Some(1),
+        _ => None,
+    }
+}
+
 fn is_plan_picker_toggle_key(key: &KeyEvent) -> bool {
     if !matches!(key.kind, KeyEventKind::Press) {
         return false;
@@ -1307,11 +2679,162 @@ fn is_privacy_picker_toggle_key(key: &KeyEvent) -> bool {
         && !key.modifiers.contains(KeyModifiers::SUPER)
 }
 
-fn is_master_presence_toggle_key(key: &KeyEvent) -> bool {
-    if !matches!(key.kind, KeyEventKind::Press) {
-        return false;
-    }
-
+fn is_help_toggle_key(key: &KeyEvent) -> bool {
+    matches!(key.kind, KeyEventKind::Press)
+        && key.code == KeyCode::Char('?')
+        && !key.modifiers.contains(KeyModifiers::CONTROL)
+        && !key.modifiers.contains(KeyModifiers::ALT)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KeyRouteState {
+    help_open: bool,
+    recent_filter_open: bool,
+    picker_open: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyAction {
+    CloseHelp,
+    EditRecentFilter,
+    OpenHelp,
+    OpenRecentFilter,
+    Other,
+}
+
+fn route_key(state: KeyRouteState, key: &KeyEvent) -> KeyAction {
+    if state.help_open {
+        KeyAction::CloseHelp
+    } else if state.recent_filter_open
+        && !key.modifiers.contains(KeyModifiers::CONTROL)
+        && !key.modifiers.contains(KeyModifiers::ALT)
+    {
+        KeyAction::EditRecentFilter
+    } else if state.picker_open {
+        KeyAction::Other
+    } else if is_help_toggle_key(key) {
+        KeyAction::OpenHelp
+    } else if is_recent_filter_key(key) {
+        KeyAction::OpenRecentFilter
+    } else {
+        KeyAction::Other
+    }
+}
+
+fn edit_recent_filter(filter: &mut String, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Esc => {
+            filter.clear();
+            false
+        }
+        KeyCode::Enter => false,
+        KeyCode::Backspace => {
+            filter.pop();
+            true
+        }
+        KeyCode::Char(ch) => {
+            filter.push(ch);
+            true
+        }
+        _ => true,
+    }
+}
+
+fn is_freeze_toggle_key(key: &KeyEvent) -> bool {
+    if !matches!(key.kind, KeyEventKind::Press) {
+        return false;
+    }
+
+    matches!(key.code, KeyCode::Char('f') | KeyCode::Char('F'))
+        && !key.modifiers.contains(KeyModifiers::CONTROL)
+        && !key.modifiers.contains(KeyModifiers::ALT)
+        && !key.modifiers.contains(KeyModifiers::SUPER)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PresenceUpdateGate {
+    Skip,
+    Publish,
+    ForcePublish,
+}
+
+fn presence_update_gate(updates_frozen: bool, resume_publish: bool) -> PresenceUpdateGate {
+    if updates_frozen {
+        PresenceUpdateGate::Skip
+    } else if resume_publish {
+        PresenceUpdateGate::ForcePublish
+    } else {
+        PresenceUpdateGate::Publish
+    }
+}
+
+fn is_privacy_mode_toggle_key(key: &KeyEvent) -> bool {
+    if !matches!(key.kind, KeyEventKind::Press) {
+        return false;
+    }
+
+    matches!(key.code, KeyCode::Char('h') | KeyCode::Char('H'))
+        && !key.modifiers.contains(KeyModifiers::CONTROL)
+        && !key.modifiers.contains(KeyModifiers::ALT)
+        && !key.modifiers.contains(KeyModifiers::SUPER)
+}
+
+fn is_activity_target_toggle_key(key: &KeyEvent) -> bool {
+    if !matches!(key.kind, KeyEventKind::Press) {
+        return false;
+    }
+
+    matches!(key.code, KeyCode::Char('t') | KeyCode::Char('T'))
+        && !key.modifiers.contains(KeyModifiers::CONTROL)
+        && !key.modifiers.contains(KeyModifiers::ALT)
+        && !key.modifiers.contains(KeyModifiers::SUPER)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct RuntimeOverrides {
+    privacy_enabled: Option<bool>,
+    show_activity_target: Option<bool>,
+    plan_tier: Option<OpenAiPlanTier>,
+}
+
+impl RuntimeOverrides {
+    fn apply(self, config: &mut PresenceConfig) {
+        if let Some(enabled) = self.privacy_enabled {
+            config.privacy.enabled = enabled;
+        }
+        if let Some(show_activity_target) = self.show_activity_target {
+            config.privacy.show_activity_target = show_activity_target;
+        }
+        if let Some(tier) = self.plan_tier {
+            config.openai_plan.mode = OpenAiPlanMode::Manual;
+            config.openai_plan.tier = tier;
+        }
+    }
+}
+
+fn is_plan_cycle_key(key: &KeyEvent) -> bool {
+    if !matches!(key.kind, KeyEventKind::Press) {
+        return false;
+    }
+
+    matches!(key.code, KeyCode::Char('l') | KeyCode::Char('L'))
+        && !key.modifiers.contains(KeyModifiers::CONTROL)
+        && !key.modifiers.contains(KeyModifiers::ALT)
+        && !key.modifiers.contains(KeyModifiers::SUPER)
+}
+
+fn is_recent_filter_key(key: &KeyEvent) -> bool {
+    matches!(key.kind, KeyEventKind::Press)
+        && key.code == KeyCode::Char('/')
+        && !key.modifiers.contains(KeyModifiers::CONTROL)
+        && !key.modifiers.contains(KeyModifiers::ALT)
+}
+
+fn is_master_presence_toggle_key(key: &KeyEvent) -> bool {
+    if !matches!(key.kind, KeyEventKind::Press) {
+        return false;
+    }
+
     matches!(key.code, KeyCode::Char('m') | KeyCode::Char('M'))
         && !key.modifiers.contains(KeyModifiers::CONTROL)
         && !key.modifiers.contains(KeyModifiers::ALT)
@@ -1327,6 +2850,407 @@ fn request_redraw(force_redraw: &mut bool, last_tick: &mut Instant, poll_interva
 mod tests {
     use super::*;
 
+    fn test_session(session_id: &str) -> CodexSessionSnapshot {
+        CodexSessionSnapshot {
+            session_id: session_id.to_string(),
+            cwd: PathBuf::from("/tmp/app"),
+            project_name: "app".to_string(),
+            git_branch: None,
+            originator: Some("codex cli".to_string()),
+            source: Some("cli".to_string()),
+            model: None,
+            reasoning_effort: None,
+            speed: SessionSpeed::default(),
+            approval_policy: None,
+            sandbox_policy: None,
+            session_total_tokens: None,
+            last_turn_tokens: None,
+            session_delta_tokens: None,
+            resume_count: 0,
+            input_tokens_total: 0,
+            cached_input_tokens_total: 0,
+            output_tokens_total: 0,
+            reasoning_output_tokens_total: 0,
+            last_input_tokens: None,
+            last_cached_input_tokens: None,
+            last_output_tokens: None,
+            total_cost_usd: 0.0,
+            known_cost_usd: None,
+            cost_breakdown: TokenCostBreakdown::default(),
+            pricing_source: PricingSource::Unavailable,
+            pricing_status: PricingStatus::Unavailable,
+            cost_attribution: CostAttribution::default(),
+            cost_breakdown_reconciled: false,
+            context_window: None,
+            limits: RateLimits::default(),
+            rate_limit_envelopes: Vec::new(),
+            activity: None,
+            started_at: Some(Utc::now()),
+            last_token_event_at: None,
+            last_activity: SystemTime::now(),
+            source_file: PathBuf::new(),
+            pid: None,
+            edit_counts: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn no_color_flag_or_non_empty_env_disables_color() {
+        assert!(no_color_from(true, None));
+        assert!(no_color_from(false, Some(std::ffi::OsStr::new("1"))));
+        assert!(!no_color_from(false, Some(std::ffi::OsStr::new(""))));
+        assert!(!no_color_from(false, None));
+    }
+
+    #[test]
+    fn waiting_sessions_are_unread_only_after_last_interaction() {
+        let base = Utc::now();
+        let waiting_at = |session_id: &str, seconds: i64| {
+            let mut session = test_session(session_id);
+            session.activity = Some(SessionActivitySnapshot {
+                kind: SessionActivityKind::WaitingInput,
+                observed_at: Some(base + chrono::Duration::seconds(seconds)),
+                ..SessionActivitySnapshot::default()
+            });
+            session
+        };
+        let mut tracker = WaitingInputTracker::default();
+        let last_interaction = base + chrono::Duration::seconds(10);
+
+        tracker.observe(&[waiting_at("before", 5), waiting_at("after", 20)], base);
+        assert_eq!(tracker.unread_since(last_interaction), vec!["after"]);
+        assert!(
+            tracker
+                .unread_since(base + chrono::Duration::seconds(30))
+                .is_empty()
+        );
+
+        let mut resumed = waiting_at("after", 40);
+        resumed.activity = None;
+        tracker.observe(&[waiting_at("before", 5), resumed], base);
+        assert!(tracker.unread_since(last_interaction).is_empty());
+
+        tracker.observe(&[waiting_at("after", 50)], base);
+        assert_eq!(tracker.unread_since(last_interaction), vec!["after"]);
+    }
+
+    #[test]
+    fn status_json_exposes_expected_top_level_keys() {
+        let config = PresenceConfig::default();
+        let session = test_session("codex-child:1");
+        let status = StatusContext {
+            session_roots: vec![PathBuf::from("/tmp/sessions")],
+            diagnostics: SessionCollectionDiagnostics::default(),
+            is_running: true,
+            running_pid: Some(42),
+            snapshot: RuntimeSnapshot {
+                sessions: vec![session],
+                ..RuntimeSnapshot::default()
+            },
+        };
+
+        let value = serde_json::to_value(status_report(&config, &status)).expect("json");
+        let object = value.as_object().expect("object");
+        for key in [
+            "running",
+            "pid",
+            "config_path",
+            "presence_enabled",
+            "session_roots",
+            "discord_client_id_default",
+            "discord_client_id_desktop",
+            "active_sessions",
+            "active_session",
+            "limits_source",
+        ] {
+            assert!(object.contains_key(key), "missing {key}");
+        }
+        assert_eq!(value["pid"], 42);
+        assert_eq!(value["active_session"]["project"], "app");
+        assert!(value["limits_source"].is_null());
+    }
+
+    #[test]
+    fn session_root_reports_cover_existing_and_missing_roots() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let populated = temp.path().join("sessions");
+        let day = populated.join("2026").join("03").join("14");
+        std::fs::create_dir_all(&day).expect("day dir");
+        std::fs::write(day.join("rollout-a.jsonl"), "").expect("write a");
+        std::fs::write(day.join("rollout-b.jsonl"), "").expect("write b");
+        std::fs::write(day.join("notes.txt"), "").expect("write notes");
+        let empty = temp.path().join("empty");
+        std::fs::create_dir_all(&empty).expect("empty dir");
+        let missing = temp.path().join("missing");
+
+        let reports: Vec<SessionRootReport> = [&populated, &empty, &missing]
+            .into_iter()
+            .map(|path| inspect_session_root(path))
+            .collect();
+
+        assert_eq!(
+            reports
+                .iter()
+                .map(|report| (report.exists, report.readable, report.jsonl_files))
+                .collect::<Vec<_>>(),
+            vec![(true, true, 2), (true, true, 0), (false, false, 0)]
+        );
+        assert!(reports.iter().all(|report| report.error.is_none()));
+
+        let mut out = Vec::new();
+        write_session_root_reports(&mut out, &reports, false).expect("write");
+        let text = String::from_utf8(out).expect("utf8");
+        assert!(text.contains(&format!("{}: ok, 2 jsonl file(s)", populated.display())));
+        assert!(text.contains(&format!("{}: ok, 0 jsonl file(s)", empty.display())));
+        assert!(text.contains(&format!("{}: missing", missing.display())));
+    }
+
+    #[test]
+    fn takeover_toast_command_names_the_previous_pid() {
+        let command = takeover_toast_command(4242);
+
+        assert!(command.contains("CreateTextNode('Codex Discord Presence')"));
+        assert!(command.contains("CreateTextNode('Presence taken over from PID 4242')"));
+        assert!(command.contains(&format!("CreateToastNotifier('{TOAST_APP_ID}')")));
+        assert!(command.contains("ToastTemplateType]::ToastText02"));
+        assert_eq!(escape_powershell_single_quoted("it's"), "it''s");
+    }
+
+    #[test]
+    fn powershell_command_runs_the_script_without_profile_or_policy_prompts() {
+        let command = powershell_command("Write-Output 'hi'");
+
+        assert_eq!(command.get_program(), "powershell");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            [
+                "-NoProfile",
+                "-ExecutionPolicy",
+                "Bypass",
+                "-Command",
+                "Write-Output 'hi'"
+            ]
+        );
+    }
+
+    #[test]
+    fn headless_log_line_reports_project_activity_and_limits() {
+        let privacy = PrivacyConfig::default();
+        assert_eq!(
+            headless_log_line(&RuntimeSnapshot::default(), &privacy, "2026-01-02 03:04:05"),
+            "2026-01-02 03:04:05 idle: no active Codex session"
+        );
+
+        let mut session = test_session("codex-child:1");
+        session.activity = Some(SessionActivitySnapshot {
+            kind: SessionActivityKind::EditingFile,
+            target: Some("src/main.rs".to_string()),
+            ..SessionActivitySnapshot::default()
+        });
+        session.limits = RateLimits {
+            primary: Some(UsageWindow {
+                used_percent: 36.0,
+                remaining_percent: 64.0,
+                window_minutes: 300,
+                resets_at: None,
+            }),
+            secondary: Some(UsageWindow {
+                used_percent: 12.0,
+                remaining_percent: 88.0,
+                window_minutes: 10080,
+                resets_at: None,
+            }),
+        };
+        let snapshot = RuntimeSnapshot {
+            sessions: vec![session],
+            ..RuntimeSnapshot::default()
+        };
+
+        assert_eq!(
+            headless_log_line(&snapshot, &privacy, "2026-01-02 03:04:05"),
+            "2026-01-02 03:04:05 app | Editing src/main.rs | 5h 64% left | 7d 88% left"
+        );
+    }
+
+    #[test]
+    fn headless_log_line_hides_the_project_when_privacy_hides_it() {
+        let snapshot = RuntimeSnapshot {
+            sessions: vec![test_session("codex-child:1")],
+            ..RuntimeSnapshot::default()
+        };
+        let privacy = PrivacyConfig {
+            show_project_name: false,
+            ..PrivacyConfig::default()
+        };
+
+        assert_eq!(
+            headless_log_line(&snapshot, &privacy, "2026-01-02 03:04:05"),
+            "2026-01-02 03:04:05 active Codex session"
+        );
+    }
+
+    #[test]
+    fn stale_secs_override_changes_the_gathered_session_set() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let at = (Utc::now() - chrono::Duration::minutes(5)).to_rfc3339();
+        let path = root.path().join("rollout-stale.jsonl");
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n{}\n",
+                serde_json::json!({
+                    "timestamp": at,
+                    "type": "session_meta",
+                    "payload": { "id": "stale-preview", "cwd": "/repo/app" },
+                }),
+                serde_json::json!({
+                    "timestamp": at,
+                    "type": "turn_context",
+                    "payload": { "cwd": "/repo/app", "model": "gpt-5.4" },
+                }),
+            ),
+        )
+        .expect("write session");
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now() - Duration::from_secs(300)))
+            .expect("set mtime");
+        let config = PresenceConfig::default();
+
+        let default_runtime = status_runtime(None).with_overrides(None, Some(90));
+        let default_snapshot =
+            simulated_runtime_snapshot(root.path(), &default_runtime, &config).expect("snapshot");
+        assert!(default_snapshot.sessions.is_empty());
+
+        let preview = status_runtime(Some(600));
+        assert_eq!(preview.stale_threshold, Duration::from_secs(600));
+        let preview_snapshot =
+            simulated_runtime_snapshot(root.path(), &preview, &config).expect("snapshot");
+        assert_eq!(preview_snapshot.sessions.len(), 1);
+        assert_eq!(preview_snapshot.sessions[0].session_id, "stale-preview");
+    }
+
+    #[test]
+    fn idle_sessions_are_listed_only_when_the_flag_is_on() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let at = (Utc::now() - chrono::Duration::minutes(20)).to_rfc3339();
+        let path = root.path().join("rollout-idle.jsonl");
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n",
+                serde_json::json!({
+                    "timestamp": at,
+                    "type": "session_meta",
+                    "payload": { "id": "finished", "cwd": "/repo/app" },
+                }),
+            ),
+        )
+        .expect("write session");
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now() - Duration::from_secs(1_200)))
+            .expect("set mtime");
+        let mut parse_cache = SessionParseCache::default();
+        let sessions = collect_active_sessions_multi(
+            &[root.path().to_path_buf()],
+            Duration::from_secs(90),
+            Duration::from_secs(3_600),
+            &mut GitBranchCache::new(Duration::from_secs(30)),
+            &mut parse_cache,
+            &config::PricingConfig::default(),
+        )
+        .expect("collect");
+        assert!(sessions.is_empty());
+
+        let mut config = PresenceConfig::default();
+        assert!(idle_sessions_for_display(&config, &parse_cache, &sessions).is_empty());
+
+        config.display.show_idle_sessions = true;
+        let idle = idle_sessions_for_display(&config, &parse_cache, &sessions);
+        assert_eq!(idle.len(), 1);
+        assert_eq!(idle[0].session_id, "finished");
+        assert!(preferred_active_session(&sessions).is_none());
+    }
+
+    #[test]
+    fn show_all_lists_very_old_sessions_without_changing_the_active_set() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let at = (Utc::now() - chrono::Duration::days(90)).to_rfc3339();
+        let path = root.path().join("rollout-ancient.jsonl");
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n",
+                serde_json::json!({
+                    "timestamp": at,
+                    "type": "session_meta",
+                    "payload": { "id": "ancient", "cwd": "/repo/app" },
+                }),
+            ),
+        )
+        .expect("write session");
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| {
+                file.set_modified(SystemTime::now() - Duration::from_secs(90 * 86_400))
+            })
+            .expect("set mtime");
+        let mut parse_cache = SessionParseCache::default();
+        let sessions = collect_active_sessions_multi(
+            &[root.path().to_path_buf()],
+            Duration::from_secs(90),
+            Duration::from_secs(3_600),
+            &mut GitBranchCache::new(Duration::from_secs(30)),
+            &mut parse_cache,
+            &config::PricingConfig::default(),
+        )
+        .expect("collect");
+        assert!(sessions.is_empty());
+
+        let config = PresenceConfig::default();
+        assert!(idle_sessions_for_display(&config, &parse_cache, &sessions).is_empty());
+        let listed = inactive_sessions_within(&config, &parse_cache, &sessions, Duration::MAX);
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].session_id, "ancient");
+    }
+
+    #[test]
+    fn simulated_session_drives_presence_with_running_command() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let cwd = root.path().join("sim-project");
+        write_simulated_session(root.path(), &cwd, Utc::now()).expect("write");
+
+        let config = PresenceConfig::default();
+        let snapshot =
+            simulated_runtime_snapshot(root.path(), &config::runtime_settings(), &config)
+                .expect("snapshot");
+        let session = snapshot
+            .presence_session(&config.privacy)
+            .expect("preferred session");
+        let activity = session.activity.as_ref().expect("activity");
+
+        assert_eq!(session.project_name, "sim-project");
+        assert_eq!(activity.kind, SessionActivityKind::RunningCommand);
+        assert_eq!(activity.target.as_deref(), Some("cargo test"));
+        assert_eq!(session.model.as_deref(), Some("gpt-5.4"));
+    }
+
+    #[test]
+    fn enter_toggles_pin_on_selected_session() {
+        let pinned = toggle_pin(None, Some("a".to_string()));
+        assert_eq!(pinned.as_deref(), Some("a"));
+        assert_eq!(
+            toggle_pin(pinned.clone(), Some("b".to_string())).as_deref(),
+            Some("b")
+        );
+        assert_eq!(toggle_pin(pinned.clone(), Some("a".to_string())), None);
+        assert_eq!(toggle_pin(pinned, None).as_deref(), Some("a"));
+    }
+
     #[test]
     fn opencode_process_lineage_selects_codex_app_surface() {
         let processes = "WindowsTerminal.exe\nopencode.exe --project app\ncmd.exe";
@@ -1426,16 +3350,462 @@ explorer.exe"#;
         assert!(!is_master_presence_toggle_key(&modified));
     }
 
+    #[test]
+    fn privacy_mode_key_overrides_the_next_presence_payload() {
+        let key = KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE);
+        assert!(is_privacy_mode_toggle_key(&key));
+        let modified = KeyEvent::new(KeyCode::Char('h'), KeyModifiers::CONTROL);
+        assert!(!is_privacy_mode_toggle_key(&modified));
+
+        let session = CodexSessionSnapshot {
+            cwd: PathBuf::from("/tmp/secret-app"),
+            project_name: "secret-app".to_string(),
+            ..test_session("codex-child:1")
+        };
+        let presentation = |config: &PresenceConfig| {
+            discord::active_presence_presentation(
+                PresenceSurface::Cli,
+                &session,
+                None,
+                &ResolvedPlan::default(),
+                &ResolvedServiceTier::default(),
+                config,
+            )
+        };
+        let mut config = PresenceConfig::default();
+        assert!(presentation(&config).details.contains("secret-app"));
+
+        let mut overrides = RuntimeOverrides {
+            privacy_enabled: Some(!config.privacy.enabled),
+            ..RuntimeOverrides::default()
+        };
+        overrides.apply(&mut config);
+        assert!(config.privacy.enabled);
+        assert!(!presentation(&config).details.contains("secret-app"));
+
+        let mut reloaded = PresenceConfig::default();
+        overrides.apply(&mut reloaded);
+        assert!(reloaded.privacy.enabled);
+
+        overrides.privacy_enabled = Some(false);
+        overrides.apply(&mut config);
+        assert!(presentation(&config).details.contains("secret-app"));
+    }
+
+    #[test]
+    fn activity_target_key_overrides_config_until_toggled_back() {
+        let key = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE);
+        assert!(is_activity_target_toggle_key(&key));
+        let modified = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::ALT);
+        assert!(!is_activity_target_toggle_key(&modified));
+
+        let mut config = PresenceConfig::default();
+        let overrides = RuntimeOverrides {
+            show_activity_target: Some(!config.privacy.show_activity_target),
+            ..RuntimeOverrides::default()
+        };
+        overrides.apply(&mut config);
+        assert_eq!(
+            config.privacy.show_activity_target,
+            !PrivacyConfig::default().show_activity_target
+        );
+        assert_eq!(config.privacy.enabled, PrivacyConfig::default().enabled);
+    }
+
+    #[test]
+    fn plan_cycle_key_forces_a_manual_plan_that_survives_reloads() {
+        let key = KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE);
+        assert!(is_plan_cycle_key(&key));
+        let modified = KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL);
+        assert!(!is_plan_cycle_key(&modified));
+
+        let mut config = PresenceConfig::default();
+        let overrides = RuntimeOverrides {
+            plan_tier: Some(config.openai_plan.tier.cycled()),
+            ..RuntimeOverrides::default()
+        };
+        overrides.apply(&mut config);
+        assert_eq!(config.openai_plan.mode, OpenAiPlanMode::Manual);
+        assert_eq!(config.openai_plan.label(), "Free ($0/month)");
+
+        let resolved = PlanDetector::new().resolve_from_sessions(&[], &config.openai_plan);
+        assert_eq!(
+            resolved.display_label(&config.openai_plan),
+            "Free ($0/month)"
+        );
+    }
+
+    #[test]
+    fn mouse_wheel_steps_the_recent_cursor_within_bounds() {
+        let wheel = |kind| MouseEvent {
+            kind,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+        let down = mouse_scroll_delta(&wheel(MouseEventKind::ScrollDown)).expect("down");
+        let up = mouse_scroll_delta(&wheel(MouseEventKind::ScrollUp)).expect("up");
+        assert_eq!(mouse_scroll_delta(&wheel(MouseEventKind::Moved)), None);
+
+        let mut cursor = None;
+        for _ in 0..5 {
+            cursor = ui::step_recent_cursor(cursor, down, 3);
+        }
+        assert_eq!(cursor, Some(2));
+        cursor = ui::step_recent_cursor(cursor, up, 3);
+        assert_eq!(cursor, Some(1));
+        for _ in 0..5 {
+            cursor = ui::step_recent_cursor(cursor, up, 3);
+        }
+        assert_eq!(cursor, Some(0));
+    }
+
+    #[test]
+    fn freeze_key_gates_presence_updates_and_forces_publish_on_resume() {
+        let key = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE);
+        assert!(is_freeze_toggle_key(&key));
+        let modified = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL);
+        assert!(!is_freeze_toggle_key(&modified));
+
+        assert_eq!(
+            presence_update_gate(false, false),
+            PresenceUpdateGate::Publish
+        );
+        assert_eq!(presence_update_gate(true, false), PresenceUpdateGate::Skip);
+        assert_eq!(presence_update_gate(true, true), PresenceUpdateGate::Skip);
+        assert_eq!(
+            presence_update_gate(false, true),
+            PresenceUpdateGate::ForcePublish
+        );
+    }
+
+    #[test]
+    fn question_mark_types_into_an_open_filter_and_toggles_help_otherwise() {
+        let question = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE);
+        let closed = KeyRouteState {
+            help_open: false,
+            recent_filter_open: false,
+            picker_open: false,
+        };
+
+        assert_eq!(route_key(closed, &question), KeyAction::OpenHelp);
+        assert_eq!(
+            route_key(
+                KeyRouteState {
+                    help_open: true,
+                    ..closed
+                },
+                &question
+            ),
+            KeyAction::CloseHelp
+        );
+        assert_eq!(
+            route_key(
+                KeyRouteState {
+                    picker_open: true,
+                    ..closed
+                },
+                &question
+            ),
+            KeyAction::Other
+        );
+
+        let filtering = KeyRouteState {
+            recent_filter_open: true,
+            ..closed
+        };
+        assert_eq!(route_key(filtering, &question), KeyAction::EditRecentFilter);
+        assert_eq!(
+            route_key(
+                filtering,
+                &KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)
+            ),
+            KeyAction::EditRecentFilter
+        );
+
+        let mut filter = "app".to_string();
+        assert!(edit_recent_filter(&mut filter, KeyCode::Char('?')));
+        assert_eq!(filter, "app?");
+        assert!(edit_recent_filter(&mut filter, KeyCode::Backspace));
+        assert_eq!(filter, "app");
+        assert!(!edit_recent_filter(&mut filter, KeyCode::Enter));
+        assert_eq!(filter, "app");
+        assert!(!edit_recent_filter(&mut filter, KeyCode::Esc));
+        assert!(filter.is_empty());
+    }
+
     #[test]
     fn every_long_running_mode_reloads_the_shared_config_each_poll() {
         let source = include_str!("app.rs");
-        let reload_call = ["reload_runtime_config", "(&mut config)"].concat();
+        let engine_source = include_str!("engine.rs");
+        let watch_reload = ["reload_runtime_config", "(&mut config)"].concat();
+        let engine_reload = ["engine", ".reload_config()"].concat();
+
+        assert_eq!(
+            source.matches(&watch_reload).count(),
+            1,
+            "the watch loop reloads the shared config directly"
+        );
+        assert_eq!(
+            source.matches(&engine_reload).count(),
+            2,
+            "TUI and wrapper loops must reload through the presence engine"
+        );
+        assert!(
+            engine_source.contains("self.reload_config();"),
+            "headless ticks must reload through the presence engine"
+        );
+    }
+
+    #[test]
+    fn codex_child_fallback_presence_uses_spawn_cwd() {
+        let started_at = Utc::now();
+        let cwd = PathBuf::from("/work/demo-app");
+
+        let fallback =
+            codex_child_fallback_session(4242, &cwd, started_at, &PrivacyConfig::default());
+
+        assert_eq!(fallback.session_id, "codex-child:4242");
+        assert_eq!(fallback.cwd, cwd);
+        assert_eq!(fallback.project_name, "demo-app");
+        assert_eq!(fallback.started_at, Some(started_at));
+        assert_eq!(fallback.detected_surface(), Some(PresenceSurface::Cli));
+        let activity = fallback.activity.expect("fallback activity");
+        assert_eq!(activity.kind, SessionActivityKind::RunningCommand);
+        assert_eq!(activity.to_text(true), "Running codex in demo-app");
+        assert_eq!(fallback.known_cost_usd, None);
+    }
+
+    #[test]
+    fn codex_child_fallback_never_publishes_codex_argv() {
+        let config = PresenceConfig {
+            privacy: PrivacyConfig {
+                show_project_name: false,
+                ..PrivacyConfig::default()
+            },
+            ..PresenceConfig::default()
+        };
+        let fallback = codex_child_fallback_session(
+            4242,
+            Path::new("/work/secret-app"),
+            Utc::now(),
+            &config.privacy,
+        );
 
+        let presentation = discord::active_presence_presentation(
+            PresenceSurface::Cli,
+            &fallback,
+            None,
+            &ResolvedPlan::default(),
+            &ResolvedServiceTier::default(),
+            &config,
+        );
+        let rendered = format!("{presentation:?}");
+
+        assert!(rendered.contains("Running codex"), "{rendered}");
+        assert!(!rendered.contains("secret-app"), "{rendered}");
+        assert!(!rendered.contains("exec"), "{rendered}");
+    }
+
+    fn usage_window(remaining_percent: f64, window_minutes: u64, reset_epoch: i64) -> UsageWindow {
+        UsageWindow {
+            used_percent: 100.0 - remaining_percent,
+            remaining_percent,
+            window_minutes,
+            resets_at: DateTime::<Utc>::from_timestamp(reset_epoch, 0),
+        }
+    }
+
+    #[test]
+    fn min_remaining_aggregate_matches_windows_and_keeps_per_window_sources() {
+        let future = Utc::now().timestamp() + 3_600;
+        let past = Utc::now().timestamp() - 60;
+        let session = |id: &str, limits: RateLimits| {
+            let mut session = test_session(id);
+            session.limits = limits;
+            session
+        };
+        let sessions = vec![
+            session(
+                "five-hour",
+                RateLimits {
+                    primary: Some(usage_window(50.0, 300, future)),
+                    secondary: Some(usage_window(70.0, 10_080, future)),
+                },
+            ),
+            session(
+                "weekly-only",
+                RateLimits {
+                    primary: Some(usage_window(10.0, 10_080, future)),
+                    secondary: None,
+                },
+            ),
+            session(
+                "expired",
+                RateLimits {
+                    primary: Some(usage_window(1.0, 300, past)),
+                    secondary: None,
+                },
+            ),
+        ];
+        let selection = EffectiveLimitSelection {
+            source_session_id: "expired".to_string(),
+            source_limit_id: None,
+            source_scope: codex_presence_core::RateLimitScope::GlobalCodex,
+            observed_at: None,
+            limits: sessions[2].limits.clone(),
+            credits: None,
+            window_sources: Vec::new(),
+        };
+
+        let aggregated =
+            aggregated_limits_source(Some(selection), &sessions, LimitsAggregate::MinRemaining)
+                .expect("aggregate");
+
+        assert_eq!(
+            aggregated.limits.primary,
+            Some(usage_window(50.0, 300, future))
+        );
+        assert_eq!(
+            aggregated.limits.secondary,
+            Some(usage_window(10.0, 10_080, future))
+        );
+        assert_eq!(aggregated.source_session_id, "five-hour");
         assert_eq!(
-            source.matches(&reload_call).count(),
-            3,
-            "TUI, headless, and wrapper loops must reload through the same boundary"
+            aggregated.window_sources,
+            vec![
+                LimitWindowSource {
+                    window_minutes: 300,
+                    session_id: "five-hour".to_string(),
+                },
+                LimitWindowSource {
+                    window_minutes: 10_080,
+                    session_id: "weekly-only".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn limit_warning_fires_once_per_window_per_reset_period() {
+        let mut tracker = LimitWarningTracker::default();
+        let healthy = RateLimits {
+            primary: Some(usage_window(40.0, 300, 1_000)),
+            secondary: Some(usage_window(80.0, 10_080, 9_000)),
+        };
+        assert!(tracker.observe(&healthy, 10.0).is_empty());
+
+        let low = RateLimits {
+            primary: Some(usage_window(8.0, 300, 1_000)),
+            secondary: Some(usage_window(80.0, 10_080, 9_000)),
+        };
+        let crossed = tracker.observe(&low, 10.0);
+        assert_eq!(crossed.len(), 1);
+        assert_eq!(crossed[0].window_minutes, 300);
+
+        let lower = RateLimits {
+            primary: Some(usage_window(3.0, 300, 1_000)),
+            secondary: Some(usage_window(80.0, 10_080, 9_000)),
+        };
+        assert!(tracker.observe(&lower, 10.0).is_empty());
+
+        let next_period = RateLimits {
+            primary: Some(usage_window(5.0, 300, 19_000)),
+            secondary: Some(usage_window(9.0, 10_080, 9_000)),
+        };
+        let crossed = tracker.observe(&next_period, 10.0);
+        assert_eq!(crossed.len(), 2);
+    }
+
+    #[test]
+    fn limit_warning_rearms_after_window_recovers() {
+        let mut tracker = LimitWarningTracker::default();
+        let low = RateLimits {
+            primary: Some(UsageWindow {
+                resets_at: None,
+                ..usage_window(5.0, 300, 0)
+            }),
+            secondary: None,
+        };
+        assert_eq!(tracker.observe(&low, 10.0).len(), 1);
+        assert!(tracker.observe(&low, 10.0).is_empty());
+
+        let recovered = RateLimits {
+            primary: Some(UsageWindow {
+                resets_at: None,
+                ..usage_window(60.0, 300, 0)
+            }),
+            secondary: None,
+        };
+        assert!(tracker.observe(&recovered, 10.0).is_empty());
+        assert_eq!(tracker.observe(&low, 10.0).len(), 1);
+    }
+
+    #[test]
+    fn pricing_report_resolves_catalog_rates_and_breakdown() {
+        let report = pricing_report(
+            &PresenceConfig::default(),
+            "gpt-5.2-codex",
+            TokenUsage {
+                input_tokens: 1_500_000,
+                cached_input_tokens: 500_000,
+                cache_write_tokens: None,
+                output_tokens: 250_000,
+            },
         );
+        assert_eq!(report.resolved_model, "gpt-5.2");
+        assert_eq!(report.source, PricingSource::Alias);
+        assert_eq!(report.status, PricingStatus::Exact);
+        assert!(report.input_per_million.is_some());
+        assert!((report.known_total_cost_usd.expect("known cost") - 5.3375).abs() < 0.0001);
+
+        let mut out = Vec::new();
+        write_pricing_report(&mut out, &report).expect("write report");
+        let text = String::from_utf8(out).expect("utf8");
+        assert!(text.contains("pricing_source: "));
+        assert!(text.contains("total: $5.34"));
+    }
+
+    #[test]
+    fn pricing_report_marks_unknown_models_unavailable() {
+        let report = pricing_report(
+            &PresenceConfig::default(),
+            "definitely-not-a-model",
+            TokenUsage::default(),
+        );
+        assert_eq!(report.source, PricingSource::Unavailable);
+        assert_eq!(report.input_per_million, None);
+        assert_eq!(report.known_total_cost_usd, None);
+    }
+
+    #[test]
+    fn watch_frame_clears_screen_before_a_single_status_render() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let now = Utc::now() - chrono::Duration::minutes(10);
+        write_simulated_session(root.path(), &root.path().join("watched"), now)
+            .expect("write session");
+        let runtime = RuntimeSettings {
+            stale_threshold: Duration::from_secs(3600),
+            active_sticky_window: Duration::from_secs(60),
+            poll_interval: Duration::from_secs(1),
+            limit_warn_percent: 10.0,
+        };
+        let roots = vec![root.path().to_path_buf()];
+        let render = |runtime: RuntimeSettings| {
+            let mut out = Vec::new();
+            write_watch_frame(&mut out, &PresenceConfig::default(), &runtime, &roots)
+                .expect("render watch frame");
+            String::from_utf8(out).expect("utf8 status")
+        };
+
+        let text = render(runtime.clone());
+        assert!(text.starts_with("\u{1b}[2J"));
+        assert_eq!(text.matches("codex-discord-presence status").count(), 1);
+        assert!(text.contains(&root.path().display().to_string()), "{text}");
+        assert!(text.contains("active_sessions: 1"), "{text}");
+
+        let text = render(runtime.with_overrides(None, Some(120)));
+        assert!(text.contains("active_sessions: 0"), "{text}");
     }
 
     #[test]
@@ -1,5 +1,10 @@
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
+
 use clap::{Parser, Subcommand};
 
+use crate::util::LogFormat;
+
 #[derive(Parser, Debug)]
 #[command(
     name = "codex-discord-presence",
@@ -8,6 +13,78 @@ use clap::{Parser, Subcommand};
     trailing_var_arg = true
 )]
 pub struct Cli {
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Use discord-presence-config.<NAME>.json and profile-scoped lock and metrics files"
+    )]
+    pub profile: Option<String>,
+    #[arg(
+        long,
+        value_name = "PORT",
+        help = "Serve a local auto-refreshing dashboard and /api/state JSON on this port"
+    )]
+    pub web: Option<u16>,
+    #[arg(
+        long,
+        value_name = "ADDR",
+        requires = "web",
+        default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST),
+        help = "Address the web dashboard binds to; use 0.0.0.0 to expose it on your network"
+    )]
+    pub web_bind: IpAddr,
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Poll interval override; wins over CODEX_PRESENCE_POLL_SECONDS"
+    )]
+    pub poll_secs: Option<u64>,
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Stale session threshold override; wins over CODEX_PRESENCE_STALE_SECONDS"
+    )]
+    pub stale_secs: Option<u64>,
+    #[arg(
+        long,
+        help = "Print one status line per poll instead of the dashboard (headless servers)"
+    )]
+    pub log_lines: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Disable terminal colors and text styling (also honors NO_COLOR)"
+    )]
+    pub no_color: bool,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = LogFormat::Human,
+        value_name = "FORMAT",
+        help = "Tracing log format: human-readable lines or one JSON object per line"
+    )]
+    pub log_format: LogFormat,
+    #[arg(
+        short,
+        long,
+        global = true,
+        conflicts_with = "verbose",
+        help = "Only log errors when RUST_LOG is unset"
+    )]
+    pub quiet: bool,
+    #[arg(
+        short,
+        long,
+        global = true,
+        action = clap::ArgAction::Count,
+        help = "Raise the log level when RUST_LOG is unset (-v debug, -vv trace)"
+    )]
+    pub verbose: u8,
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -25,7 +102,275 @@ pub enum Commands {
         args: Vec<String>,
     },
     /// Print a one-shot operational status snapshot.
-    Status,
+    Status {
+        #[arg(long, hide = true)]
+        debug_cache: bool,
+        #[arg(long, help = "Print machine-readable JSON")]
+        json: bool,
+        #[arg(
+            long,
+            help = "Report each sessions root: existence, readability, and jsonl file count"
+        )]
+        roots: bool,
+    },
     /// Run health diagnostics for setup and runtime requirements.
     Doctor,
+    /// Resolve a model's pricing and compute the cost of a token usage sample.
+    Pricing {
+        #[arg(value_name = "MODEL", help = "Model id, alias, or override key")]
+        model: String,
+        #[arg(long, default_value_t = 0, help = "Total input tokens")]
+        input: u64,
+        #[arg(
+            long,
+            default_value_t = 0,
+            help = "Cached input tokens (subset of input)"
+        )]
+        cached: u64,
+        #[arg(long, default_value_t = 0, help = "Output tokens")]
+        output: u64,
+        #[arg(long, help = "Print machine-readable JSON")]
+        json: bool,
+    },
+    /// Reprint the status snapshot on an interval without connecting to Discord.
+    Watch {
+        #[arg(
+            long,
+            value_name = "DURATION",
+            default_value = "5s",
+            value_parser = parse_interval,
+            help = "Refresh interval such as `5`, `5s`, `2m`, or `1h`"
+        )]
+        interval: Duration,
+    },
+    /// Write a scripted session file and publish one presence update from it.
+    #[command(hide = true)]
+    Simulate {
+        #[arg(
+            long,
+            value_name = "DURATION",
+            default_value = "10s",
+            value_parser = parse_interval,
+            help = "How long to keep the simulated presence visible before clearing it"
+        )]
+        hold: Duration,
+    },
+    /// Inspect or edit the presence configuration file.
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Set a supported dotted config key, then normalize and save the file.
+    Set {
+        #[arg(value_name = "KEY", help = "Dotted key such as `privacy.show_cost`")]
+        key: String,
+        #[arg(value_name = "VALUE", allow_hyphen_values = true)]
+        value: String,
+    },
+    /// Check the config file without rewriting it and report every finding.
+    Validate,
+    /// Print the loaded config as JSON.
+    Show {
+        #[arg(long, help = "Apply environment overrides before printing")]
+        effective: bool,
+    },
+}
+
+pub fn parse_interval(raw: &str) -> Result<Duration, String> {
+    let value = raw.trim();
+    let split = value
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (digits, unit) = value.split_at(split);
+    let amount: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid interval `{raw}`"))?;
+    let secs = match unit.trim() {
+        "" | "s" => amount,
+        "m" => amount.saturating_mul(60),
+        "h" => amount.saturating_mul(3_600),
+        other => return Err(format!("unsupported interval unit `{other}`")),
+    };
+    if secs == 0 {
+        return Err("interval must be at least 1s".to_string());
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watch_interval_accepts_bare_seconds_and_units() {
+        assert_eq!(parse_interval("5"), Ok(Duration::from_secs(5)));
+        assert_eq!(parse_interval("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_interval("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_interval("1h"), Ok(Duration::from_secs(3_600)));
+    }
+
+    #[test]
+    fn watch_interval_rejects_zero_and_unknown_units() {
+        assert!(parse_interval("0s").is_err());
+        assert!(parse_interval("5d").is_err());
+        assert!(parse_interval("fast").is_err());
+    }
+
+    #[test]
+    fn log_format_defaults_to_human_and_accepts_json() {
+        let cli = Cli::try_parse_from(["codex-discord-presence"]).expect("parse default");
+        assert_eq!(cli.log_format, LogFormat::Human);
+
+        let cli = Cli::try_parse_from(["codex-discord-presence", "status", "--log-format", "json"])
+            .expect("parse json log format");
+        assert_eq!(cli.log_format, LogFormat::Json);
+        assert!(Cli::try_parse_from(["codex-discord-presence", "--log-format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn quiet_and_stacked_verbose_flags_parse() {
+        let cli = Cli::try_parse_from(["codex-discord-presence", "-vv", "status"])
+            .expect("parse verbose");
+        assert_eq!((cli.quiet, cli.verbose), (false, 2));
+
+        let cli = Cli::try_parse_from(["codex-discord-presence", "--quiet"]).expect("parse quiet");
+        assert_eq!((cli.quiet, cli.verbose), (true, 0));
+        assert!(Cli::try_parse_from(["codex-discord-presence", "-q", "-v"]).is_err());
+    }
+
+    #[test]
+    fn web_dashboard_binds_to_localhost_unless_opted_in() {
+        let cli =
+            Cli::try_parse_from(["codex-discord-presence", "--web", "8787"]).expect("parse web");
+        assert_eq!(cli.web, Some(8787));
+        assert_eq!(cli.web_bind, IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+        let cli = Cli::try_parse_from([
+            "codex-discord-presence",
+            "--web",
+            "8787",
+            "--web-bind",
+            "0.0.0.0",
+        ])
+        .expect("parse web bind");
+        assert_eq!(cli.web_bind, IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        assert!(Cli::try_parse_from(["codex-discord-presence", "--web-bind", "0.0.0.0"]).is_err());
+    }
+
+    #[test]
+    fn pricing_subcommand_parses_model_and_token_counts() {
+        let cli = Cli::try_parse_from([
+            "codex-discord-presence",
+            "pricing",
+            "gpt-5.3-codex",
+            "--input",
+            "1000000",
+            "--output",
+            "500000",
+            "--json",
+        ])
+        .expect("parse pricing");
+        match cli.command {
+            Some(Commands::Pricing {
+                model,
+                input,
+                cached,
+                output,
+                json,
+            }) => {
+                assert_eq!(model, "gpt-5.3-codex");
+                assert_eq!((input, cached, output), (1_000_000, 0, 500_000));
+                assert!(json);
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn config_set_subcommand_parses_key_and_value() {
+        let cli = Cli::try_parse_from([
+            "codex-discord-presence",
+            "config",
+            "set",
+            "privacy.show_cost",
+            "false",
+        ])
+        .expect("parse config set");
+        match cli.command {
+            Some(Commands::Config {
+                action: ConfigAction::Set { key, value },
+            }) => {
+                assert_eq!(key, "privacy.show_cost");
+                assert_eq!(value, "false");
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn config_show_subcommand_parses_effective_flag() {
+        let cli = Cli::try_parse_from(["codex-discord-presence", "config", "show", "--effective"])
+            .expect("parse config show");
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Config {
+                action: ConfigAction::Show { effective: true }
+            })
+        ));
+    }
+
+    #[test]
+    fn profile_flag_is_accepted_before_and_after_subcommands() {
+        let cli = Cli::try_parse_from(["codex-discord-presence", "--profile", "work"])
+            .expect("parse profile");
+        assert_eq!(cli.profile.as_deref(), Some("work"));
+        assert!(cli.command.is_none());
+
+        let cli = Cli::try_parse_from(["codex-discord-presence", "status", "--profile", "stream"])
+            .expect("parse status profile");
+        assert_eq!(cli.profile.as_deref(), Some("stream"));
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Status {
+                debug_cache: false,
+                json: false,
+                roots: false,
+            })
+        ));
+    }
+
+    #[test]
+    fn status_and_doctor_accept_a_stale_threshold_preview() {
+        let cli = Cli::try_parse_from(["codex-discord-presence", "status", "--stale-secs", "600"])
+            .expect("parse status stale");
+        assert!(matches!(cli.command, Some(Commands::Status { .. })));
+        assert_eq!(cli.stale_secs, Some(600));
+
+        let cli = Cli::try_parse_from(["codex-discord-presence", "--stale-secs", "600", "status"])
+            .expect("parse leading stale");
+        assert!(matches!(cli.command, Some(Commands::Status { .. })));
+        assert_eq!(cli.stale_secs, Some(600));
+
+        let cli = Cli::try_parse_from(["codex-discord-presence", "doctor", "--stale-secs", "30"])
+            .expect("parse doctor stale");
+        assert!(matches!(cli.command, Some(Commands::Doctor)));
+        assert_eq!(cli.stale_secs, Some(30));
+        assert!(
+            Cli::try_parse_from(["codex-discord-presence", "doctor", "--stale-secs", "0"]).is_err()
+        );
+    }
+
+    #[test]
+    fn watch_subcommand_parses_interval_flag() {
+        let cli = Cli::try_parse_from(["codex-discord-presence", "watch", "--interval", "30s"])
+            .expect("parse watch");
+        match cli.command {
+            Some(Commands::Watch { interval }) => assert_eq!(interval, Duration::from_secs(30)),
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
 }
@@ -3,22 +3,96 @@ use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 #[cfg(windows)]
-use std::process::Stdio;
-use std::time::Duration;
+use std::process::Command;
+use std::sync::OnceLock;
+#[cfg(windows)]
+use std::time::Instant;
+use std::time::{Duration, SystemTime};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
 use codex_presence_core::{PresenceFieldId, PresenceLayoutConfig};
 
-use crate::util::write_json_pretty_atomic;
+use crate::session::SessionActivityKind;
+use crate::util::{template_placeholders, write_json_pretty_atomic, write_text_atomic};
 
 const DEFAULT_STALE_SECONDS: u64 = 90;
 const DEFAULT_POLL_SECONDS: u64 = 2;
 const DEFAULT_ACTIVE_STICKY_SECONDS: u64 = 3600;
 const MIN_ACTIVE_STICKY_SECONDS: u64 = 60;
+const DEFAULT_LIMIT_WARN_PERCENT: u64 = 10;
+const DEFAULT_GIT_TIMEOUT_MS: u64 = 2000;
+#[cfg(windows)]
+const WSL_COMMAND_TIMEOUT: Duration = Duration::from_secs(3);
+#[cfg(windows)]
+const WSL_WAIT_POLL: Duration = Duration::from_millis(20);
 const CONFIG_SCHEMA_VERSION: u32 = 13;
+pub const SETTABLE_CONFIG_KEYS: &[&str] = &[
+    "presence_enabled",
+    "privacy.enabled",
+    "privacy.show_project_name",
+    "privacy.show_git_branch",
+    "privacy.show_model",
+    "privacy.show_tokens",
+    "privacy.show_cost",
+    "privacy.show_limits",
+    "privacy.show_credits",
+    "privacy.show_context",
+    "privacy.show_activity",
+    "privacy.show_activity_target",
+    "privacy.show_systems",
+    "display.large_image_key",
+    "display.desktop_presence_design",
+    "display.terminal_logo_mode",
+    "display.terminal_logo_path",
+    "display.banner_path",
+    "display.recent_active_marker",
+    "display.recent_line_template",
+    "display.max_models_shown",
+    "display.codex_child_fallback",
+    "display.show_aggregate_cost",
+    "display.activity_emoji",
+    "display.show_model_rates",
+    "display.show_reset_countdown",
+    "display.state_parts_order",
+    "display.state_separator",
+    "display.details_template",
+    "display.idle_details",
+    "display.idle_state",
+    "display.idle_messages",
+    "display.clear_when_idle",
+    "display.timer_origin",
+    "display.mouse",
+    "display.large_text_shows_uptime",
+    "display.limits_follow_active",
+    "display.limits_aggregate",
+    "display.show_idle_sessions",
+    "display.iterm_badge",
+    "display.takeover_toast",
+    "display.state_template",
+    "display.min_bar_width",
+    "display.theme",
+    "display.recent_sort",
+    "display.bar_style",
+    "display.bar_chars",
+    "metrics.window",
+    "openai_plan.mode",
+    "openai_plan.tier",
+    "openai_plan.show_price",
+    "openai_plan.custom_price_usd",
+    "pricing.display_currency",
+    "pricing.usd_rate",
+    "pricing.catalog_path",
+    "pricing.monthly_budget_usd",
+];
+pub const PRESENCE_TEMPLATE_PLACEHOLDERS: &[&str] = &[
+    "activity", "project", "branch", "model", "tokens", "cost", "limit5h", "limit7d",
+];
+pub const RECENT_LINE_PLACEHOLDERS: &[&str] =
+    &["project", "activity", "branch", "last", "total", "cost"];
 pub const DEFAULT_DISCORD_CLIENT_ID: &str = "1470480085453770854";
 pub const DEFAULT_DISCORD_DESKTOP_CLIENT_ID: &str = "1478395304624652345";
 pub const DEFAULT_DISCORD_PUBLIC_KEY: &str =
@@ -36,6 +110,95 @@ pub struct PresenceConfig {
     pub display: DisplayConfig,
     pub pricing: PricingConfig,
     pub openai_plan: OpenAiPlanDisplayConfig,
+    pub metrics: MetricsConfig,
+    #[serde(skip)]
+    pub stealth_restore_privacy: Option<bool>,
+    #[serde(skip)]
+    pub file_stamp: ConfigFileStamp,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConfigFileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl ConfigFileStamp {
+    fn of(path: &Path) -> Self {
+        let meta = fs::metadata(path).ok();
+        Self {
+            modified: meta.as_ref().and_then(|meta| meta.modified().ok()),
+            len: meta.map_or(0, |meta| meta.len()),
+        }
+    }
+
+    fn matches(self, other: Self) -> bool {
+        self.modified.is_some() && self == other
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigFinding {
+    pub key: String,
+    pub message: String,
+}
+
+impl ConfigFinding {
+    fn new(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+
+    fn parse(self, raw: &str) -> Result<PresenceConfig> {
+        match self {
+            Self::Json => serde_json::from_str(raw).context("invalid JSON"),
+            Self::Toml => toml::from_str(raw).context("invalid TOML"),
+        }
+    }
+
+    fn parse_value(self, raw: &str) -> Result<serde_json::Value> {
+        match self {
+            Self::Json => serde_json::from_str(raw).context("invalid JSON"),
+            Self::Toml => {
+                let value: toml::Value = toml::from_str(raw).context("invalid TOML")?;
+                serde_json::to_value(value).context("invalid TOML")
+            }
+        }
+    }
+
+    fn write(self, path: &Path, config: &PresenceConfig) -> std::io::Result<()> {
+        match self {
+            Self::Json => write_json_pretty_atomic(path, config),
+            Self::Toml => {
+                let value = toml::Value::try_from(config).map_err(std::io::Error::other)?;
+                let rendered = toml::to_string_pretty(&value).map_err(std::io::Error::other)?;
+                write_text_atomic(path, &rendered)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -53,6 +216,23 @@ pub struct PrivacyConfig {
     pub show_activity: bool,
     pub show_activity_target: bool,
     pub show_systems: bool,
+    pub project_allowlist: Vec<String>,
+    pub project_denylist: Vec<String>,
+}
+
+impl PrivacyConfig {
+    pub fn allows_project(&self, project_name: &str) -> bool {
+        let project = project_name.trim();
+        let listed = |names: &[String]| {
+            names
+                .iter()
+                .any(|name| name.trim().eq_ignore_ascii_case(project))
+        };
+        if listed(&self.project_denylist) {
+            return false;
+        }
+        self.project_allowlist.is_empty() || listed(&self.project_allowlist)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -175,11 +355,47 @@ impl PrivacyField {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
 pub struct PricingConfig {
     pub aliases: BTreeMap<String, String>,
     pub overrides: BTreeMap<String, ModelPricingOverride>,
+    pub display_currency: Currency,
+    pub usd_rate: f64,
+    pub catalog_path: Option<String>,
+    pub monthly_budget_usd: Option<f64>,
+}
+
+impl Default for PricingConfig {
+    fn default() -> Self {
+        Self {
+            aliases: BTreeMap::new(),
+            overrides: BTreeMap::new(),
+            display_currency: Currency::Usd,
+            usd_rate: 1.0,
+            catalog_path: None,
+            monthly_budget_usd: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Currency {
+    #[default]
+    Usd,
+    Eur,
+    Gbp,
+}
+
+impl Currency {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Usd => "$",
+            Self::Eur => "€",
+            Self::Gbp => "£",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -233,6 +449,16 @@ impl OpenAiPlanTier {
             Self::Business | Self::Enterprise => None,
         }
     }
+
+    pub const CYCLE: [Self; 4] = [Self::Free, Self::Go, Self::Plus, Self::Pro20x];
+
+    pub fn cycled(self) -> Self {
+        let next = Self::CYCLE
+            .iter()
+            .position(|tier| *tier == self)
+            .map_or(0, |index| (index + 1) % Self::CYCLE.len());
+        Self::CYCLE[next]
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -249,13 +475,14 @@ pub struct OpenAiPlanDisplayConfig {
     pub mode: OpenAiPlanMode,
     pub tier: OpenAiPlanTier,
     pub show_price: bool,
+    pub custom_price_usd: Option<u32>,
 }
 
 impl OpenAiPlanDisplayConfig {
     // Legacy display helper kept for backwards compatibility; runtime now uses telemetry plan.
     pub fn label(&self) -> String {
         if self.show_price
-            && let Some(monthly) = self.tier.monthly_price_usd()
+            && let Some(monthly) = self.custom_price_usd.or(self.tier.monthly_price_usd())
         {
             return format!("{} (${monthly}/month)", self.tier.title());
         }
@@ -347,6 +574,7 @@ impl Default for OpenAiPlanDisplayConfig {
             mode: OpenAiPlanMode::Auto,
             tier: OpenAiPlanTier::Pro20x,
             show_price: true,
+            custom_price_usd: None,
         }
     }
 }
@@ -368,6 +596,101 @@ pub enum TerminalLogoMode {
     Image,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct MetricsConfig {
+    pub window: MetricsWindow,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricsWindow {
+    #[default]
+    Lifetime,
+    RollingHour,
+    SinceStart,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UiTheme {
+    #[default]
+    Dark,
+    Light,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RecentSort {
+    #[default]
+    Rank,
+    Tokens,
+    Recency,
+    Cost,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BarStyle {
+    #[default]
+    Ascii,
+    Blocks,
+}
+
+impl BarStyle {
+    pub const fn chars(self) -> BarChars {
+        match self {
+            Self::Ascii => BarChars::ASCII,
+            Self::Blocks => BarChars::BLOCKS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimerOrigin {
+    #[default]
+    Session,
+    Daemon,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitsAggregate {
+    #[default]
+    Latest,
+    MinRemaining,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct BarChars {
+    pub filled: char,
+    pub empty: char,
+}
+
+impl Default for BarChars {
+    fn default() -> Self {
+        Self::BLOCKS
+    }
+}
+
+impl BarChars {
+    pub const ASCII: Self = Self {
+        filled: '#',
+        empty: '-',
+    };
+    pub const BLOCKS: Self = Self {
+        filled: '█',
+        empty: '░',
+    };
+
+    pub const fn is_full_block(self) -> bool {
+        self.filled == '█'
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PresenceSurface {
     Cli,
@@ -450,9 +773,59 @@ pub struct DisplayConfig {
     pub small_image_key: String,
     pub small_text: String,
     pub activity_small_image_keys: ActivitySmallImageKeys,
+    pub buttons: Vec<PresenceButton>,
     pub terminal_logo_mode: TerminalLogoMode,
     pub terminal_logo_path: Option<String>,
+    pub banner_path: Option<String>,
     pub presence_layout: PresenceLayoutConfig,
+    pub recent_active_marker: String,
+    pub recent_line_template: String,
+    pub max_models_shown: usize,
+    pub codex_child_fallback: bool,
+    pub show_aggregate_cost: bool,
+    pub activity_emoji: bool,
+    pub show_model_rates: bool,
+    pub show_reset_countdown: bool,
+    pub state_parts_order: Vec<String>,
+    pub state_separator: String,
+    pub details_template: String,
+    pub idle_details: String,
+    pub idle_state: String,
+    pub idle_messages: Vec<String>,
+    pub clear_when_idle: bool,
+    pub timer_origin: TimerOrigin,
+    pub mouse: bool,
+    pub large_text_shows_uptime: bool,
+    pub limits_follow_active: bool,
+    pub limits_aggregate: LimitsAggregate,
+    pub show_idle_sessions: bool,
+    pub iterm_badge: bool,
+    pub takeover_toast: bool,
+    pub state_template: String,
+    pub min_bar_width: u16,
+    pub tool_activity_map: BTreeMap<String, SessionActivityKind>,
+    pub theme: UiTheme,
+    pub recent_sort: RecentSort,
+    pub bar_style: BarStyle,
+    pub bar_chars: Option<BarChars>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct PresenceButton {
+    pub label: String,
+    pub url: String,
+}
+
+impl PresenceButton {
+    pub fn is_valid(&self) -> bool {
+        let label_len = self.label.trim().chars().count();
+        let url = self.url.trim();
+        (1..=32).contains(&label_len)
+            && url.len() <= 512
+            && (url.starts_with("https://") || url.starts_with("http://"))
+            && url.len() > "https://".len()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
@@ -462,6 +835,7 @@ pub struct ActivitySmallImageKeys {
     pub reading: Option<String>,
     pub editing: Option<String>,
     pub running: Option<String>,
+    pub archiving: Option<String>,
     pub waiting: Option<String>,
     pub idle: Option<String>,
 }
@@ -471,6 +845,7 @@ pub struct RuntimeSettings {
     pub stale_threshold: Duration,
     pub active_sticky_window: Duration,
     pub poll_interval: Duration,
+    pub limit_warn_percent: f64,
 }
 
 impl Default for PresenceConfig {
@@ -485,6 +860,9 @@ impl Default for PresenceConfig {
             display: DisplayConfig::default(),
             pricing: PricingConfig::default(),
             openai_plan: OpenAiPlanDisplayConfig::default(),
+            metrics: MetricsConfig::default(),
+            stealth_restore_privacy: None,
+            file_stamp: ConfigFileStamp::default(),
         }
     }
 }
@@ -504,6 +882,8 @@ impl Default for PrivacyConfig {
             show_activity: true,
             show_activity_target: true,
             show_systems: true,
+            project_allowlist: Vec::new(),
+            project_denylist: Vec::new(),
         }
     }
 }
@@ -529,13 +909,103 @@ impl Default for DisplayConfig {
             small_image_key: "openai".to_string(),
             small_text: "OpenAI".to_string(),
             activity_small_image_keys: ActivitySmallImageKeys::default(),
+            buttons: Vec::new(),
             terminal_logo_mode: TerminalLogoMode::Auto,
             terminal_logo_path: None,
+            banner_path: None,
             presence_layout: PresenceLayoutConfig::default(),
+            recent_active_marker: ">".to_string(),
+            recent_line_template: String::new(),
+            max_models_shown: 2,
+            codex_child_fallback: true,
+            show_aggregate_cost: false,
+            activity_emoji: false,
+            show_model_rates: false,
+            show_reset_countdown: false,
+            state_parts_order: Vec::new(),
+            state_separator: codex_presence_core::DEFAULT_STATE_SEPARATOR.to_string(),
+            details_template: String::new(),
+            idle_details: String::new(),
+            idle_state: String::new(),
+            idle_messages: Vec::new(),
+            clear_when_idle: false,
+            timer_origin: TimerOrigin::Session,
+            mouse: false,
+            large_text_shows_uptime: false,
+            limits_follow_active: false,
+            limits_aggregate: LimitsAggregate::Latest,
+            show_idle_sessions: false,
+            iterm_badge: false,
+            takeover_toast: false,
+            state_template: String::new(),
+            min_bar_width: 12,
+            tool_activity_map: BTreeMap::new(),
+            theme: UiTheme::Dark,
+            recent_sort: RecentSort::Rank,
+            bar_style: BarStyle::Ascii,
+            bar_chars: None,
         }
     }
 }
 
+impl DisplayConfig {
+    pub fn effective_bar_chars(&self) -> BarChars {
+        self.bar_chars.unwrap_or(self.bar_style.chars())
+    }
+
+    pub fn effective_large_image_key(&self) -> String {
+        self.with_env_overrides(|name| env::var(name).ok())
+            .large_image_key
+    }
+
+    pub fn effective_desktop_large_image_key(&self) -> String {
+        self.with_env_overrides(|name| env::var(name).ok())
+            .desktop_large_image_key
+    }
+
+    pub fn effective_small_image_key(&self) -> String {
+        self.with_env_overrides(|name| env::var(name).ok())
+            .small_image_key
+    }
+
+    pub fn effective_small_text(&self) -> String {
+        self.with_env_overrides(|name| env::var(name).ok())
+            .small_text
+    }
+
+    fn with_env_overrides(&self, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let mut display = self.clone();
+        let overrides = [
+            (
+                "CODEX_PRESENCE_LARGE_IMAGE_KEY",
+                &mut display.large_image_key,
+            ),
+            (
+                "CODEX_PRESENCE_DESKTOP_LARGE_IMAGE_KEY",
+                &mut display.desktop_large_image_key,
+            ),
+            (
+                "CODEX_PRESENCE_SMALL_IMAGE_KEY",
+                &mut display.small_image_key,
+            ),
+            ("CODEX_PRESENCE_SMALL_TEXT", &mut display.small_text),
+        ];
+        for (name, field) in overrides {
+            *field = display_override_from(lookup(name).as_deref(), field);
+        }
+        normalize_codex_display(&mut display, &DisplayConfig::default());
+        display
+    }
+}
+
+fn display_override_from(env_value: Option<&str>, configured: &str) -> String {
+    env_value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(configured)
+        .to_string()
+}
+
 impl PresenceConfig {
     pub fn load_or_init() -> Result<Self> {
         let cfg_path = config_path();
@@ -545,12 +1015,30 @@ impl PresenceConfig {
             })?;
         }
 
-        if cfg_path.exists() {
-            Self::load_from_path(&cfg_path)
+        let legacy_path = config_path_in(&codex_home(), active_profile());
+        let mut cfg = if cfg_path.exists() {
+            Self::load_from_path(&cfg_path)?
+        } else if legacy_path != cfg_path && legacy_path.exists() {
+            let migrated_path = match legacy_path.file_name() {
+                Some(name) => cfg_path.with_file_name(name),
+                None => cfg_path.clone(),
+            };
+            let cfg = Self::load_from_path(&legacy_path)?;
+            cfg.save_to_path(&migrated_path)?;
+            cfg
         } else {
             let cfg = PresenceConfig::default();
             cfg.save()?;
-            Ok(cfg)
+            cfg
+        };
+        cfg.apply_stealth_override(stealth_requested());
+        Ok(cfg)
+    }
+
+    pub fn apply_stealth_override(&mut self, forced: bool) {
+        if forced && self.stealth_restore_privacy.is_none() {
+            self.stealth_restore_privacy = Some(self.privacy.enabled);
+            self.privacy.enabled = true;
         }
     }
 
@@ -559,14 +1047,200 @@ impl PresenceConfig {
         self.save_to_path(&path)
     }
 
+    pub fn set_and_save(&mut self, key: &str, value: &str) -> Result<()> {
+        self.set_value(key, value)?;
+        self.normalize_for_runtime();
+        self.save()
+    }
+
+    pub fn set_value(&mut self, key: &str, value: &str) -> Result<()> {
+        let key = key.trim();
+        let value = value.trim();
+        match key {
+            "presence_enabled" => self.presence_enabled = parse_config_bool(key, value)?,
+            "privacy.enabled" => {
+                let enabled = parse_config_bool(key, value)?;
+                match self.stealth_restore_privacy.as_mut() {
+                    Some(restore) => *restore = enabled,
+                    None => self.privacy.enabled = enabled,
+                }
+            }
+            "privacy.show_project_name" => {
+                self.privacy.show_project_name = parse_config_bool(key, value)?
+            }
+            "privacy.show_git_branch" => {
+                self.privacy.show_git_branch = parse_config_bool(key, value)?
+            }
+            "privacy.show_model" => self.privacy.show_model = parse_config_bool(key, value)?,
+            "privacy.show_tokens" => self.privacy.show_tokens = parse_config_bool(key, value)?,
+            "privacy.show_cost" => self.privacy.show_cost = parse_config_bool(key, value)?,
+            "privacy.show_limits" => self.privacy.show_limits = parse_config_bool(key, value)?,
+            "privacy.show_credits" => self.privacy.show_credits = parse_config_bool(key, value)?,
+            "privacy.show_context" => self.privacy.show_context = parse_config_bool(key, value)?,
+            "privacy.show_activity" => self.privacy.show_activity = parse_config_bool(key, value)?,
+            "privacy.show_activity_target" => {
+                self.privacy.show_activity_target = parse_config_bool(key, value)?
+            }
+            "privacy.show_systems" => self.privacy.show_systems = parse_config_bool(key, value)?,
+            "display.large_image_key" => {
+                let allowed = DisplayConfig::default().large_image_key;
+                if value != allowed {
+                    bail!("{key} only accepts `{allowed}`, got `{value}`");
+                }
+                self.display.large_image_key = allowed;
+            }
+            "display.desktop_presence_design" => {
+                self.display.desktop_presence_design = parse_config_enum(key, value)?
+            }
+            "display.terminal_logo_mode" => {
+                self.display.terminal_logo_mode = parse_config_enum(key, value)?
+            }
+            "display.terminal_logo_path" => {
+                self.display.terminal_logo_path = parse_config_optional_string(value)
+            }
+            "display.banner_path" => self.display.banner_path = parse_config_optional_string(value),
+            "display.recent_active_marker" => {
+                if value.is_empty() {
+                    bail!("{key} must not be empty");
+                }
+                self.display.recent_active_marker = value.to_string();
+            }
+            "display.recent_line_template" => self.display.recent_line_template = value.to_string(),
+            "display.max_models_shown" => {
+                self.display.max_models_shown = match value.parse::<usize>() {
+                    Ok(count) if count > 0 => count,
+                    _ => bail!("{key} expects a positive integer, got `{value}`"),
+                }
+            }
+            "display.codex_child_fallback" => {
+                self.display.codex_child_fallback = parse_config_bool(key, value)?
+            }
+            "display.show_aggregate_cost" => {
+                self.display.show_aggregate_cost = parse_config_bool(key, value)?
+            }
+            "display.show_reset_countdown" => {
+                self.display.show_reset_countdown = parse_config_bool(key, value)?
+            }
+            "display.show_model_rates" => {
+                self.display.show_model_rates = parse_config_bool(key, value)?
+            }
+            "display.activity_emoji" => {
+                self.display.activity_emoji = parse_config_bool(key, value)?
+            }
+            "display.state_parts_order" => {
+                let parts = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|part| !part.is_empty())
+                    .map(str::to_ascii_lowercase)
+                    .collect::<Vec<_>>();
+                if let Some(unknown) = parts
+                    .iter()
+                    .find(|part| PresenceFieldId::parse(part).is_none())
+                {
+                    bail!("{key} does not accept `{unknown}`");
+                }
+                self.display.state_parts_order = parts;
+            }
+            "display.state_separator" => {
+                if value.is_empty() {
+                    bail!("{key} cannot be empty");
+                }
+                self.display.state_separator = value.to_string();
+            }
+            "display.details_template" => self.display.details_template = value.to_string(),
+            "display.idle_details" => self.display.idle_details = value.to_string(),
+            "display.idle_state" => self.display.idle_state = value.to_string(),
+            "display.idle_messages" => {
+                self.display.idle_messages = value
+                    .split('|')
+                    .map(str::trim)
+                    .filter(|message| !message.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+            "display.clear_when_idle" => {
+                self.display.clear_when_idle = parse_config_bool(key, value)?
+            }
+            "display.state_template" => self.display.state_template = value.to_string(),
+            "display.min_bar_width" => {
+                self.display.min_bar_width = value
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("{key} expects a whole number, got `{value}`"))?
+            }
+            "display.theme" => self.display.theme = parse_config_enum(key, value)?,
+            "display.recent_sort" => self.display.recent_sort = parse_config_enum(key, value)?,
+            "display.timer_origin" => self.display.timer_origin = parse_config_enum(key, value)?,
+            "display.mouse" => self.display.mouse = parse_config_bool(key, value)?,
+            "display.large_text_shows_uptime" => {
+                self.display.large_text_shows_uptime = parse_config_bool(key, value)?
+            }
+            "display.limits_follow_active" => {
+                self.display.limits_follow_active = parse_config_bool(key, value)?
+            }
+            "display.limits_aggregate" => {
+                self.display.limits_aggregate = parse_config_enum(key, value)?
+            }
+            "display.show_idle_sessions" => {
+                self.display.show_idle_sessions = parse_config_bool(key, value)?
+            }
+            "display.iterm_badge" => self.display.iterm_badge = parse_config_bool(key, value)?,
+            "display.takeover_toast" => {
+                self.display.takeover_toast = parse_config_bool(key, value)?
+            }
+            "display.bar_style" => self.display.bar_style = parse_config_enum(key, value)?,
+            "display.bar_chars" => self.display.bar_chars = parse_config_bar_chars(key, value)?,
+            "metrics.window" => self.metrics.window = parse_config_enum(key, value)?,
+            "openai_plan.mode" => self.openai_plan.mode = parse_config_enum(key, value)?,
+            "openai_plan.tier" => self.openai_plan.tier = parse_config_enum(key, value)?,
+            "openai_plan.show_price" => {
+                self.openai_plan.show_price = parse_config_bool(key, value)?
+            }
+            "openai_plan.custom_price_usd" => {
+                self.openai_plan.custom_price_usd = match parse_config_optional_string(value) {
+                    Some(raw) => Some(raw.parse::<u32>().map_err(|_| {
+                        anyhow::anyhow!("{key} expects a whole number of USD, got `{value}`")
+                    })?),
+                    None => None,
+                }
+            }
+            "pricing.display_currency" => {
+                self.pricing.display_currency = parse_config_enum(key, value)?
+            }
+            "pricing.usd_rate" => self.pricing.usd_rate = parse_config_positive_f64(key, value)?,
+            "pricing.catalog_path" => {
+                self.pricing.catalog_path = parse_config_optional_string(value)
+            }
+            "pricing.monthly_budget_usd" => {
+                self.pricing.monthly_budget_usd = match parse_config_optional_string(value) {
+                    Some(raw) => Some(parse_config_positive_f64(key, &raw)?),
+                    None => None,
+                }
+            }
+            _ => bail!(
+                "unknown config key `{key}`; supported keys: {}",
+                SETTABLE_CONFIG_KEYS.join(", ")
+            ),
+        }
+        Ok(())
+    }
+
     pub fn reload_from_disk(&mut self) -> bool {
         self.reload_from_path(&config_path())
     }
 
     pub fn reload_from_path(&mut self, path: &Path) -> bool {
+        let stamp = ConfigFileStamp::of(path);
+        if self.file_stamp.matches(stamp) {
+            return false;
+        }
         match Self::load_from_path(path) {
-            Ok(reloaded) => {
-                let changed = *self != reloaded;
+            Ok(mut reloaded) => {
+                reloaded.apply_stealth_override(stealth_requested());
+                let changed = PresenceConfig {
+                    file_stamp: self.file_stamp,
+                    ..reloaded.clone()
+                } != *self;
                 *self = reloaded;
                 changed
             }
@@ -576,6 +1250,7 @@ impl PresenceConfig {
                     error = %error,
                     "presence config reload failed; keeping the last valid configuration"
                 );
+                self.file_stamp = stamp;
                 false
             }
         }
@@ -594,17 +1269,34 @@ impl PresenceConfig {
     fn load_from_path(path: &Path) -> Result<Self> {
         let raw = fs::read_to_string(path)
             .with_context(|| format!("failed to read {}", path.display()))?;
-        let mut parsed: PresenceConfig = serde_json::from_str(&raw)
-            .with_context(|| format!("invalid JSON in {}", path.display()))?;
+        let mut parsed = ConfigFormat::for_path(path)
+            .parse(&raw)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        let migrating = parsed.schema_version < CONFIG_SCHEMA_VERSION;
         if parsed.normalize_for_runtime() {
+            if migrating {
+                let backup = config_backup_path(path);
+                fs::copy(path, &backup)
+                    .with_context(|| format!("failed to back up config to {}", backup.display()))?;
+            }
             parsed.save_to_path(path)?;
         }
+        parsed.file_stamp = ConfigFileStamp::of(path);
         Ok(parsed)
     }
 
     fn save_to_path(&self, path: &Path) -> Result<()> {
-        write_json_pretty_atomic(path, self)
-            .with_context(|| format!("failed to write {}", path.display()))?;
+        let format = ConfigFormat::for_path(path);
+        let result = match self.stealth_restore_privacy {
+            Some(privacy_enabled) => {
+                let mut persisted = self.clone();
+                persisted.privacy.enabled = privacy_enabled;
+                persisted.stealth_restore_privacy = None;
+                format.write(path, &persisted)
+            }
+            None => format.write(path, self),
+        };
+        result.with_context(|| format!("failed to write {}", path.display()))?;
         Ok(())
     }
 
@@ -616,6 +1308,19 @@ impl PresenceConfig {
         Some(codex_client_id_for_surface(surface, self.display.desktop_presence_design).to_string())
     }
 
+    pub fn effective(&self) -> Self {
+        self.effective_with(|name| env::var(name).ok())
+    }
+
+    fn effective_with(&self, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let mut effective = self.clone();
+        effective.discord_client_id = self.effective_client_id_for_surface(PresenceSurface::Cli);
+        effective.discord_client_id_desktop =
+            self.effective_client_id_for_surface(PresenceSurface::Desktop);
+        effective.display = self.display.with_env_overrides(lookup);
+        effective
+    }
+
     pub fn normalize_for_runtime(&mut self) -> bool {
         self.normalize_and_migrate()
     }
@@ -696,6 +1401,7 @@ impl PresenceConfig {
             &mut self.display.activity_small_image_keys.reading,
             &mut self.display.activity_small_image_keys.editing,
             &mut self.display.activity_small_image_keys.running,
+            &mut self.display.activity_small_image_keys.archiving,
             &mut self.display.activity_small_image_keys.waiting,
             &mut self.display.activity_small_image_keys.idle,
         ] {
@@ -712,6 +1418,15 @@ impl PresenceConfig {
             self.display.terminal_logo_path = None;
             changed = true;
         }
+        if self
+            .display
+            .banner_path
+            .as_deref()
+            .is_some_and(|path| path.trim().is_empty())
+        {
+            self.display.banner_path = None;
+            changed = true;
+        }
         if normalize_pricing_config(&mut self.pricing) {
             changed = true;
         }
@@ -720,6 +1435,12 @@ impl PresenceConfig {
     }
 }
 
+fn config_backup_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".bak");
+    path.with_file_name(file_name)
+}
+
 fn codex_client_id_for_surface(
     surface: PresenceSurface,
     desktop_design: DesktopPresenceDesign,
@@ -735,6 +1456,18 @@ fn codex_client_id_for_surface(
     }
 }
 
+impl RuntimeSettings {
+    pub fn with_overrides(mut self, poll_secs: Option<u64>, stale_secs: Option<u64>) -> Self {
+        if let Some(poll_secs) = poll_secs {
+            self.poll_interval = Duration::from_secs(poll_secs);
+        }
+        if let Some(stale_secs) = stale_secs {
+            self.stale_threshold = Duration::from_secs(stale_secs);
+        }
+        self
+    }
+}
+
 pub fn runtime_settings() -> RuntimeSettings {
     let sticky_seconds = env_u64(
         "CODEX_PRESENCE_ACTIVE_STICKY_SECONDS",
@@ -751,6 +1484,11 @@ pub fn runtime_settings() -> RuntimeSettings {
             "CODEX_PRESENCE_POLL_SECONDS",
             DEFAULT_POLL_SECONDS,
         )),
+        limit_warn_percent: env_u64(
+            "CODEX_PRESENCE_LIMIT_WARN_PERCENT",
+            DEFAULT_LIMIT_WARN_PERCENT,
+        )
+        .min(100) as f64,
     }
 }
 
@@ -796,8 +1534,96 @@ pub fn sessions_paths() -> Vec<PathBuf> {
     ordered
 }
 
+pub fn is_cross_namespace_sessions_root(path: &Path) -> bool {
+    let normalized = path
+        .to_string_lossy()
+        .replace('\\', "/")
+        .to_ascii_lowercase();
+    if normalized.starts_with("//wsl$/") || normalized.starts_with("//wsl.localhost/") {
+        return true;
+    }
+    normalized
+        .strip_prefix("/mnt/")
+        .and_then(|rest| rest.split('/').next())
+        .is_some_and(|drive| drive.len() == 1 && drive.chars().all(|ch| ch.is_ascii_alphabetic()))
+}
+
+static ACTIVE_PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
+pub fn set_active_profile(profile: Option<&str>) -> Result<()> {
+    let profile = match profile.map(str::trim) {
+        Some(name) if is_valid_profile_name(name) => Some(name.to_string()),
+        Some(name) => {
+            bail!("invalid profile `{name}`; use letters, digits, `-`, or `_` (max 64 characters)")
+        }
+        None => None,
+    };
+    if ACTIVE_PROFILE.set(profile).is_err() {
+        bail!("config profile was already selected");
+    }
+    Ok(())
+}
+
+pub fn active_profile() -> Option<&'static str> {
+    ACTIVE_PROFILE.get().and_then(Option::as_deref)
+}
+
+fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'))
+}
+
+pub fn profile_file_name(stem: &str, extension: &str, profile: Option<&str>) -> String {
+    match profile {
+        Some(name) => format!("{stem}.{name}.{extension}"),
+        None => format!("{stem}.{extension}"),
+    }
+}
+
+pub fn profile_scoped_path(base: &Path, stem: &str, extension: &str) -> PathBuf {
+    base.join(profile_file_name(stem, extension, active_profile()))
+}
+
+pub fn presence_config_home() -> PathBuf {
+    presence_config_home_from(env::var_os("XDG_CONFIG_HOME"), codex_home())
+}
+
+fn presence_config_home_from(
+    xdg_config_home: Option<std::ffi::OsString>,
+    codex_home: PathBuf,
+) -> PathBuf {
+    if cfg!(windows) {
+        return codex_home;
+    }
+    xdg_config_home
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .map(|path| path.join("codex-discord-presence"))
+        .unwrap_or(codex_home)
+}
+
 pub fn config_path() -> PathBuf {
-    codex_home().join("discord-presence-config.json")
+    config_path_in(&presence_config_home(), active_profile())
+}
+
+fn config_path_in(home: &Path, profile: Option<&str>) -> PathBuf {
+    let toml_path = home.join(profile_file_name(
+        "discord-presence-config",
+        "toml",
+        profile,
+    ));
+    if toml_path.is_file() {
+        toml_path
+    } else {
+        home.join(profile_file_name(
+            "discord-presence-config",
+            "json",
+            profile,
+        ))
+    }
 }
 
 pub fn global_state_paths() -> Vec<PathBuf> {
@@ -823,11 +1649,11 @@ pub fn global_state_paths() -> Vec<PathBuf> {
 }
 
 pub fn lock_path() -> PathBuf {
-    codex_home().join("codex-discord-presence.lock")
+    profile_scoped_path(&codex_home(), "codex-discord-presence", "lock")
 }
 
 pub fn instance_meta_path() -> PathBuf {
-    codex_home().join("codex-discord-presence.instance.json")
+    profile_scoped_path(&codex_home(), "codex-discord-presence", "instance.json")
 }
 
 fn env_u64(name: &str, default: u64) -> u64 {
@@ -838,6 +1664,290 @@ fn env_u64(name: &str, default: u64) -> u64 {
         .unwrap_or(default)
 }
 
+fn parse_config_bool(key: &str, value: &str) -> Result<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Ok(true),
+        "false" | "0" | "no" | "off" => Ok(false),
+        _ => bail!("{key} expects true or false, got `{value}`"),
+    }
+}
+
+fn parse_config_enum<T: DeserializeOwned>(key: &str, value: &str) -> Result<T> {
+    serde_json::from_value(serde_json::Value::String(value.to_ascii_lowercase()))
+        .map_err(|_| anyhow::anyhow!("{key} does not accept `{value}`"))
+}
+
+fn parse_config_bar_chars(key: &str, value: &str) -> Result<Option<BarChars>> {
+    if value.eq_ignore_ascii_case("default") {
+        return Ok(None);
+    }
+    let mut chars = value.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(filled), Some(empty), None) if !filled.is_control() && !empty.is_control() => {
+            Ok(Some(BarChars { filled, empty }))
+        }
+        _ => bail!("{key} expects two characters (filled then empty) or `default`, got `{value}`"),
+    }
+}
+
+fn parse_config_positive_f64(key: &str, value: &str) -> Result<f64> {
+    match value.parse::<f64>() {
+        Ok(number) if number.is_finite() && number > 0.0 => Ok(number),
+        _ => bail!("{key} expects a positive number, got `{value}`"),
+    }
+}
+
+fn parse_config_optional_string(value: &str) -> Option<String> {
+    match value {
+        "" | "none" | "null" => None,
+        other => Some(other.to_string()),
+    }
+}
+
+pub fn validate_config_file(path: &Path) -> Result<Vec<ConfigFinding>> {
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(validate_config_text(&raw, ConfigFormat::for_path(path)))
+}
+
+pub fn validate_config_text(raw: &str, format: ConfigFormat) -> Vec<ConfigFinding> {
+    let mut value = match format.parse_value(raw) {
+        Ok(value) => value,
+        Err(error) => return vec![ConfigFinding::new("file", format!("{error:#}"))],
+    };
+    let mut findings = Vec::new();
+    check_enum_value::<OpenAiPlanTier>(&mut value, "openai_plan", "tier", &mut findings);
+    check_enum_value::<OpenAiPlanMode>(&mut value, "openai_plan", "mode", &mut findings);
+    check_enum_value::<TerminalLogoMode>(
+        &mut value,
+        "display",
+        "terminal_logo_mode",
+        &mut findings,
+    );
+    check_enum_value::<DesktopPresenceDesign>(
+        &mut value,
+        "display",
+        "desktop_presence_design",
+        &mut findings,
+    );
+    check_enum_value::<UiTheme>(&mut value, "display", "theme", &mut findings);
+    check_enum_value::<RecentSort>(&mut value, "display", "recent_sort", &mut findings);
+    check_enum_value::<BarStyle>(&mut value, "display", "bar_style", &mut findings);
+    check_enum_value::<TimerOrigin>(&mut value, "display", "timer_origin", &mut findings);
+    check_enum_value::<LimitsAggregate>(&mut value, "display", "limits_aggregate", &mut findings);
+    check_enum_value::<MetricsWindow>(&mut value, "metrics", "window", &mut findings);
+    check_enum_value::<Currency>(&mut value, "pricing", "display_currency", &mut findings);
+
+    let parsed: PresenceConfig = match serde_json::from_value(value) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            findings.push(ConfigFinding::new("config", error.to_string()));
+            return findings;
+        }
+    };
+
+    let display = &parsed.display;
+    for (key, text) in [
+        ("display.large_image_key", &display.large_image_key),
+        ("display.large_text", &display.large_text),
+        (
+            "display.desktop_large_image_key",
+            &display.desktop_large_image_key,
+        ),
+        ("display.desktop_large_text", &display.desktop_large_text),
+        ("display.small_image_key", &display.small_image_key),
+        ("display.small_text", &display.small_text),
+    ] {
+        if text.trim().is_empty() {
+            findings.push(ConfigFinding::new(key, "is empty"));
+        }
+    }
+    if display
+        .bar_chars
+        .is_some_and(|chars| chars.filled.is_control() || chars.empty.is_control())
+    {
+        findings.push(ConfigFinding::new(
+            "display.bar_chars",
+            "filled and empty must be printable characters",
+        ));
+    }
+    if display.buttons.len() > 2 {
+        findings.push(ConfigFinding::new(
+            "display.buttons",
+            format!(
+                "Discord shows at most 2 buttons, got {}",
+                display.buttons.len()
+            ),
+        ));
+    }
+    for (index, button) in display.buttons.iter().enumerate() {
+        if !button.is_valid() {
+            findings.push(ConfigFinding::new(
+                format!("display.buttons.{index}"),
+                "needs a 1-32 character label and an http(s) URL up to 512 characters",
+            ));
+        }
+    }
+    if !display.idle_state.trim().is_empty()
+        && display
+            .idle_messages
+            .iter()
+            .any(|message| !message.trim().is_empty())
+    {
+        findings.push(ConfigFinding::new(
+            "display.idle_messages",
+            "ignored while display.idle_state is set",
+        ));
+    }
+    for part in &display.state_parts_order {
+        if PresenceFieldId::parse(part).is_none() {
+            findings.push(ConfigFinding::new(
+                "display.state_parts_order",
+                format!("unknown part `{part}`"),
+            ));
+        }
+    }
+    for (key, template, placeholders) in [
+        (
+            "display.details_template",
+            &display.details_template,
+            PRESENCE_TEMPLATE_PLACEHOLDERS,
+        ),
+        (
+            "display.state_template",
+            &display.state_template,
+            PRESENCE_TEMPLATE_PLACEHOLDERS,
+        ),
+        (
+            "display.recent_line_template",
+            &display.recent_line_template,
+            RECENT_LINE_PLACEHOLDERS,
+        ),
+    ] {
+        for name in template_placeholders(template) {
+            if !placeholders.contains(&name) {
+                findings.push(ConfigFinding::new(
+                    key,
+                    format!("unknown placeholder `{{{name}}}`"),
+                ));
+            }
+        }
+    }
+    let usd_rate = parsed.pricing.usd_rate;
+    if !usd_rate.is_finite() || usd_rate <= 0.0 {
+        findings.push(ConfigFinding::new(
+            "pricing.usd_rate",
+            format!("rate must be greater than zero, got {usd_rate}"),
+        ));
+    }
+    for (model, pricing) in &parsed.pricing.overrides {
+        for (field, rate) in [
+            ("input_per_million", pricing.input_per_million),
+            ("output_per_million", pricing.output_per_million),
+        ] {
+            if !rate.is_finite() || rate <= 0.0 {
+                findings.push(ConfigFinding::new(
+                    format!("pricing.overrides.{model}.{field}"),
+                    format!("rate must be greater than zero, got {rate}"),
+                ));
+            }
+        }
+    }
+    if let Some(logo) = display
+        .terminal_logo_path
+        .as_deref()
+        .filter(|path| !path.trim().is_empty())
+        && !Path::new(logo).exists()
+    {
+        findings.push(ConfigFinding::new(
+            "display.terminal_logo_path",
+            format!("file not found: {logo}"),
+        ));
+    }
+    if let Some(banner) = display
+        .banner_path
+        .as_deref()
+        .filter(|path| !path.trim().is_empty())
+        && !Path::new(banner).exists()
+    {
+        findings.push(ConfigFinding::new(
+            "display.banner_path",
+            format!("file not found: {banner}"),
+        ));
+    }
+
+    let mut normalized = parsed.clone();
+    if normalized.normalize_and_migrate()
+        && let (Ok(before), Ok(after)) = (
+            serde_json::to_value(&parsed),
+            serde_json::to_value(&normalized),
+        )
+    {
+        let mut corrected = Vec::new();
+        diff_value_paths("", &before, &after, &mut corrected);
+        for key in corrected {
+            if !findings
+                .iter()
+                .any(|finding| finding.key == key || finding.key.starts_with(&format!("{key}.")))
+            {
+                findings.push(ConfigFinding::new(key, "corrected by normalization"));
+            }
+        }
+    }
+    findings
+}
+
+fn check_enum_value<T: DeserializeOwned>(
+    value: &mut serde_json::Value,
+    section: &str,
+    field: &str,
+    findings: &mut Vec<ConfigFinding>,
+) {
+    let Some(object) = value
+        .get_mut(section)
+        .and_then(serde_json::Value::as_object_mut)
+    else {
+        return;
+    };
+    let Some(raw) = object.get(field) else {
+        return;
+    };
+    if serde_json::from_value::<T>(raw.clone()).is_err() {
+        findings.push(ConfigFinding::new(
+            format!("{section}.{field}"),
+            format!("unknown value {raw}"),
+        ));
+        object.remove(field);
+    }
+}
+
+fn diff_value_paths(
+    prefix: &str,
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+    paths: &mut Vec<String>,
+) {
+    match (before, after) {
+        (serde_json::Value::Object(left), serde_json::Value::Object(right)) => {
+            let keys: std::collections::BTreeSet<&String> =
+                left.keys().chain(right.keys()).collect();
+            for key in keys {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                match (left.get(key), right.get(key)) {
+                    (Some(l), Some(r)) => diff_value_paths(&path, l, r, paths),
+                    _ => paths.push(path),
+                }
+            }
+        }
+        _ if before != after => paths.push(prefix.to_string()),
+        _ => {}
+    }
+}
+
 fn is_missing(value: &Option<String>) -> bool {
     value.as_ref().map(|v| v.trim().is_empty()).unwrap_or(true)
 }
@@ -880,6 +1990,7 @@ fn has_activity_image_overrides(keys: &ActivitySmallImageKeys) -> bool {
         || keys.reading.is_some()
         || keys.editing.is_some()
         || keys.running.is_some()
+        || keys.archiving.is_some()
         || keys.waiting.is_some()
         || keys.idle.is_some()
 }
@@ -977,6 +2088,12 @@ fn normalize_pricing_config(pricing: &mut PricingConfig) -> bool {
         changed = true;
     }
 
+    if !pricing.usd_rate.is_finite() || pricing.usd_rate <= 0.0 {
+        pricing.usd_rate = 1.0;
+        pricing.display_currency = Currency::Usd;
+        changed = true;
+    }
+
     changed
 }
 
@@ -1050,7 +2167,21 @@ fn include_wsl_session_roots() -> bool {
         || parse_bool_flag(env::var("CC_PRESENCE_INCLUDE_WSL").ok().as_deref())
 }
 
-#[cfg(any(windows, test))]
+pub fn git_timeout() -> Duration {
+    Duration::from_millis(env_u64(
+        "CODEX_PRESENCE_GIT_TIMEOUT_MS",
+        DEFAULT_GIT_TIMEOUT_MS,
+    ))
+}
+
+pub fn show_all_sessions_requested() -> bool {
+    parse_bool_flag(env::var("CODEX_PRESENCE_SHOW_ALL").ok().as_deref())
+}
+
+fn stealth_requested() -> bool {
+    parse_bool_flag(env::var("CODEX_PRESENCE_STEALTH").ok().as_deref())
+}
+
 fn parse_bool_flag(value: Option<&str>) -> bool {
     matches!(
         value.map(str::trim).map(str::to_ascii_lowercase).as_deref(),
@@ -1084,45 +2215,61 @@ fn windows_wsl_sessions_candidates() -> Vec<PathBuf> {
 
 #[cfg(windows)]
 fn windows_wsl_distro_names() -> Vec<String> {
-    let output = crate::util::silent_command("wsl.exe")
-        .args(["-l", "-q"])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::null())
-        .output();
-
-    let Ok(output) = output else {
+    static DISTROS: OnceLock<Vec<String>> = OnceLock::new();
+    if let Some(distros) = DISTROS.get() {
+        return distros.clone();
+    }
+    let mut command = crate::util::silent_command("wsl.exe");
+    command.args(["-l", "-q"]);
+    let Some(stdout) = wsl_output_with_timeout(command, WSL_COMMAND_TIMEOUT) else {
         return Vec::new();
     };
-    if !output.status.success() {
-        return Vec::new();
-    }
+    let distros = parse_wsl_distro_list(&decode_windows_text_output(&stdout));
+    DISTROS.get_or_init(|| distros).clone()
+}
 
-    decode_windows_text_output(&output.stdout)
-        .lines()
-        .map(|line| line.trim().trim_start_matches('*').trim().to_string())
-        .filter(|line| !line.is_empty())
-        .collect()
+#[cfg(any(windows, test))]
+fn parse_wsl_distro_list(text: &str) -> Vec<String> {
+    let mut distros: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let name = line
+            .trim_matches(|ch: char| ch.is_whitespace() || ch == '\0' || ch == '\u{feff}')
+            .trim_start_matches('*')
+            .trim();
+        let name = name.strip_suffix("(Default)").unwrap_or(name).trim();
+        if name.is_empty() || distros.iter().any(|known| known == name) {
+            continue;
+        }
+        distros.push(name.to_string());
+    }
+    distros
 }
 
 #[cfg(windows)]
 fn wsl_home_for_distro(distro: &str) -> Option<String> {
-    let output = crate::util::silent_command("wsl.exe")
-        .args(["-d", distro, "--", "sh", "-lc", "printf %s \"$HOME\""])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::null())
-        .output()
-        .ok()?;
-
-    if !output.status.success() {
-        return None;
-    }
+    let mut command = crate::util::silent_command("wsl.exe");
+    command.args(["-d", distro, "--", "sh", "-lc", "printf %s \"$HOME\""]);
+    let stdout = wsl_output_with_timeout(command, WSL_COMMAND_TIMEOUT)?;
 
-    let home = decode_windows_text_output(&output.stdout)
-        .trim()
-        .to_string();
+    let home = decode_windows_text_output(&stdout).trim().to_string();
     if home.is_empty() { None } else { Some(home) }
 }
 
+#[cfg(windows)]
+fn wsl_output_with_timeout(command: Command, timeout: Duration) -> Option<Vec<u8>> {
+    match crate::util::command_stdout_until(command, Instant::now() + timeout, WSL_WAIT_POLL) {
+        crate::util::CommandStdout::Success(stdout) => Some(stdout),
+        crate::util::CommandStdout::Failed => None,
+        crate::util::CommandStdout::TimedOut => {
+            warn!(
+                timeout_ms = timeout.as_millis() as u64,
+                "wsl.exe probe timed out"
+            );
+            None
+        }
+    }
+}
+
 #[cfg(windows)]
 fn wsl_home_to_unc_sessions_path(distro: &str, home: &str) -> PathBuf {
     let mut unc = format!(r"\\wsl.localhost\{}", distro);
@@ -1176,6 +2323,372 @@ mod tests {
         );
     }
 
+    #[test]
+    fn display_env_override_wins_over_config() {
+        assert_eq!(
+            display_override_from(Some(" host-logo "), "codex-logo"),
+            "host-logo"
+        );
+        assert_eq!(display_override_from(None, "codex-logo"), "codex-logo");
+    }
+
+    #[test]
+    fn empty_display_env_override_falls_through_to_config() {
+        assert_eq!(display_override_from(Some(""), "openai"), "openai");
+        assert_eq!(display_override_from(Some("   "), "openai"), "openai");
+    }
+
+    #[test]
+    fn cli_runtime_overrides_win_over_env_settings() {
+        let env_settings = RuntimeSettings {
+            stale_threshold: Duration::from_secs(DEFAULT_STALE_SECONDS),
+            active_sticky_window: Duration::from_secs(DEFAULT_ACTIVE_STICKY_SECONDS),
+            poll_interval: Duration::from_secs(7),
+            limit_warn_percent: 10.0,
+        };
+
+        let unchanged = env_settings.clone().with_overrides(None, None);
+        assert_eq!(unchanged.poll_interval, Duration::from_secs(7));
+        assert_eq!(
+            unchanged.stale_threshold,
+            Duration::from_secs(DEFAULT_STALE_SECONDS)
+        );
+
+        let overridden = env_settings.with_overrides(Some(2), Some(45));
+        assert_eq!(overridden.poll_interval, Duration::from_secs(2));
+        assert_eq!(overridden.stale_threshold, Duration::from_secs(45));
+        assert_eq!(
+            overridden.active_sticky_window,
+            Duration::from_secs(DEFAULT_ACTIVE_STICKY_SECONDS)
+        );
+    }
+
+    #[test]
+    fn stealth_override_forces_privacy_without_persisting_it() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("discord-presence-config.json");
+        let mut cfg = PresenceConfig::default();
+        assert!(!cfg.privacy.enabled);
+
+        cfg.apply_stealth_override(false);
+        assert!(!cfg.privacy.enabled);
+
+        cfg.apply_stealth_override(true);
+        assert!(cfg.privacy.enabled);
+        cfg.save_to_path(&path).expect("save");
+
+        let persisted = PresenceConfig::load_from_path(&path).expect("load");
+        assert!(!persisted.privacy.enabled);
+        assert_eq!(persisted.stealth_restore_privacy, None);
+    }
+
+    #[test]
+    fn toml_config_is_preferred_and_round_trips_in_toml() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(
+            config_path_in(dir.path(), None),
+            dir.path().join("discord-presence-config.json")
+        );
+
+        let path = dir.path().join("discord-presence-config.toml");
+        fs::write(
+            &path,
+            "schema_version = 13\npresence_enabled = false\n\n[privacy]\nshow_cost = false\n\n[pricing]\ndisplay_currency = \"eur\"\nusd_rate = 0.9\n\n[pricing.aliases]\n\"my-model\" = \"gpt-5.4\"\n",
+        )
+        .expect("write toml");
+        assert_eq!(config_path_in(dir.path(), None), path);
+
+        let loaded = PresenceConfig::load_from_path(&path).expect("load toml");
+        assert!(!loaded.presence_enabled);
+        assert!(!loaded.privacy.show_cost);
+        assert_eq!(loaded.pricing.display_currency, Currency::Eur);
+        assert_eq!(
+            loaded.pricing.aliases.get("my-model").map(String::as_str),
+            Some("gpt-5.4")
+        );
+
+        loaded.save_to_path(&path).expect("save toml");
+        let raw = fs::read_to_string(&path).expect("read toml");
+        assert!(raw.contains("[pricing]"));
+        assert!(serde_json::from_str::<serde_json::Value>(&raw).is_err());
+        let reloaded = PresenceConfig::load_from_path(&path).expect("reload toml");
+        assert_eq!(
+            PresenceConfig {
+                file_stamp: loaded.file_stamp,
+                ..reloaded
+            },
+            loaded
+        );
+    }
+
+    #[test]
+    fn profiles_resolve_to_distinct_config_and_lock_paths() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let work = config_path_in(dir.path(), Some("work"));
+        let stream = config_path_in(dir.path(), Some("stream"));
+        assert_eq!(work, dir.path().join("discord-presence-config.work.json"));
+        assert_eq!(
+            stream,
+            dir.path().join("discord-presence-config.stream.json")
+        );
+        assert_ne!(work, config_path_in(dir.path(), None));
+
+        assert_eq!(
+            profile_file_name("codex-discord-presence", "lock", Some("work")),
+            "codex-discord-presence.work.lock"
+        );
+        assert_ne!(
+            profile_file_name("codex-discord-presence", "lock", Some("work")),
+            profile_file_name("codex-discord-presence", "lock", Some("stream"))
+        );
+        assert_eq!(
+            profile_file_name("codex-discord-presence", "instance.json", None),
+            "codex-discord-presence.instance.json"
+        );
+        assert!(is_valid_profile_name("work_2"));
+        assert!(!is_valid_profile_name("../work"));
+        assert!(!is_valid_profile_name(""));
+    }
+
+    #[test]
+    fn config_file_stamp_compares_mtime_and_length() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("discord-presence-config.json");
+        fs::write(&path, "{}").expect("write");
+        let stamp = ConfigFileStamp::of(&path);
+        assert_eq!(stamp, ConfigFileStamp::of(&path));
+        assert!(stamp.matches(ConfigFileStamp::of(&path)));
+
+        let modified = fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .expect("mtime");
+        fs::write(&path, "{ }").expect("rewrite");
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(modified))
+            .expect("restore mtime");
+        assert_ne!(stamp, ConfigFileStamp::of(&path));
+        assert!(!stamp.matches(ConfigFileStamp::of(&path)));
+
+        let missing = ConfigFileStamp::of(&dir.path().join("missing.json"));
+        assert_eq!(missing, ConfigFileStamp::default());
+        assert!(!missing.matches(missing));
+    }
+
+    #[test]
+    fn reload_keeps_previous_config_when_file_is_malformed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("discord-presence-config.json");
+        PresenceConfig::default().save_to_path(&path).expect("save");
+        let mut cfg = PresenceConfig::load_from_path(&path).expect("load");
+
+        assert!(!cfg.reload_from_path(&path));
+
+        fs::write(&path, "{ not json").expect("write malformed");
+        assert!(!cfg.reload_from_path(&path));
+        assert_eq!(
+            PresenceConfig {
+                file_stamp: ConfigFileStamp::default(),
+                ..cfg.clone()
+            },
+            PresenceConfig::default()
+        );
+
+        let mut edited = PresenceConfig::default();
+        edited.privacy.show_cost = false;
+        edited.display.recent_active_marker = "*".to_string();
+        edited.save_to_path(&path).expect("save edited");
+        cfg.file_stamp = ConfigFileStamp::default();
+        assert!(cfg.reload_from_path(&path));
+        assert!(!cfg.privacy.show_cost);
+        assert_eq!(cfg.display.recent_active_marker, "*");
+    }
+
+    #[test]
+    fn migration_backs_up_the_original_file_but_no_op_loads_do_not() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("discord-presence-config.json");
+        let backup = temp.path().join("discord-presence-config.json.bak");
+        let legacy = r#"{ "schema_version": 2, "privacy": { "show_cost": false } }"#;
+        fs::write(&path, legacy).expect("write legacy");
+
+        let migrated = PresenceConfig::load_from_path(&path).expect("load legacy");
+        assert_eq!(migrated.schema_version, CONFIG_SCHEMA_VERSION);
+        assert_eq!(fs::read_to_string(&backup).expect("backup"), legacy);
+
+        fs::remove_file(&backup).expect("remove backup");
+        PresenceConfig::load_from_path(&path).expect("reload current");
+        assert!(!backup.exists());
+    }
+
+    #[test]
+    fn validate_reports_findings_for_broken_config() {
+        let raw = r#"{
+            "schema_version": 2,
+            "display": {
+                "large_image_key": "",
+                "terminal_logo_path": "/definitely/missing/logo.png"
+            },
+            "pricing": {
+                "overrides": {
+                    "gpt-custom": { "input_per_million": 0.0, "output_per_million": 4.0 }
+                }
+            },
+            "openai_plan": { "tier": "platinum" }
+        }"#;
+
+        let findings = validate_config_text(raw, ConfigFormat::Json);
+        let keys: Vec<&str> = findings
+            .iter()
+            .map(|finding| finding.key.as_str())
+            .collect();
+
+        assert!(keys.contains(&"openai_plan.tier"), "{keys:?}");
+        assert!(keys.contains(&"display.large_image_key"), "{keys:?}");
+        assert!(
+            keys.contains(&"pricing.overrides.gpt-custom.input_per_million"),
+            "{keys:?}"
+        );
+        assert!(!keys.contains(&"pricing.overrides.gpt-custom.output_per_million"));
+        assert!(keys.contains(&"display.terminal_logo_path"), "{keys:?}");
+        assert!(
+            findings.contains(&ConfigFinding::new(
+                "schema_version",
+                "corrected by normalization"
+            )),
+            "{findings:?}"
+        );
+    }
+
+    #[test]
+    fn validate_flags_idle_messages_shadowed_by_idle_state() {
+        let raw = r#"{
+            "display": {
+                "idle_state": "Back soon",
+                "idle_messages": ["Stretching", "Refilling coffee"]
+            }
+        }"#;
+        assert!(
+            validate_config_text(raw, ConfigFormat::Json).contains(&ConfigFinding::new(
+                "display.idle_messages",
+                "ignored while display.idle_state is set"
+            ))
+        );
+
+        let raw = r#"{ "display": { "idle_messages": ["Stretching"] } }"#;
+        assert!(
+            !validate_config_text(raw, ConfigFormat::Json)
+                .iter()
+                .any(|finding| finding.key == "display.idle_messages")
+        );
+    }
+
+    #[test]
+    fn validate_accepts_a_normalized_default_config() {
+        let mut cfg = PresenceConfig::default();
+        cfg.normalize_and_migrate();
+        let raw = serde_json::to_string_pretty(&cfg).expect("json");
+        assert_eq!(validate_config_text(&raw, ConfigFormat::Json), Vec::new());
+        let findings = validate_config_text("{ nope", ConfigFormat::Json);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].key, "file");
+    }
+
+    #[test]
+    fn config_home_falls_back_to_codex_home_without_xdg() {
+        let codex = PathBuf::from("/home/dev/.codex");
+        assert_eq!(presence_config_home_from(None, codex.clone()), codex);
+        assert_eq!(
+            presence_config_home_from(Some("".into()), codex.clone()),
+            codex
+        );
+        assert_eq!(
+            presence_config_home_from(Some("relative/config".into()), codex.clone()),
+            codex
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn config_home_uses_xdg_config_home_when_set() {
+        let codex = PathBuf::from("/home/dev/.codex");
+        let home = presence_config_home_from(Some("/home/dev/.config".into()), codex);
+        assert_eq!(
+            home,
+            PathBuf::from("/home/dev/.config/codex-discord-presence")
+        );
+        assert_eq!(
+            config_path_in(&home, None),
+            home.join("discord-presence-config.json")
+        );
+    }
+
+    #[test]
+    fn config_set_updates_bool_string_and_enum_fields() {
+        let mut cfg = PresenceConfig::default();
+
+        cfg.set_value("privacy.show_cost", "false").expect("bool");
+        assert!(!cfg.privacy.show_cost);
+        cfg.set_value("presence_enabled", "off")
+            .expect("bool alias");
+        assert!(!cfg.presence_enabled);
+
+        cfg.set_value("display.recent_active_marker", "*")
+            .expect("string");
+        assert_eq!(cfg.display.recent_active_marker, "*");
+        cfg.set_value("pricing.catalog_path", "rates.json")
+            .expect("optional string");
+        assert_eq!(cfg.pricing.catalog_path.as_deref(), Some("rates.json"));
+        cfg.set_value("pricing.catalog_path", "none")
+            .expect("clear");
+        assert_eq!(cfg.pricing.catalog_path, None);
+
+        cfg.set_value("openai_plan.tier", "plus").expect("enum");
+        assert_eq!(cfg.openai_plan.tier, OpenAiPlanTier::Plus);
+        cfg.set_value("openai_plan.tier", "pro-5x")
+            .expect("enum alias");
+        assert_eq!(cfg.openai_plan.tier, OpenAiPlanTier::Pro5x);
+        cfg.set_value("pricing.display_currency", "GBP")
+            .expect("currency");
+        assert_eq!(cfg.pricing.display_currency, Currency::Gbp);
+        cfg.set_value("pricing.monthly_budget_usd", "25")
+            .expect("budget");
+        assert_eq!(cfg.pricing.monthly_budget_usd, Some(25.0));
+    }
+
+    #[test]
+    fn config_set_large_image_key_rejects_non_canonical_values() {
+        let mut cfg = PresenceConfig::default();
+        cfg.set_value("display.large_image_key", "codex-logo")
+            .expect("canonical key");
+        assert_eq!(cfg.display.large_image_key, "codex-logo");
+
+        let error = cfg
+            .set_value("display.large_image_key", "custom")
+            .expect_err("non-canonical key");
+        assert!(
+            error.to_string().contains("only accepts `codex-logo`"),
+            "{error}"
+        );
+        assert_eq!(cfg, PresenceConfig::default());
+    }
+
+    #[test]
+    fn config_set_rejects_unknown_keys_and_invalid_values() {
+        let mut cfg = PresenceConfig::default();
+        let unknown = cfg
+            .set_value("display.large_image_ky", "custom")
+            .expect_err("unknown key");
+        assert!(unknown.to_string().contains("unknown config key"));
+        assert!(cfg.set_value("privacy.show_cost", "maybe").is_err());
+        assert!(cfg.set_value("openai_plan.tier", "platinum").is_err());
+        assert!(cfg.set_value("pricing.usd_rate", "-1").is_err());
+        assert!(cfg.set_value("display.max_models_shown", "0").is_err());
+        assert!(cfg.set_value("display.large_image_key", " ").is_err());
+        assert_eq!(cfg, PresenceConfig::default());
+    }
+
     #[test]
     fn migration_sets_default_client_id_when_missing() {
         let mut cfg = PresenceConfig {
@@ -1188,6 +2701,9 @@ mod tests {
             display: DisplayConfig::default(),
             pricing: PricingConfig::default(),
             openai_plan: OpenAiPlanDisplayConfig::default(),
+            metrics: MetricsConfig::default(),
+            stealth_restore_privacy: None,
+            file_stamp: ConfigFileStamp::default(),
         };
 
         let changed = cfg.normalize_and_migrate();
@@ -1352,6 +2868,64 @@ mod tests {
         assert!(cfg.pricing.overrides.contains_key("gpt-5.2-codex"));
     }
 
+    #[test]
+    fn bar_style_selects_glyphs_and_bar_chars_override_them() {
+        let mut cfg = PresenceConfig::default();
+        assert_eq!(cfg.display.bar_style, BarStyle::Ascii);
+        assert_eq!(cfg.display.effective_bar_chars(), BarChars::ASCII);
+
+        cfg.set_value("display.bar_style", "blocks")
+            .expect("set bar style");
+        assert_eq!(cfg.display.effective_bar_chars(), BarChars::BLOCKS);
+
+        cfg.set_value("display.bar_chars", "=.")
+            .expect("set bar chars");
+        assert_eq!(
+            cfg.display.effective_bar_chars(),
+            BarChars {
+                filled: '=',
+                empty: '.'
+            }
+        );
+        assert_eq!(cfg.display.bar_style, BarStyle::Blocks);
+
+        cfg.set_value("display.bar_chars", "default")
+            .expect("reset bar chars");
+        assert_eq!(cfg.display.bar_chars, None);
+        assert!(cfg.set_value("display.bar_style", "fancy").is_err());
+        assert!(cfg.set_value("display.bar_chars", "abc").is_err());
+
+        let raw = r#"{ "display": { "bar_style": "fancy" } }"#;
+        assert!(
+            validate_config_text(raw, ConfigFormat::Json)
+                .iter()
+                .any(|finding| finding.key == "display.bar_style")
+        );
+    }
+
+    #[test]
+    fn invalid_usd_rate_is_flagged_and_normalized_back_to_usd() {
+        let raw = r#"{ "pricing": { "display_currency": "eur", "usd_rate": 0.0 } }"#;
+        assert!(
+            validate_config_text(raw, ConfigFormat::Json)
+                .iter()
+                .any(|finding| finding.key == "pricing.usd_rate")
+        );
+
+        let mut cfg = PresenceConfig::default();
+        cfg.pricing.display_currency = Currency::Eur;
+        cfg.pricing.usd_rate = -0.9;
+        assert!(cfg.normalize_and_migrate());
+        assert_eq!(cfg.pricing.usd_rate, 1.0);
+        assert_eq!(cfg.pricing.display_currency, Currency::Usd);
+
+        cfg.pricing.display_currency = Currency::Eur;
+        cfg.pricing.usd_rate = 0.9;
+        cfg.normalize_and_migrate();
+        assert_eq!(cfg.pricing.usd_rate, 0.9);
+        assert_eq!(cfg.pricing.display_currency, Currency::Eur);
+    }
+
     #[test]
     fn default_openai_plan_is_pro_20x_with_price() {
         let cfg = PresenceConfig::default();
@@ -1394,10 +2968,62 @@ mod tests {
             mode: OpenAiPlanMode::Manual,
             tier: OpenAiPlanTier::Go,
             show_price: false,
+            custom_price_usd: None,
         };
         assert_eq!(cfg.label(), "Go");
     }
 
+    #[test]
+    fn effective_config_normalizes_env_branding_and_runtime_client_ids() {
+        let config = PresenceConfig {
+            discord_client_id: Some("stale-id".to_string()),
+            ..PresenceConfig::default()
+        };
+        let effective = config.effective_with(|name| match name {
+            "CODEX_PRESENCE_LARGE_IMAGE_KEY" => Some(" env-logo ".to_string()),
+            "CODEX_PRESENCE_SMALL_TEXT" => Some("Not Codex".to_string()),
+            _ => None,
+        });
+
+        assert_eq!(effective.display.large_image_key, "codex-logo");
+        assert_eq!(effective.display.small_image_key, "openai");
+        assert_eq!(
+            effective.display.small_text,
+            DisplayConfig::default().small_text
+        );
+        assert_eq!(
+            effective.discord_client_id.as_deref(),
+            Some(DEFAULT_DISCORD_CLIENT_ID)
+        );
+        let json = serde_json::to_string_pretty(&effective).expect("json");
+        assert!(!json.contains("env-logo"));
+        assert!(!json.contains("Not Codex"));
+        assert!(json.contains(DEFAULT_DISCORD_CLIENT_ID));
+        assert!(!json.contains("stale-id"));
+        assert_eq!(config.display.large_image_key, "codex-logo");
+    }
+
+    #[test]
+    fn plan_tier_cycle_wraps_and_updates_the_label() {
+        let mut plan = OpenAiPlanDisplayConfig::default();
+        let mut labels = Vec::new();
+        for _ in 0..5 {
+            plan.tier = plan.tier.cycled();
+            labels.push(plan.label());
+        }
+        assert_eq!(
+            labels,
+            [
+                "Free ($0/month)",
+                "Go ($8/month)",
+                "Plus ($20/month)",
+                "Pro 20x ($200/month)",
+                "Free ($0/month)",
+            ]
+        );
+        assert_eq!(OpenAiPlanTier::Business.cycled(), OpenAiPlanTier::Free);
+    }
+
     #[test]
     fn plan_preset_index_tracks_auto_and_manual_modes() {
         assert_eq!(plan_preset_index(&PresenceConfig::default().openai_plan), 0);
@@ -1405,6 +3031,7 @@ mod tests {
             mode: OpenAiPlanMode::Manual,
             tier: OpenAiPlanTier::Business,
             show_price: true,
+            custom_price_usd: None,
         };
         assert_eq!(plan_preset_index(&plan), 6);
     }
@@ -1438,6 +3065,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cross_namespace_session_roots_are_wsl_shares_and_windows_drive_mounts() {
+        for root in [
+            r"\\wsl$\Ubuntu\home\dev\.codex\sessions",
+            r"\\wsl.localhost\Debian\home\dev\.codex\sessions",
+            "/mnt/c/Users/dev/.codex/sessions",
+        ] {
+            assert!(is_cross_namespace_sessions_root(Path::new(root)), "{root}");
+        }
+        for root in [
+            "/home/dev/.codex/sessions",
+            r"C:\Users\dev\.codex\sessions",
+            "/mnt/data/.codex/sessions",
+        ] {
+            assert!(!is_cross_namespace_sessions_root(Path::new(root)), "{root}");
+        }
+    }
+
+    #[test]
+    fn wsl_distro_list_parsing_strips_default_markers_and_whitespace() {
+        let text = "\u{feff}* Ubuntu-22.04 \r\nDebian (Default)\t\r\n\r\n  docker-desktop  \0\r\nUbuntu-22.04\r\n";
+
+        assert_eq!(
+            parse_wsl_distro_list(text),
+            vec!["Ubuntu-22.04", "Debian", "docker-desktop"]
+        );
+        assert!(parse_wsl_distro_list(" \r\n\0").is_empty());
+    }
+
     #[test]
     fn windows_wsl_roots_are_explicit_opt_in() {
         let source = include_str!("config.rs");
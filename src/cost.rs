@@ -1,6 +1,12 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
-use crate::config::{ModelPricingOverride, PricingConfig};
+use crate::config::{self, Currency, ModelPricingOverride, PricingConfig};
 use crate::model::{
     CatalogRates, ModelResolutionSource, SessionSpeed, SpeedMode, SpeedSource,
     resolve_context_window, resolve_model,
@@ -24,6 +30,7 @@ pub enum PricingSource {
     Exact,
     Alias,
     Override,
+    UserCatalog,
     ProviderReported,
     Unavailable,
     // Legacy wire values retained for backwards-compatible deserialization.
@@ -235,9 +242,18 @@ fn unavailable_computation() -> CostComputation {
 pub fn format_presentable_cost(
     known_total_cost_usd: Option<f64>,
     status: PricingStatus,
+) -> Option<String> {
+    format_presentable_cost_in(known_total_cost_usd, status, Currency::Usd, 1.0)
+}
+
+pub fn format_presentable_cost_in(
+    known_total_cost_usd: Option<f64>,
+    status: PricingStatus,
+    currency: Currency,
+    usd_rate: f64,
 ) -> Option<String> {
     let total = known_total_cost_usd.filter(|value| value.is_finite() && *value >= 0.0)?;
-    let formatted = crate::util::format_cost(total);
+    let formatted = crate::util::format_cost_in(total, currency, usd_rate);
     match status {
         PricingStatus::Exact => Some(formatted),
         PricingStatus::Partial => Some(format!(">={formatted}")),
@@ -275,6 +291,13 @@ pub fn resolve_model_pricing(model_id: &str, pricing_config: &PricingConfig) ->
                 resolved_model: alias_target,
             };
         }
+        if let Some(catalog_pricing) = lookup_user_catalog(&alias_target, pricing_config) {
+            return PricingResolution {
+                pricing: Some(catalog_pricing),
+                source: PricingSource::UserCatalog,
+                resolved_model: alias_target,
+            };
+        }
         if let Some(model) = resolve_model(&alias_target)
             && let Some(rates) = model.api_rates()
         {
@@ -286,6 +309,14 @@ pub fn resolve_model_pricing(model_id: &str, pricing_config: &PricingConfig) ->
         }
     }
 
+    if let Some(catalog_pricing) = lookup_user_catalog(&key, pricing_config) {
+        return PricingResolution {
+            pricing: Some(catalog_pricing),
+            source: PricingSource::UserCatalog,
+            resolved_model: key,
+        };
+    }
+
     if let Some(model) = resolve_model(&key)
         && let Some(rates) = model.api_rates()
     {
@@ -306,9 +337,69 @@ pub fn resolve_model_pricing(model_id: &str, pricing_config: &PricingConfig) ->
     }
 }
 
+type UserCatalog = BTreeMap<String, ModelPricingOverride>;
+
+#[derive(Debug, Clone)]
+struct CachedUserCatalog {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    entries: Arc<UserCatalog>,
+}
+
+fn lookup_user_catalog(model_key: &str, pricing_config: &PricingConfig) -> Option<ModelPricing> {
+    let raw_path = pricing_config
+        .catalog_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())?;
+    let catalog = load_user_catalog(&config::codex_home().join(raw_path));
+    lookup_override(model_key, &catalog)
+}
+
+fn load_user_catalog(path: &std::path::Path) -> Arc<UserCatalog> {
+    static CACHE: OnceLock<Mutex<Option<CachedUserCatalog>>> = OnceLock::new();
+    let modified = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok();
+    let cache = CACHE.get_or_init(|| Mutex::new(None));
+    let mut guard = cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(cached) = guard.as_ref()
+        && cached.path == path
+        && cached.modified == modified
+    {
+        return Arc::clone(&cached.entries);
+    }
+
+    let entries = Arc::new(read_user_catalog(path).unwrap_or_else(|err| {
+        warn!(
+            path = %path.display(),
+            error = %err,
+            "failed to load pricing catalog; using built-in pricing"
+        );
+        UserCatalog::new()
+    }));
+    *guard = Some(CachedUserCatalog {
+        path: path.to_path_buf(),
+        modified,
+        entries: Arc::clone(&entries),
+    });
+    entries
+}
+
+fn read_user_catalog(path: &std::path::Path) -> anyhow::Result<UserCatalog> {
+    let raw = std::fs::read_to_string(path)?;
+    let parsed: UserCatalog = serde_json::from_str(&raw)?;
+    Ok(parsed
+        .into_iter()
+        .map(|(model, pricing)| (normalize_model_key(&model), pricing))
+        .collect())
+}
+
 fn lookup_override(
     model_key: &str,
-    overrides: &std::collections::BTreeMap<String, ModelPricingOverride>,
+    overrides: &BTreeMap<String, ModelPricingOverride>,
 ) -> Option<ModelPricing> {
     let entry = overrides.get(model_key)?;
     if !valid_rate(entry.input_per_million) || !valid_rate(entry.output_per_million) {
@@ -471,4 +562,67 @@ mod tests {
             Some("$0.00".to_string())
         );
     }
+
+    #[test]
+    fn presentable_cost_keeps_partial_prefix_before_currency_symbol() {
+        assert_eq!(
+            format_presentable_cost_in(Some(2.0), PricingStatus::Partial, Currency::Eur, 0.9),
+            Some(">=€1.80".to_string())
+        );
+        assert_eq!(
+            format_presentable_cost_in(Some(1.0), PricingStatus::Exact, Currency::Gbp, 0.79),
+            Some("£0.790".to_string())
+        );
+    }
+
+    fn catalog_config(dir: &tempfile::TempDir, contents: &str) -> PricingConfig {
+        let path = dir.path().join("pricing-catalog.json");
+        std::fs::write(&path, contents).expect("write catalog");
+        PricingConfig {
+            catalog_path: Some(path.display().to_string()),
+            ..PricingConfig::default()
+        }
+    }
+
+    #[test]
+    fn user_catalog_is_consulted_after_overrides_and_before_builtins() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut config = catalog_config(
+            &dir,
+            r#"{"gpt-5.4":{"input_per_million":9.0,"cached_input_per_million":1.0,"output_per_million":90.0},"future-model":{"input_per_million":2.0,"output_per_million":8.0}}"#,
+        );
+
+        let catalog_hit = resolve_model_pricing("gpt-5.4", &config);
+        assert_eq!(catalog_hit.source, PricingSource::UserCatalog);
+        assert_eq!(
+            catalog_hit.pricing.map(|pricing| pricing.input_per_million),
+            Some(9.0)
+        );
+        let unknown_to_builtins = resolve_model_pricing("future-model", &config);
+        assert_eq!(unknown_to_builtins.source, PricingSource::UserCatalog);
+
+        config.overrides.insert(
+            "gpt-5.4".to_string(),
+            ModelPricingOverride {
+                input_per_million: 3.0,
+                cached_input_per_million: None,
+                output_per_million: 30.0,
+            },
+        );
+        assert_eq!(
+            resolve_model_pricing("gpt-5.4", &config).source,
+            PricingSource::Override
+        );
+    }
+
+    #[test]
+    fn malformed_user_catalog_falls_back_to_builtin_pricing() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = catalog_config(&dir, "{ not json");
+        let builtin = resolve_model_pricing("gpt-5.4", &PricingConfig::default());
+
+        let resolved = resolve_model_pricing("gpt-5.4", &config);
+        assert_eq!(resolved.source, builtin.source);
+        assert_eq!(resolved.pricing, builtin.pricing);
+    }
 }
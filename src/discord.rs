@@ -1,17 +1,22 @@
 use anyhow::{Context, Result, anyhow};
-use chrono::Utc;
-use discord_rich_presence::activity::{Activity, Assets, Timestamps};
+use chrono::{DateTime, Utc};
+use discord_rich_presence::activity::{Activity, Assets, Button, Party, Timestamps};
 use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::env;
 use std::time::{Duration, Instant, SystemTime};
+use tracing::warn;
 
 use codex_presence_core::{
-    CreditBalance, PresenceFieldId, PresenceValues, compose_presence, format_window_label,
-    select_credits_global_first,
+    CreditBalance, PresenceFieldId, PresenceLayoutConfig, PresenceValues, PresenceZone,
+    compose_presence_with_separator, format_window_label, select_credits_global_first,
 };
 
-use crate::config::{DesktopPresenceDesign, PresenceConfig, PresenceSurface};
+use crate::config::{
+    DesktopPresenceDesign, PresenceButton, PresenceConfig, PresenceSurface, PrivacyConfig,
+    TimerOrigin,
+};
 use crate::cost::format_presentable_cost;
 use crate::model::format_model_display;
 use crate::session::{
@@ -21,7 +26,7 @@ use crate::telemetry::plan::ResolvedPlan;
 use crate::telemetry::service_tier::ResolvedServiceTier;
 #[cfg(test)]
 use crate::util::format_cost;
-use crate::util::format_tokens;
+use crate::util::{expand_template, format_tokens, human_duration};
 
 pub struct DiscordPresence {
     surface: PresenceSurface,
@@ -33,12 +38,15 @@ pub struct DiscordPresence {
     last_publish_at: Option<Instant>,
     known_asset_keys: Option<HashSet<String>>,
     last_asset_refresh_at: Option<Instant>,
+    asset_keys_checked: bool,
     last_heartbeat_at: Option<Instant>,
     reconnect_backoff: Duration,
     last_reconnect_attempt: Option<Instant>,
     consecutive_errors: u32,
     idle_start_epoch: Option<i64>,
     paused: bool,
+    session_ids: Vec<String>,
+    daemon_start_epoch: i64,
     #[cfg(test)]
     clear_attempts: u32,
     #[cfg(test)]
@@ -54,9 +62,11 @@ const DISCORD_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(2);
 const RECONNECT_MIN_BACKOFF: Duration = Duration::from_secs(5);
 const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(60);
 const IDLE_STATE: &str = "Idling...";
+const IDLE_MESSAGE_ROTATION_SECS: i64 = 60;
 const PAUSED_STATUS: &str = "Paused";
+const CLEARED_STATUS: &str = "Connected (cleared)";
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct PresencePresentation {
     pub app_name: String,
     pub details: String,
@@ -71,9 +81,20 @@ pub struct PresencePresentation {
 struct PresencePayload {
     session_id: Option<String>,
     start_epoch: i64,
+    end_epoch: Option<i64>,
     activity_name: String,
     details: String,
     state: String,
+    large_text: String,
+    buttons: Vec<PresenceButton>,
+    party: Option<PresenceParty>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PresenceParty {
+    leader_session_id: String,
+    position: usize,
+    total: usize,
 }
 
 impl DiscordPresence {
@@ -90,12 +111,15 @@ impl DiscordPresence {
             last_publish_at: None,
             known_asset_keys: None,
             last_asset_refresh_at: None,
+            asset_keys_checked: false,
             last_heartbeat_at: None,
             reconnect_backoff: RECONNECT_MIN_BACKOFF,
             last_reconnect_attempt: None,
             consecutive_errors: 0,
             idle_start_epoch: None,
             paused: false,
+            session_ids: Vec::new(),
+            daemon_start_epoch: Utc::now().timestamp().max(0),
             #[cfg(test)]
             clear_attempts: 0,
             #[cfg(test)]
@@ -109,6 +133,18 @@ impl DiscordPresence {
         &self.last_status
     }
 
+    pub fn force_next_publish(&mut self) {
+        self.last_sent = None;
+    }
+
+    pub fn set_sessions(&mut self, sessions: &[CodexSessionSnapshot], privacy: &PrivacyConfig) {
+        self.session_ids = sessions
+            .iter()
+            .filter(|session| privacy.allows_project(&session.project_name))
+            .map(|session| session.session_id.clone())
+            .collect();
+    }
+
     pub fn update(
         &mut self,
         active_session: Option<&CodexSessionSnapshot>,
@@ -139,6 +175,7 @@ impl DiscordPresence {
         }
 
         self.refresh_asset_keys_if_needed();
+        self.warn_unknown_asset_keys_once(config, self.surface);
         let needs_heartbeat = self
             .last_heartbeat_at
             .map(|value| value.elapsed() >= DISCORD_HEARTBEAT_INTERVAL)
@@ -156,15 +193,33 @@ impl DiscordPresence {
                     resolved_service_tier,
                     config,
                 );
-                let start_epoch = presence_start_epoch(session);
+                let start_epoch = activity_start_epoch(
+                    config.display.timer_origin,
+                    session,
+                    self.daemon_start_epoch,
+                );
+                let end_epoch = reset_countdown_epoch(
+                    effective_selection.map_or(&session.limits, |selection| &selection.limits),
+                    config,
+                    Utc::now(),
+                );
                 let payload = PresencePayload {
                     session_id: Some(session.session_id.clone()),
                     start_epoch,
+                    end_epoch,
                     activity_name: presentation.app_name.clone(),
                     details: presentation.details.clone(),
                     state: presentation.state.clone(),
+                    large_text: large_text_with_uptime(
+                        &presentation.large_text,
+                        config,
+                        self.daemon_start_epoch,
+                        Utc::now().timestamp(),
+                    ),
+                    buttons: presence_buttons(config),
+                    party: presence_party(config, session, &self.session_ids),
                 };
-                let payload_changed = self.last_sent.as_ref() != Some(&payload);
+                let payload_changed = activity_changed(&self.last_sent, &payload);
 
                 if should_skip_publish(&self.last_sent, &payload, needs_heartbeat) {
                     self.last_status = "Connected".to_string();
@@ -197,13 +252,16 @@ impl DiscordPresence {
                     details: &presentation.details,
                     state: &presentation.state,
                     start_epoch,
+                    end_epoch,
                     large_image_key: large_image_key.as_deref(),
-                    large_text: non_empty_trimmed(&presentation.large_text),
+                    large_text: non_empty_trimmed(&payload.large_text),
                     small_image_key: small_image_key.as_deref(),
                     small_text: presentation
                         .small_text
                         .as_deref()
                         .and_then(non_empty_trimmed),
+                    buttons: &payload.buttons,
+                    party: payload.party.as_ref(),
                 });
                 let client = self
                     .client
@@ -222,17 +280,28 @@ impl DiscordPresence {
                 self.last_heartbeat_at = Some(Instant::now());
                 self.last_status = "Connected".to_string();
             }
+            None if config.display.clear_when_idle => self.clear_idle_activity()?,
             None => {
                 let idle_start = idle_start_epoch(&mut self.idle_start_epoch);
-                let presentation = idle_presence_presentation(self.surface, config);
+                let presentation =
+                    idle_presence_presentation(self.surface, config, Utc::now().timestamp());
                 let payload = PresencePayload {
                     session_id: None,
                     start_epoch: idle_start,
+                    end_epoch: None,
                     activity_name: presentation.app_name.clone(),
                     details: presentation.details.clone(),
                     state: presentation.state.clone(),
+                    large_text: large_text_with_uptime(
+                        &presentation.large_text,
+                        config,
+                        self.daemon_start_epoch,
+                        Utc::now().timestamp(),
+                    ),
+                    buttons: presence_buttons(config),
+                    party: None,
                 };
-                let payload_changed = self.last_sent.as_ref() != Some(&payload);
+                let payload_changed = activity_changed(&self.last_sent, &payload);
 
                 if should_skip_publish(&self.last_sent, &payload, needs_heartbeat) {
                     self.last_status = "Connected (idle)".to_string();
@@ -258,10 +327,13 @@ impl DiscordPresence {
                     details: &presentation.details,
                     state: &presentation.state,
                     start_epoch: idle_start,
+                    end_epoch: None,
                     large_image_key: resolved_large_key.as_deref(),
-                    large_text: non_empty_trimmed(&presentation.large_text),
+                    large_text: non_empty_trimmed(&payload.large_text),
                     small_image_key: None,
                     small_text: None,
+                    buttons: &payload.buttons,
+                    party: None,
                 });
 
                 let client = self
@@ -334,6 +406,18 @@ impl DiscordPresence {
         Ok(false)
     }
 
+    fn clear_idle_activity(&mut self) -> Result<()> {
+        if self.last_sent.is_some() || self.last_status != CLEARED_STATUS {
+            self.clear_activity()?;
+        }
+        self.last_sent = None;
+        self.last_publish_at = None;
+        self.last_heartbeat_at = None;
+        self.idle_start_epoch = None;
+        self.last_status = CLEARED_STATUS.to_string();
+        Ok(())
+    }
+
     fn clear_activity(&mut self) -> Result<()> {
         #[cfg(test)]
         {
@@ -413,6 +497,23 @@ impl DiscordPresence {
         }
     }
 
+    fn warn_unknown_asset_keys_once(&mut self, config: &PresenceConfig, surface: PresenceSurface) {
+        if self.asset_keys_checked {
+            return;
+        }
+        let Some(known) = self.known_asset_keys.as_ref() else {
+            return;
+        };
+        self.asset_keys_checked = true;
+        let unknown = unknown_configured_image_keys(config, surface, known);
+        if !unknown.is_empty() {
+            warn!(
+                keys = %unknown.join(", "),
+                "configured image keys are not uploaded to this Discord application; use the asset names from the Rich Presence Art Assets page or an https:// image URL"
+            );
+        }
+    }
+
     fn handle_ipc_error(&mut self, message: &str) {
         self.client = None;
         self.increase_backoff();
@@ -443,6 +544,7 @@ impl DiscordPresence {
         self.last_heartbeat_at = None;
         self.known_asset_keys = None;
         self.last_asset_refresh_at = None;
+        self.asset_keys_checked = false;
         self.last_reconnect_attempt = None;
         self.reconnect_backoff = RECONNECT_MIN_BACKOFF;
         self.consecutive_errors = 0;
@@ -451,10 +553,10 @@ impl DiscordPresence {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 struct SurfaceDisplay<'a> {
     activity_name: &'a str,
-    large_image_key: &'a str,
+    large_image_key: String,
     large_text: &'a str,
     idle_details: &'a str,
 }
@@ -485,22 +587,33 @@ fn display_branding<'a>(
         (PresenceSurface::Cli | PresenceSurface::VsCode, _)
         | (PresenceSurface::Desktop, DesktopPresenceDesign::ChatGptApp) => SurfaceDisplay {
             activity_name: label,
-            large_image_key: &config.display.large_image_key,
+            large_image_key: config.display.effective_large_image_key(),
             large_text: label,
             idle_details: label,
         },
         (PresenceSurface::Desktop, DesktopPresenceDesign::CodexApp) => SurfaceDisplay {
             activity_name: label,
-            large_image_key: &config.display.desktop_large_image_key,
+            large_image_key: config.display.effective_desktop_large_image_key(),
             large_text: label,
             idle_details: label,
         },
     }
 }
 
-fn idle_presence_lines(surface: PresenceSurface, config: &PresenceConfig) -> (String, String) {
+fn idle_presence_lines(
+    surface: PresenceSurface,
+    config: &PresenceConfig,
+    now_epoch: i64,
+) -> (String, String) {
     let branding = display_branding(surface, config);
-    (branding.idle_details.to_string(), IDLE_STATE.to_string())
+    let details = non_empty_trimmed(&config.display.idle_details).unwrap_or(branding.idle_details);
+    let state = non_empty_trimmed(&config.display.idle_state)
+        .or_else(|| rotating_idle_message(&config.display.idle_messages, now_epoch))
+        .unwrap_or(IDLE_STATE);
+    (
+        truncate_for_limit(details, 128),
+        truncate_for_limit(state, 128),
+    )
 }
 
 pub fn active_presence_presentation(
@@ -551,24 +664,37 @@ fn active_presence_presentation_with_credits(
         app_name: branding.activity_name.to_string(),
         details,
         state,
-        large_image_key: branding.large_image_key.to_string(),
+        large_image_key: branding.large_image_key,
         large_text: branding.large_text.to_string(),
         small_image_key,
         small_text,
     }
 }
 
+fn rotating_idle_message(messages: &[String], now_epoch: i64) -> Option<&str> {
+    let messages: Vec<&str> = messages
+        .iter()
+        .filter_map(|message| non_empty_trimmed(message))
+        .collect();
+    if messages.is_empty() {
+        return None;
+    }
+    let slot = now_epoch.max(0) / IDLE_MESSAGE_ROTATION_SECS;
+    Some(messages[(slot as usize) % messages.len()])
+}
+
 pub fn idle_presence_presentation(
     surface: PresenceSurface,
     config: &PresenceConfig,
+    now_epoch: i64,
 ) -> PresencePresentation {
     let branding = display_branding(surface, config);
-    let (details, state) = idle_presence_lines(surface, config);
+    let (details, state) = idle_presence_lines(surface, config, now_epoch);
     PresencePresentation {
         app_name: branding.activity_name.to_string(),
         details,
         state,
-        large_image_key: branding.large_image_key.to_string(),
+        large_image_key: branding.large_image_key,
         large_text: branding.large_text.to_string(),
         small_image_key: None,
         small_text: None,
@@ -597,18 +723,25 @@ struct ActivitySpec<'a> {
     details: &'a str,
     state: &'a str,
     start_epoch: i64,
+    end_epoch: Option<i64>,
     large_image_key: Option<&'a str>,
     large_text: Option<&'a str>,
     small_image_key: Option<&'a str>,
     small_text: Option<&'a str>,
+    buttons: &'a [PresenceButton],
+    party: Option<&'a PresenceParty>,
 }
 
 fn build_activity(spec: ActivitySpec<'_>) -> Activity<'_> {
+    let mut timestamps = Timestamps::new().start(spec.start_epoch);
+    if let Some(end_epoch) = spec.end_epoch {
+        timestamps = timestamps.end(end_epoch);
+    }
     let mut activity = Activity::new()
         .name(spec.name)
         .details(spec.details)
         .state(spec.state)
-        .timestamps(Timestamps::new().start(spec.start_epoch));
+        .timestamps(timestamps);
 
     let mut assets = Assets::new();
     let mut has_assets = false;
@@ -633,7 +766,56 @@ fn build_activity(spec: ActivitySpec<'_>) -> Activity<'_> {
         activity = activity.assets(assets);
     }
 
-    activity
+    if let Some(party) = spec.party {
+        activity = activity.party(
+            Party::new()
+                .id(&party.leader_session_id)
+                .size([party_size(party.position), party_size(party.total)]),
+        );
+    }
+
+    activity.buttons(
+        spec.buttons
+            .iter()
+            .map(|button| Button::new(button.label.trim(), button.url.trim()))
+            .collect(),
+    )
+}
+
+fn presence_buttons(config: &PresenceConfig) -> Vec<PresenceButton> {
+    if config.privacy.enabled {
+        return Vec::new();
+    }
+    config
+        .display
+        .buttons
+        .iter()
+        .filter(|button| button.is_valid())
+        .take(2)
+        .cloned()
+        .collect()
+}
+
+fn presence_party(
+    config: &PresenceConfig,
+    session: &CodexSessionSnapshot,
+    session_ids: &[String],
+) -> Option<PresenceParty> {
+    if config.privacy.enabled || session_ids.len() < 2 {
+        return None;
+    }
+    let position = session_ids
+        .iter()
+        .position(|id| *id == session.session_id)?;
+    Some(PresenceParty {
+        leader_session_id: session.session_id.clone(),
+        position: position + 1,
+        total: session_ids.len(),
+    })
+}
+
+fn party_size(value: usize) -> i32 {
+    i32::try_from(value).unwrap_or(i32::MAX)
 }
 
 fn should_skip_publish(
@@ -644,16 +826,79 @@ fn should_skip_publish(
     !needs_heartbeat && previous.as_ref() == Some(payload)
 }
 
+fn activity_changed(previous: &Option<PresencePayload>, payload: &PresencePayload) -> bool {
+    previous.as_ref().is_none_or(|previous| {
+        let previous = PresencePayload {
+            large_text: payload.large_text.clone(),
+            ..previous.clone()
+        };
+        previous != *payload
+    })
+}
+
 fn idle_start_epoch(idle_start_epoch: &mut Option<i64>) -> i64 {
     *idle_start_epoch.get_or_insert_with(|| Utc::now().timestamp().max(0))
 }
 
+fn large_text_with_uptime(
+    large_text: &str,
+    config: &PresenceConfig,
+    daemon_start_epoch: i64,
+    now_epoch: i64,
+) -> String {
+    let base = large_text.trim();
+    if !config.display.large_text_shows_uptime || config.privacy.enabled || base.is_empty() {
+        return large_text.to_string();
+    }
+    let uptime_secs = now_epoch.saturating_sub(daemon_start_epoch).max(0) as u64;
+    let uptime_minutes = uptime_secs / 60;
+    let uptime = if uptime_minutes < 60 {
+        format!("{uptime_minutes}m")
+    } else {
+        human_duration(Duration::from_secs(uptime_minutes * 60))
+    };
+    truncate_for_limit(&format!("{base} · up {uptime}"), 128)
+}
+
+fn activity_start_epoch(
+    origin: TimerOrigin,
+    session: &CodexSessionSnapshot,
+    daemon_start_epoch: i64,
+) -> i64 {
+    match origin {
+        TimerOrigin::Session => presence_start_epoch(session),
+        TimerOrigin::Daemon => daemon_start_epoch,
+    }
+}
+
 fn presence_start_epoch(session: &CodexSessionSnapshot) -> i64 {
     system_time_to_epoch(session.last_activity)
         .or_else(|| session.started_at.map(|value| value.timestamp().max(0)))
         .unwrap_or_else(|| Utc::now().timestamp().max(0))
 }
 
+fn reset_countdown_epoch(
+    limits: &RateLimits,
+    config: &PresenceConfig,
+    now: DateTime<Utc>,
+) -> Option<i64> {
+    if !config.display.show_reset_countdown || config.privacy.enabled || !config.privacy.show_limits
+    {
+        return None;
+    }
+    soonest_reset_epoch(limits, now)
+}
+
+fn soonest_reset_epoch(limits: &RateLimits, now: DateTime<Utc>) -> Option<i64> {
+    [&limits.primary, &limits.secondary]
+        .into_iter()
+        .flatten()
+        .filter_map(|window| window.resets_at)
+        .filter(|resets_at| *resets_at > now)
+        .min()
+        .map(|resets_at| resets_at.timestamp())
+}
+
 fn system_time_to_epoch(value: SystemTime) -> Option<i64> {
     let duration = value.duration_since(SystemTime::UNIX_EPOCH).ok()?;
     i64::try_from(duration.as_secs()).ok()
@@ -703,7 +948,7 @@ fn presence_lines(
                     resolved_service_tier.is_fast()
                 },
             ),
-            resolved_plan.label(config.openai_plan.show_price)
+            resolved_plan.display_label(&config.openai_plan)
         );
         values.insert(PresenceFieldId::Model, truncate_for_limit(&label, 68));
     }
@@ -753,8 +998,80 @@ fn presence_lines(
     } else {
         "Coding session"
     };
-    let lines = compose_presence(&layout, &values, fallback, "Codex session");
-    (lines.details, lines.state)
+    apply_state_parts_order(&mut layout, &config.display.state_parts_order);
+    let lines = compose_presence_with_separator(
+        &layout,
+        &values,
+        fallback,
+        "Codex session",
+        &config.display.state_separator,
+    );
+    let lookup = |name: &str| {
+        let field = match name {
+            "activity" => PresenceFieldId::Activity,
+            "project" => PresenceFieldId::Project,
+            "branch" => PresenceFieldId::Branch,
+            "model" => PresenceFieldId::Model,
+            "tokens" => PresenceFieldId::Tokens,
+            "cost" => PresenceFieldId::Cost,
+            "limit5h" | "limit7d" => {
+                let minutes = if name == "limit5h" { 300 } else { 10_080 };
+                return Some(
+                    limit_window_part(limits, minutes)
+                        .filter(|_| config.privacy.show_limits)
+                        .unwrap_or_default(),
+                );
+            }
+            _ => return None,
+        };
+        Some(values.0.get(&field).cloned().unwrap_or_default())
+    };
+    (
+        render_presence_template(&config.display.details_template, lookup).unwrap_or(lines.details),
+        render_presence_template(&config.display.state_template, lookup).unwrap_or(lines.state),
+    )
+}
+
+fn render_presence_template(
+    template: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    if template.trim().is_empty() {
+        return None;
+    }
+    let rendered = expand_template(template, lookup)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!rendered.is_empty()).then(|| truncate_for_limit(&rendered, 128))
+}
+
+fn limit_window_part(limits: &RateLimits, window_minutes: u64) -> Option<String> {
+    [&limits.primary, &limits.secondary]
+        .into_iter()
+        .flatten()
+        .find(|window| window.window_minutes == window_minutes)
+        .map(|window| format!("{:.0}%", window.remaining_percent))
+}
+
+fn apply_state_parts_order(layout: &mut PresenceLayoutConfig, order: &[String]) {
+    if order.is_empty() {
+        return;
+    }
+    let (mut state, mut fields): (Vec<_>, Vec<_>) = layout
+        .fields
+        .drain(..)
+        .partition(|item| item.zone == PresenceZone::State);
+    for field in order.iter().filter_map(|part| PresenceFieldId::parse(part)) {
+        if let Some(index) = state.iter().position(|item| item.field == field) {
+            fields.push(state.remove(index));
+        }
+    }
+    for mut item in state {
+        item.enabled = false;
+        fields.push(item);
+    }
+    layout.fields = fields;
 }
 
 fn token_state_part(session: &CodexSessionSnapshot) -> Option<String> {
@@ -846,8 +1163,8 @@ fn small_asset_for_activity(
     session: &CodexSessionSnapshot,
     config: &PresenceConfig,
 ) -> (String, String) {
-    let fallback_key = config.display.small_image_key.clone();
-    let fallback_text = config.display.small_text.clone();
+    let fallback_key = config.display.effective_small_image_key();
+    let fallback_text = config.display.effective_small_text();
     let Some(activity) = &session.activity else {
         return (fallback_key, fallback_text);
     };
@@ -855,9 +1172,14 @@ fn small_asset_for_activity(
     let mapped_key = match activity.kind {
         SessionActivityKind::Thinking => &config.display.activity_small_image_keys.thinking,
         SessionActivityKind::ReadingFile => &config.display.activity_small_image_keys.reading,
-        SessionActivityKind::EditingFile => &config.display.activity_small_image_keys.editing,
+        SessionActivityKind::EditingFile | SessionActivityKind::WritingTests => {
+            &config.display.activity_small_image_keys.editing
+        }
         SessionActivityKind::RunningCommand => &config.display.activity_small_image_keys.running,
-        SessionActivityKind::WaitingInput => &config.display.activity_small_image_keys.waiting,
+        SessionActivityKind::Archiving => &config.display.activity_small_image_keys.archiving,
+        SessionActivityKind::WaitingInput | SessionActivityKind::AwaitingApproval => {
+            &config.display.activity_small_image_keys.waiting
+        }
         SessionActivityKind::Idle => &config.display.activity_small_image_keys.idle,
     }
     .as_ref()
@@ -887,6 +1209,39 @@ fn resolve_image_key(
     Some(key.to_string())
 }
 
+fn unknown_configured_image_keys(
+    config: &PresenceConfig,
+    surface: PresenceSurface,
+    known_asset_keys: &HashSet<String>,
+) -> Vec<String> {
+    let display = &config.display;
+    let activity_keys = &display.activity_small_image_keys;
+    let configured = [
+        Some(display_branding(surface, config).large_image_key),
+        Some(display.effective_small_image_key()),
+        activity_keys.thinking.clone(),
+        activity_keys.reading.clone(),
+        activity_keys.editing.clone(),
+        activity_keys.running.clone(),
+        activity_keys.archiving.clone(),
+        activity_keys.waiting.clone(),
+        activity_keys.idle.clone(),
+    ];
+    let mut unknown = Vec::new();
+    for key in configured.iter().flatten() {
+        let key = key.trim();
+        if key.is_empty()
+            || looks_like_image_url(key)
+            || known_asset_keys.contains(key)
+            || unknown.iter().any(|seen| seen == key)
+        {
+            continue;
+        }
+        unknown.push(key.to_string());
+    }
+    unknown
+}
+
 fn normalize_asset_pair(
     large_image_key: Option<String>,
     small_image_key: Option<String>,
@@ -922,9 +1277,14 @@ struct DiscordAssetResponse {
 
 fn fetch_discord_asset_keys(client_id: &str) -> Result<HashSet<String>> {
     let url = format!("https://discord.com/api/v10/oauth2/applications/{client_id}/assets");
-    let agent = ureq::AgentBuilder::new()
-        .timeout(DISCORD_ASSET_FETCH_TIMEOUT)
-        .build();
+    let mut builder = ureq::AgentBuilder::new().timeout(DISCORD_ASSET_FETCH_TIMEOUT);
+    if let Some(proxy) = proxy_url_from_env("discord.com", |key| env::var(key).ok()) {
+        builder = builder.proxy(
+            ureq::Proxy::new(proxy)
+                .context("invalid HTTPS_PROXY or HTTP_PROXY value in environment")?,
+        );
+    }
+    let agent = builder.build();
     let body = agent
         .get(&url)
         .call()
@@ -939,6 +1299,25 @@ fn fetch_discord_asset_keys(client_id: &str) -> Result<HashSet<String>> {
     parse_discord_asset_keys(&body)
 }
 
+fn proxy_url_from_env(host: &str, lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let env_value = |upper: &str| {
+        lookup(upper)
+            .or_else(|| lookup(&upper.to_ascii_lowercase()))
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    if let Some(no_proxy) = env_value("NO_PROXY")
+        && no_proxy.split(',').any(|entry| {
+            let entry = entry.trim().trim_start_matches('.').to_ascii_lowercase();
+            entry == "*"
+                || (!entry.is_empty() && (host == entry || host.ends_with(&format!(".{entry}"))))
+        })
+    {
+        return None;
+    }
+    env_value("HTTPS_PROXY").or_else(|| env_value("HTTP_PROXY"))
+}
+
 fn parse_discord_asset_keys(body: &str) -> Result<HashSet<String>> {
     let parsed: Vec<DiscordAssetResponse> =
         serde_json::from_str(body).context("failed to parse Discord assets response JSON")?;
@@ -1033,9 +1412,11 @@ mod tests {
             session_total_tokens: Some(30_000),
             last_turn_tokens: Some(1_700),
             session_delta_tokens: Some(600),
+            resume_count: 0,
             input_tokens_total: 24_000,
             cached_input_tokens_total: 15_000,
             output_tokens_total: 6_000,
+            reasoning_output_tokens_total: 0,
             last_input_tokens: Some(1_500),
             last_cached_input_tokens: Some(900),
             last_output_tokens: Some(200),
@@ -1082,6 +1463,8 @@ mod tests {
             activity: None,
             last_activity: SystemTime::now(),
             source_file: PathBuf::from("session.jsonl"),
+            pid: None,
+            edit_counts: Default::default(),
         }
     }
 
@@ -1106,21 +1489,118 @@ mod tests {
         assert_eq!(presence_start_epoch(&session), 400);
     }
 
+    #[test]
+    fn unknown_image_keys_skip_urls_known_assets_and_duplicates() {
+        let known = ["codex-logo", "openai"]
+            .into_iter()
+            .map(str::to_string)
+            .collect::<HashSet<_>>();
+        let desktop_known = ["codex-app", "openai"]
+            .into_iter()
+            .map(str::to_string)
+            .collect::<HashSet<_>>();
+        let mut config = PresenceConfig::default();
+        assert!(unknown_configured_image_keys(&config, PresenceSurface::Cli, &known).is_empty());
+        assert!(
+            unknown_configured_image_keys(&config, PresenceSurface::Desktop, &desktop_known)
+                .is_empty()
+        );
+
+        config.display.activity_small_image_keys.waiting =
+            Some("https://example.com/app.png".to_string());
+        config.display.activity_small_image_keys.thinking = Some(" brain ".to_string());
+        config.display.activity_small_image_keys.idle = Some("brain".to_string());
+        config.display.activity_small_image_keys.running = Some("openai".to_string());
+        assert_eq!(
+            unknown_configured_image_keys(&config, PresenceSurface::Cli, &known),
+            vec!["brain".to_string()]
+        );
+    }
+
+    #[test]
+    fn large_text_appends_uptime_only_when_enabled_and_public() {
+        let mut config = PresenceConfig::default();
+        assert_eq!(large_text_with_uptime("Codex", &config, 0, 7_500), "Codex");
+
+        config.display.large_text_shows_uptime = true;
+        assert_eq!(
+            large_text_with_uptime("Codex", &config, 0, 7_500),
+            "Codex · up 2h 5m"
+        );
+        assert_eq!(large_text_with_uptime("", &config, 0, 7_500), "");
+
+        config.privacy.enabled = true;
+        assert_eq!(large_text_with_uptime("Codex", &config, 0, 7_500), "Codex");
+    }
+
+    #[test]
+    fn uptime_in_large_text_republishes_per_minute_without_clearing() {
+        let mut config = PresenceConfig::default();
+        config.display.large_text_shows_uptime = true;
+        assert_eq!(
+            large_text_with_uptime("Codex", &config, 0, 125),
+            "Codex · up 2m"
+        );
+        assert_eq!(
+            large_text_with_uptime("Codex", &config, 0, 179),
+            "Codex · up 2m"
+        );
+
+        let payload_at = |now_epoch| PresencePayload {
+            session_id: Some("session-1".to_string()),
+            start_epoch: 100,
+            end_epoch: None,
+            activity_name: "Codex CLI".to_string(),
+            details: "Editing src/main.rs".to_string(),
+            state: "GPT-5.3-Codex".to_string(),
+            large_text: large_text_with_uptime("Codex", &config, 0, now_epoch),
+            buttons: Vec::new(),
+            party: None,
+        };
+        let previous = Some(payload_at(125));
+        assert!(should_skip_publish(&previous, &payload_at(179), false));
+        assert!(!should_skip_publish(&previous, &payload_at(180), false));
+        assert!(!activity_changed(&previous, &payload_at(180)));
+        assert!(activity_changed(&None, &payload_at(180)));
+    }
+
+    #[test]
+    fn timer_origin_picks_session_or_daemon_start() {
+        let mut session = sample_session();
+        session.last_activity = SystemTime::UNIX_EPOCH + Duration::from_secs(400);
+        assert_eq!(
+            activity_start_epoch(TimerOrigin::Session, &session, 50),
+            400
+        );
+        assert_eq!(activity_start_epoch(TimerOrigin::Daemon, &session, 50), 50);
+
+        session.last_activity = SystemTime::UNIX_EPOCH + Duration::from_secs(900);
+        assert_eq!(activity_start_epoch(TimerOrigin::Daemon, &session, 50), 50);
+    }
+
     #[test]
     fn update_publishes_when_start_epoch_changes_even_if_text_same() {
         let old_payload = PresencePayload {
             session_id: Some("session-1".to_string()),
             start_epoch: 100,
+            end_epoch: None,
             activity_name: "Codex CLI".to_string(),
             details: "Editing src/main.rs".to_string(),
             state: "GPT-5.3-Codex".to_string(),
+            large_text: String::new(),
+            buttons: Vec::new(),
+            party: None,
         };
         let new_payload = PresencePayload {
             session_id: Some("session-1".to_string()),
             start_epoch: 120,
+            end_epoch: None,
             activity_name: "Codex CLI".to_string(),
             details: "Editing src/main.rs".to_string(),
             state: "GPT-5.3-Codex".to_string(),
+            large_text: String::new(),
+            buttons: Vec::new(),
+            party: None,
         };
         assert!(!should_skip_publish(
             &Some(old_payload),
@@ -1134,13 +1614,46 @@ mod tests {
         let payload = PresencePayload {
             session_id: Some("session-1".to_string()),
             start_epoch: 100,
+            end_epoch: None,
             activity_name: "Codex CLI".to_string(),
             details: "Editing src/main.rs".to_string(),
             state: "GPT-5.3-Codex".to_string(),
+            large_text: String::new(),
+            buttons: Vec::new(),
+            party: None,
         };
         assert!(!should_skip_publish(&Some(payload.clone()), &payload, true));
     }
 
+    #[test]
+    fn clear_when_idle_clears_once_and_resets_last_sent() {
+        let mut presence =
+            DiscordPresence::new(Some(crate::config::DEFAULT_DISCORD_CLIENT_ID.to_string()));
+        presence.last_sent = Some(PresencePayload {
+            session_id: None,
+            start_epoch: 100,
+            end_epoch: None,
+            activity_name: "Codex CLI".to_string(),
+            details: "Codex CLI".to_string(),
+            state: "Idling...".to_string(),
+            large_text: String::new(),
+            buttons: Vec::new(),
+            party: None,
+        });
+        presence.idle_start_epoch = Some(100);
+        presence.last_publish_at = Some(Instant::now());
+
+        presence.clear_idle_activity().expect("clear idle");
+        assert_eq!(presence.status(), "Connected (cleared)");
+        assert_eq!(presence.clear_attempts, 1);
+        assert!(presence.last_sent.is_none());
+        assert!(presence.last_publish_at.is_none());
+        assert!(presence.idle_start_epoch.is_none());
+
+        presence.clear_idle_activity().expect("clear idle again");
+        assert_eq!(presence.clear_attempts, 1);
+    }
+
     #[test]
     fn master_presence_pause_is_idempotent_and_resume_forces_a_fresh_publish() {
         let mut presence =
@@ -1154,9 +1667,13 @@ mod tests {
         presence.last_sent = Some(PresencePayload {
             session_id: Some("session-1".to_string()),
             start_epoch: 100,
+            end_epoch: None,
             activity_name: "ChatGPT App".to_string(),
             details: "Editing".to_string(),
             state: "GPT-5.6 Sol".to_string(),
+            large_text: String::new(),
+            buttons: Vec::new(),
+            party: None,
         });
         presence.last_publish_at = Some(Instant::now());
         presence.last_heartbeat_at = Some(Instant::now());
@@ -1243,6 +1760,76 @@ mod tests {
         assert!(state.contains("7d 18%"));
     }
 
+    #[test]
+    fn state_parts_follow_configured_order_and_separator() {
+        let session = sample_session();
+        let mut config = PresenceConfig::default();
+        config.display.state_parts_order = vec!["tokens".to_string(), "cost".to_string()];
+        config.display.state_separator = " / ".to_string();
+        let plan = resolved_plan_pro();
+        let service_tier = resolved_service_tier(false);
+        let (_details, state) = presence_lines(
+            &session,
+            Some(&session.limits),
+            None,
+            &plan,
+            &service_tier,
+            &config,
+        );
+
+        assert_eq!(
+            state,
+            format!("30.0K tok / {}", format_cost(session.total_cost_usd))
+        );
+        assert!(!state.contains("GPT-5.3 Codex"));
+        assert!(!state.contains("5h 64%"));
+        assert!(!state.contains("Ctx"));
+    }
+
+    #[test]
+    fn presence_templates_expand_placeholders_and_respect_privacy() {
+        let session = sample_session();
+        let mut config = PresenceConfig::default();
+        config.display.details_template = "{project} on {branch} {unknown}".to_string();
+        config.display.state_template = "{tokens} · 5h {limit5h} · 7d {limit7d}".to_string();
+        let plan = resolved_plan_pro();
+        let service_tier = resolved_service_tier(false);
+        let lines = |config: &PresenceConfig| {
+            presence_lines(
+                &session,
+                Some(&session.limits),
+                None,
+                &plan,
+                &service_tier,
+                config,
+            )
+        };
+
+        let (details, state) = lines(&config);
+        assert_eq!(details, "project-alpha on feature/main {unknown}");
+        assert_eq!(state, "30.0K tok · 5h 64% · 7d 18%");
+
+        config.privacy.show_git_branch = false;
+        config.privacy.show_limits = false;
+        let (details, state) = lines(&config);
+        assert_eq!(details, "project-alpha on {unknown}");
+        assert_eq!(state, "30.0K tok · 5h · 7d");
+
+        config.display.details_template = "{branch}".to_string();
+        let default_details = lines(&PresenceConfig {
+            privacy: config.privacy.clone(),
+            ..PresenceConfig::default()
+        })
+        .0;
+        assert_eq!(lines(&config).0, default_details);
+
+        config.privacy.enabled = true;
+        assert_eq!(
+            lines(&config),
+            ("Using Codex".to_string(), "In a coding session".to_string())
+        );
+    }
+
     #[test]
     fn state_keeps_priority_when_length_is_limited() {
         let mut session = sample_session();
@@ -1275,6 +1862,7 @@ mod tests {
             last_effective_signal_at: None,
             idle_candidate_at: None,
             pending_calls: 0,
+            tool: None,
         });
         let config = PresenceConfig::default();
         let plan = resolved_plan_pro();
@@ -1315,6 +1903,7 @@ mod tests {
             last_effective_signal_at: None,
             idle_candidate_at: None,
             pending_calls: 0,
+            tool: None,
         });
         let config = PresenceConfig::default();
         let plan = resolved_plan_pro();
@@ -1449,13 +2038,183 @@ mod tests {
             details: "Running command - project-alpha",
             state: "GPT-5.6 Sol · Max | Pro 20x ($200/month)",
             start_epoch: 100,
+            end_epoch: None,
             large_image_key: Some("chatgpt-logo"),
             large_text: Some("ChatGPT App"),
             small_image_key: None,
             small_text: None,
+            buttons: &[],
+            party: None,
         });
         let serialized = serde_json::to_value(activity).expect("serialize activity");
         assert_eq!(serialized["name"], "ChatGPT App");
+        assert!(serialized.get("buttons").is_none());
+    }
+
+    #[test]
+    fn buttons_are_attached_only_when_configured_valid_and_public() {
+        let button = |label: &str, url: &str| PresenceButton {
+            label: label.to_string(),
+            url: url.to_string(),
+        };
+        let mut config = PresenceConfig::default();
+        assert!(presence_buttons(&config).is_empty());
+
+        config.display.buttons = vec![
+            button("View Repo", "https://github.com/example/project"),
+            button("", "https://example.com"),
+            button("Bad URL", "javascript:alert(1)"),
+            button(
+                "A label that is far too long for Discord",
+                "https://example.com",
+            ),
+            button("Docs", "https://docs.example.com"),
+            button("Third", "https://example.com/third"),
+        ];
+        let buttons = presence_buttons(&config);
+        assert_eq!(
+            buttons
+                .iter()
+                .map(|button| button.label.as_str())
+                .collect::<Vec<_>>(),
+            ["View Repo", "Docs"]
+        );
+
+        let activity = build_activity(ActivitySpec {
+            name: "Codex CLI",
+            details: "Editing main.rs",
+            state: "GPT-5.4",
+            start_epoch: 100,
+            end_epoch: None,
+            large_image_key: None,
+            large_text: None,
+            small_image_key: None,
+            small_text: None,
+            buttons: &buttons,
+            party: None,
+        });
+        let serialized = serde_json::to_value(activity).expect("serialize activity");
+        assert_eq!(serialized["buttons"][0]["label"], "View Repo");
+        assert_eq!(
+            serialized["buttons"][0]["url"],
+            "https://github.com/example/project"
+        );
+        assert_eq!(serialized["buttons"][1]["label"], "Docs");
+
+        config.privacy.enabled = true;
+        assert!(presence_buttons(&config).is_empty());
+    }
+
+    #[test]
+    fn reset_countdown_targets_the_soonest_future_window_reset() {
+        let now = Utc.timestamp_opt(10_000, 0).single().expect("now");
+        let window = |minutes: u64, resets_at: Option<i64>| {
+            Some(UsageWindow {
+                used_percent: 50.0,
+                remaining_percent: 50.0,
+                window_minutes: minutes,
+                resets_at: resets_at.and_then(|epoch| Utc.timestamp_opt(epoch, 0).single()),
+            })
+        };
+        let limits = |primary: Option<i64>, secondary: Option<i64>| RateLimits {
+            primary: window(300, primary),
+            secondary: window(10_080, secondary),
+        };
+
+        assert_eq!(
+            soonest_reset_epoch(&limits(Some(12_000), Some(50_000)), now),
+            Some(12_000)
+        );
+        assert_eq!(
+            soonest_reset_epoch(&limits(Some(90_000), Some(20_000)), now),
+            Some(20_000)
+        );
+        assert_eq!(
+            soonest_reset_epoch(&limits(Some(9_000), Some(30_000)), now),
+            Some(30_000)
+        );
+        assert_eq!(
+            soonest_reset_epoch(&limits(None, Some(40_000)), now),
+            Some(40_000)
+        );
+        assert_eq!(soonest_reset_epoch(&limits(None, None), now), None);
+
+        let mut config = PresenceConfig::default();
+        let sample = limits(Some(12_000), Some(50_000));
+        assert_eq!(reset_countdown_epoch(&sample, &config, now), None);
+        config.display.show_reset_countdown = true;
+        assert_eq!(reset_countdown_epoch(&sample, &config, now), Some(12_000));
+        config.privacy.enabled = true;
+        assert_eq!(reset_countdown_epoch(&sample, &config, now), None);
+
+        let activity = build_activity(ActivitySpec {
+            name: "Codex CLI",
+            details: "Editing main.rs",
+            state: "GPT-5.4",
+            start_epoch: 100,
+            end_epoch: Some(12_000),
+            large_image_key: None,
+            large_text: None,
+            small_image_key: None,
+            small_text: None,
+            buttons: &[],
+            party: None,
+        });
+        let serialized = serde_json::to_value(activity).expect("serialize activity");
+        assert_eq!(serialized["timestamps"]["start"], 100);
+        assert_eq!(serialized["timestamps"]["end"], 12_000);
+    }
+
+    #[test]
+    fn party_reports_session_count_only_when_public() {
+        let mut config = PresenceConfig::default();
+        let mut session = sample_session();
+        session.session_id = "session-b".to_string();
+        let ids = ["session-a", "session-b", "session-c"].map(str::to_string);
+
+        let party = presence_party(&config, &session, &ids).expect("party");
+        assert_eq!((party.position, party.total), (2, ids.len()));
+        let activity = build_activity(ActivitySpec {
+            name: "Codex CLI",
+            details: "Editing main.rs",
+            state: "GPT-5.4",
+            start_epoch: 100,
+            end_epoch: None,
+            large_image_key: None,
+            large_text: None,
+            small_image_key: None,
+            small_text: None,
+            buttons: &[],
+            party: Some(&party),
+        });
+        let serialized = serde_json::to_value(activity).expect("serialize activity");
+        assert_eq!(serialized["party"]["id"], "session-b");
+        assert_eq!(serialized["party"]["size"], serde_json::json!([2, 3]));
+
+        assert!(presence_party(&config, &session, &ids[..1]).is_none());
+        config.privacy.enabled = true;
+        assert!(presence_party(&config, &session, &ids).is_none());
+    }
+
+    #[test]
+    fn party_size_excludes_sessions_hidden_by_project_privacy() {
+        let mut config = PresenceConfig::default();
+        config.privacy.project_denylist = vec!["secret".to_string()];
+        let session_in = |id: &str, project: &str| CodexSessionSnapshot {
+            session_id: id.to_string(),
+            project_name: project.to_string(),
+            ..sample_session()
+        };
+        let sessions = [
+            session_in("session-a", "app"),
+            session_in("session-b", "secret"),
+            session_in("session-c", "docs"),
+        ];
+        let mut presence = DiscordPresence::new(None);
+        presence.set_sessions(&sessions, &config.privacy);
+
+        let party = presence_party(&config, &sessions[2], &presence.session_ids).expect("party");
+        assert_eq!((party.position, party.total), (2, 2));
     }
 
     #[test]
@@ -1496,6 +2255,7 @@ mod tests {
             last_effective_signal_at: None,
             idle_candidate_at: None,
             pending_calls: 0,
+            tool: None,
         });
         let mut config = PresenceConfig::default();
         config.privacy.show_git_branch = false;
@@ -1523,7 +2283,7 @@ mod tests {
         let mut config = PresenceConfig::default();
         config.display.desktop_presence_design = crate::config::DesktopPresenceDesign::ChatGptApp;
 
-        let presentation = idle_presence_presentation(PresenceSurface::Desktop, &config);
+        let presentation = idle_presence_presentation(PresenceSurface::Desktop, &config, 0);
 
         assert_eq!(presentation.app_name, "ChatGPT App");
         assert_eq!(presentation.details, "ChatGPT App");
@@ -1588,6 +2348,7 @@ mod tests {
             last_effective_signal_at: None,
             idle_candidate_at: None,
             pending_calls: 0,
+            tool: None,
         });
         let mut config = PresenceConfig::default();
         config.display.activity_small_image_keys.thinking = Some("thinking-icon".to_string());
@@ -1628,6 +2389,67 @@ mod tests {
         assert!(keys.contains("openai"));
     }
 
+    #[test]
+    fn proxy_url_from_env_prefers_https_and_honors_no_proxy() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                pairs
+                    .iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert_eq!(
+            proxy_url_from_env(
+                "discord.com",
+                env(&[
+                    ("HTTPS_PROXY", "http://proxy.corp:8443"),
+                    ("HTTP_PROXY", "http://proxy.corp:8080"),
+                ])
+            )
+            .as_deref(),
+            Some("http://proxy.corp:8443")
+        );
+        assert_eq!(
+            proxy_url_from_env(
+                "discord.com",
+                env(&[("http_proxy", "http://proxy.corp:8080")])
+            )
+            .as_deref(),
+            Some("http://proxy.corp:8080")
+        );
+        assert_eq!(
+            proxy_url_from_env(
+                "discord.com",
+                env(&[
+                    ("HTTPS_PROXY", "http://proxy.corp:8443"),
+                    ("NO_PROXY", "localhost, .discord.com"),
+                ])
+            ),
+            None
+        );
+        assert_eq!(
+            proxy_url_from_env(
+                "discord.com",
+                env(&[("HTTPS_PROXY", "http://proxy.corp:8443"), ("NO_PROXY", "*")])
+            ),
+            None
+        );
+        assert_eq!(
+            proxy_url_from_env(
+                "discord.com",
+                env(&[
+                    ("HTTPS_PROXY", "http://proxy.corp:8443"),
+                    ("NO_PROXY", "notdiscord.com"),
+                ])
+            )
+            .as_deref(),
+            Some("http://proxy.corp:8443")
+        );
+        assert_eq!(proxy_url_from_env("discord.com", env(&[])), None);
+    }
+
     #[test]
     fn detect_surface_uses_desktop_originator() {
         let mut session = sample_session();
@@ -1681,12 +2503,65 @@ mod tests {
     #[test]
     fn idle_presence_lines_keep_desktop_identity_and_idling_state() {
         let config = PresenceConfig::default();
-        let (details, state) = idle_presence_lines(PresenceSurface::Desktop, &config);
+        let (details, state) = idle_presence_lines(PresenceSurface::Desktop, &config, 0);
 
         assert_eq!(details, "Codex App");
         assert_eq!(state, "Idling...");
     }
 
+    #[test]
+    fn idle_presence_lines_use_configured_text_and_fall_back_when_empty() {
+        let mut config = PresenceConfig::default();
+        config.display.idle_details = "  Between sessions ".to_string();
+        config.display.idle_state = "Back soon".to_string();
+        let presentation = idle_presence_presentation(PresenceSurface::Cli, &config, 0);
+        assert_eq!(presentation.details, "Between sessions");
+        assert_eq!(presentation.state, "Back soon");
+
+        config.display.idle_details = "   ".to_string();
+        config.display.idle_state = String::new();
+        assert_eq!(
+            idle_presence_lines(PresenceSurface::Cli, &config, 0),
+            idle_presence_lines(PresenceSurface::Cli, &PresenceConfig::default(), 0)
+        );
+        assert_eq!(
+            idle_presence_lines(PresenceSurface::Cli, &config, 0).1,
+            "Idling..."
+        );
+    }
+
+    #[test]
+    fn idle_messages_rotate_by_time_slot() {
+        let messages = vec![
+            "Idling...".to_string(),
+            " ".to_string(),
+            "Stretching".to_string(),
+            "Refilling coffee".to_string(),
+        ];
+
+        assert_eq!(rotating_idle_message(&messages, 0), Some("Idling..."));
+        assert_eq!(rotating_idle_message(&messages, 59), Some("Idling..."));
+        assert_eq!(rotating_idle_message(&messages, 60), Some("Stretching"));
+        assert_eq!(
+            rotating_idle_message(&messages, 150),
+            Some("Refilling coffee")
+        );
+        assert_eq!(rotating_idle_message(&messages, 180), Some("Idling..."));
+        assert_eq!(rotating_idle_message(&[], 60), None);
+
+        let mut config = PresenceConfig::default();
+        config.display.idle_messages = messages;
+        assert_eq!(
+            idle_presence_lines(PresenceSurface::Cli, &config, 61).1,
+            "Stretching"
+        );
+        config.display.idle_state = "Back soon".to_string();
+        assert_eq!(
+            idle_presence_lines(PresenceSurface::Cli, &config, 61).1,
+            "Back soon"
+        );
+    }
+
     #[test]
     fn desktop_missing_client_status_is_explicit() {
         let status = status_for_client_id(PresenceSurface::Desktop, None);
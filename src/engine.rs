@@ -0,0 +1,307 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::app::{self, LimitWarningTracker, RuntimeSnapshot};
+use crate::config::{self, PresenceConfig, PresenceSurface, RuntimeSettings};
+use crate::discord::DiscordPresence;
+use crate::metrics::{MetricsSnapshot, MetricsTracker};
+use crate::session::{CodexSessionSnapshot, GitBranchCache, SessionParseCache};
+use crate::telemetry::plan::PlanDetector;
+
+pub struct PresenceEngineBuilder {
+    config: PresenceConfig,
+    runtime: Option<RuntimeSettings>,
+    sessions_roots: Option<Vec<PathBuf>>,
+    client_id: Option<Option<String>>,
+    config_path: Option<Option<PathBuf>>,
+    surface: PresenceSurface,
+    persist_metrics: bool,
+}
+
+impl PresenceEngineBuilder {
+    pub fn runtime(mut self, runtime: RuntimeSettings) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    pub fn sessions_roots(mut self, sessions_roots: Vec<PathBuf>) -> Self {
+        self.sessions_roots = Some(sessions_roots);
+        self
+    }
+
+    pub fn client_id(mut self, client_id: Option<String>) -> Self {
+        self.client_id = Some(client_id);
+        self
+    }
+
+    pub fn config_path(mut self, config_path: Option<PathBuf>) -> Self {
+        self.config_path = Some(config_path);
+        self
+    }
+
+    pub fn surface(mut self, surface: PresenceSurface) -> Self {
+        self.surface = surface;
+        self
+    }
+
+    pub fn persist_metrics(mut self, enabled: bool) -> Self {
+        self.persist_metrics = enabled;
+        self
+    }
+
+    pub fn build(self) -> PresenceEngine {
+        let client_id = self
+            .client_id
+            .unwrap_or_else(|| self.config.effective_client_id());
+        let mut metrics = MetricsTracker::new();
+        metrics.set_persistence(self.persist_metrics);
+        PresenceEngine {
+            runtime: self.runtime.unwrap_or_else(config::runtime_settings),
+            sessions_roots: self.sessions_roots.unwrap_or_else(config::sessions_paths),
+            config_path: self
+                .config_path
+                .unwrap_or_else(|| Some(config::config_path())),
+            surface: self.surface,
+            git_cache: GitBranchCache::new(Duration::from_secs(30)),
+            parse_cache: SessionParseCache::default(),
+            metrics,
+            plan_detector: PlanDetector::new(),
+            discord: DiscordPresence::new(client_id),
+            limit_warnings: LimitWarningTracker::default(),
+            snapshot: RuntimeSnapshot::default(),
+            config: self.config,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineTick {
+    pub active_sessions: usize,
+    pub presence_session_id: Option<String>,
+    pub discord_status: String,
+}
+
+pub struct PresenceEngine {
+    config: PresenceConfig,
+    runtime: RuntimeSettings,
+    sessions_roots: Vec<PathBuf>,
+    config_path: Option<PathBuf>,
+    surface: PresenceSurface,
+    git_cache: GitBranchCache,
+    parse_cache: SessionParseCache,
+    metrics: MetricsTracker,
+    plan_detector: PlanDetector,
+    discord: DiscordPresence,
+    limit_warnings: LimitWarningTracker,
+    snapshot: RuntimeSnapshot,
+}
+
+impl PresenceEngine {
+    pub fn builder(config: PresenceConfig) -> PresenceEngineBuilder {
+        PresenceEngineBuilder {
+            config,
+            runtime: None,
+            sessions_roots: None,
+            client_id: None,
+            config_path: None,
+            surface: PresenceSurface::Cli,
+            persist_metrics: true,
+        }
+    }
+
+    pub fn tick(&mut self) -> Result<EngineTick> {
+        self.reload_config();
+        self.collect()?;
+        self.publish();
+        self.publish_web();
+        Ok(self.summary())
+    }
+
+    pub fn reload_config(&mut self) -> bool {
+        match &self.config_path {
+            Some(path) => self.config.reload_from_path(path),
+            None => false,
+        }
+    }
+
+    pub fn collect(&mut self) -> Result<()> {
+        self.snapshot = app::collect_runtime_snapshot(
+            &self.sessions_roots,
+            &self.runtime,
+            &self.config,
+            &mut self.git_cache,
+            &mut self.parse_cache,
+            &mut self.metrics,
+            &mut self.plan_detector,
+        )?;
+        self.limit_warnings
+            .warn_crossings(&self.snapshot, self.runtime.limit_warn_percent);
+        Ok(())
+    }
+
+    pub fn publish(&mut self) {
+        app::publish_runtime_snapshot(
+            &mut self.discord,
+            &self.snapshot,
+            &self.config,
+            self.surface,
+        );
+    }
+
+    pub fn force_publish(&mut self) {
+        self.discord.force_next_publish();
+        self.publish();
+    }
+
+    pub fn publish_session(&mut self, session: &CodexSessionSnapshot, surface: PresenceSurface) {
+        app::publish_session(
+            &mut self.discord,
+            &self.snapshot,
+            session,
+            &self.config,
+            surface,
+        );
+    }
+
+    pub fn publish_web(&self) {
+        app::publish_web_state(
+            &self.snapshot,
+            self.metrics.snapshot(),
+            &self.config,
+            self.surface,
+        );
+    }
+
+    pub(crate) fn inactive_sessions_within(&self, window: Duration) -> Vec<CodexSessionSnapshot> {
+        app::inactive_sessions_within(
+            &self.config,
+            &self.parse_cache,
+            &self.snapshot.sessions,
+            window,
+        )
+    }
+
+    pub fn summary(&self) -> EngineTick {
+        EngineTick {
+            active_sessions: self.snapshot.sessions.len(),
+            presence_session_id: self
+                .snapshot
+                .presence_session(&self.config.privacy)
+                .map(|session| session.session_id.clone()),
+            discord_status: self.discord.status().to_string(),
+        }
+    }
+
+    pub fn sessions(&self) -> &[CodexSessionSnapshot] {
+        &self.snapshot.sessions
+    }
+
+    pub fn metrics(&self) -> Option<&MetricsSnapshot> {
+        self.metrics.snapshot()
+    }
+
+    pub fn config(&self) -> &PresenceConfig {
+        &self.config
+    }
+
+    pub fn config_mut(&mut self) -> &mut PresenceConfig {
+        &mut self.config
+    }
+
+    pub(crate) fn snapshot(&self) -> &RuntimeSnapshot {
+        &self.snapshot
+    }
+
+    pub(crate) fn snapshot_mut(&mut self) -> &mut RuntimeSnapshot {
+        &mut self.snapshot
+    }
+
+    pub(crate) fn config_and_snapshot_mut(&mut self) -> (&mut PresenceConfig, &RuntimeSnapshot) {
+        (&mut self.config, &self.snapshot)
+    }
+
+    pub fn discord_status(&self) -> &str {
+        self.discord.status()
+    }
+
+    pub fn runtime(&self) -> &RuntimeSettings {
+        &self.runtime
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        self.runtime.poll_interval
+    }
+
+    pub fn shutdown(&mut self) {
+        self.discord.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Utc;
+
+    #[test]
+    fn engine_ticks_pick_up_sessions_from_the_configured_roots() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let mut engine = PresenceEngine::builder(PresenceConfig::default())
+            .runtime(config::runtime_settings())
+            .sessions_roots(vec![root.path().to_path_buf()])
+            .config_path(None)
+            .client_id(None)
+            .persist_metrics(false)
+            .build();
+
+        let idle = engine.tick().expect("idle tick");
+        assert_eq!(idle.active_sessions, 0);
+        assert_eq!(idle.presence_session_id, None);
+
+        let cwd = root.path().join("embedded-project");
+        app::write_simulated_session(root.path(), &cwd, Utc::now()).expect("write session");
+        let first = engine.tick().expect("first tick");
+        let second = engine.tick().expect("second tick");
+
+        assert_eq!(first.active_sessions, 1);
+        assert_eq!(first, second);
+        let session_id = first.presence_session_id.expect("presence session");
+        assert!(session_id.starts_with("simulated-"), "{session_id}");
+        assert_eq!(engine.sessions()[0].project_name, "embedded-project");
+        assert_eq!(
+            engine.metrics().map(|metrics| metrics.active_sessions),
+            Some(1)
+        );
+
+        engine.shutdown();
+    }
+
+    #[test]
+    fn engine_ticks_hot_reload_the_configured_config_file() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let config_path = root.path().join("discord-presence-config.json");
+        let mut engine = PresenceEngine::builder(PresenceConfig::default())
+            .sessions_roots(vec![root.path().join("sessions")])
+            .config_path(Some(config_path.clone()))
+            .client_id(None)
+            .persist_metrics(false)
+            .build();
+        assert!(engine.config().presence_enabled);
+
+        let paused = PresenceConfig {
+            presence_enabled: false,
+            ..PresenceConfig::default()
+        };
+        std::fs::write(
+            &config_path,
+            serde_json::to_string_pretty(&paused).expect("config json"),
+        )
+        .expect("write config");
+        engine.tick().expect("tick");
+
+        assert!(!engine.config().presence_enabled);
+        engine.shutdown();
+    }
+}
@@ -3,6 +3,7 @@ pub mod cli;
 pub mod config;
 pub mod cost;
 pub mod discord;
+pub mod engine;
 pub mod metrics;
 pub mod model;
 pub mod opencode;
@@ -11,3 +12,4 @@ pub mod session;
 pub mod telemetry;
 pub mod ui;
 pub mod util;
+pub mod web;
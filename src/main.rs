@@ -4,10 +4,12 @@ use anyhow::Result;
 use clap::Parser;
 
 use codex_discord_presence::app::{self, AppMode};
-use codex_discord_presence::cli::{Cli, Commands};
+use codex_discord_presence::cli::{Cli, Commands, ConfigAction};
 use codex_discord_presence::config::{self, PresenceConfig};
+use codex_discord_presence::cost::TokenUsage;
 use codex_discord_presence::process_guard;
-use codex_discord_presence::util::setup_tracing;
+use codex_discord_presence::util::{log_level_directive, setup_tracing};
+use codex_discord_presence::web;
 
 fn main() -> ExitCode {
     match run() {
@@ -20,34 +22,101 @@ fn main() -> ExitCode {
 }
 
 fn run() -> Result<u8> {
-    setup_tracing();
     let cli = Cli::parse();
-    let config = PresenceConfig::load_or_init()?;
+    setup_tracing(cli.log_format, log_level_directive(cli.quiet, cli.verbose));
+    config::set_active_profile(cli.profile.as_deref())?;
+    app::set_no_color_flag(cli.no_color);
+    let web_port = cli.web;
 
     match cli.command {
-        Some(Commands::Status) => {
-            app::print_status(&config)?;
+        Some(Commands::Status {
+            debug_cache,
+            json,
+            roots,
+        }) => {
+            let config = PresenceConfig::load_or_init()?;
+            app::print_status(&config, debug_cache, json, roots, cli.stale_secs)?;
+            Ok(0)
+        }
+        Some(Commands::Doctor) => app::doctor(&PresenceConfig::load_or_init()?, cli.stale_secs),
+        Some(Commands::Pricing {
+            model,
+            input,
+            cached,
+            output,
+            json,
+        }) => {
+            app::print_pricing(
+                &PresenceConfig::load_or_init()?,
+                &model,
+                TokenUsage {
+                    input_tokens: input,
+                    cached_input_tokens: cached,
+                    cache_write_tokens: None,
+                    output_tokens: output,
+                },
+                json,
+            )?;
+            Ok(0)
+        }
+        Some(Commands::Config {
+            action: ConfigAction::Set { key, value },
+        }) => {
+            PresenceConfig::load_or_init()?.set_and_save(&key, &value)?;
+            println!("{key} updated in {}", config::config_path().display());
+            Ok(0)
+        }
+        Some(Commands::Config {
+            action: ConfigAction::Validate,
+        }) => app::validate_config(),
+        Some(Commands::Config {
+            action: ConfigAction::Show { effective },
+        }) => {
+            app::print_config(&PresenceConfig::load_or_init()?, effective)?;
+            Ok(0)
+        }
+        Some(Commands::Simulate { hold }) => {
+            app::simulate_session(&PresenceConfig::load_or_init()?, hold)
+        }
+        Some(Commands::Watch { interval }) => {
+            let runtime = config::runtime_settings().with_overrides(cli.poll_secs, cli.stale_secs);
+            app::watch_status(PresenceConfig::load_or_init()?, interval, runtime)?;
             Ok(0)
         }
-        Some(Commands::Doctor) => app::doctor(&config),
         Some(Commands::Codex { args }) => {
+            let config = PresenceConfig::load_or_init()?;
             let acquired = process_guard::acquire_or_takeover_single_instance()?;
             if let Some(pid) = acquired.takeover_pid {
                 println!("Existing instance detected (PID {pid}); takeover completed.");
+                app::notify_takeover(&config, pid);
             }
             let _guard = acquired.guard;
-            let runtime = config::runtime_settings();
+            if let Some(port) = web_port {
+                web::start(cli.web_bind, port)?;
+            }
+            let runtime = config::runtime_settings().with_overrides(cli.poll_secs, cli.stale_secs);
             app::run(config, AppMode::CodexChild { args }, runtime)?;
             Ok(0)
         }
         None => {
+            let config = PresenceConfig::load_or_init()?;
             let acquired = process_guard::acquire_or_takeover_single_instance()?;
             if let Some(pid) = acquired.takeover_pid {
                 println!("Existing instance detected (PID {pid}); takeover completed.");
+                app::notify_takeover(&config, pid);
             }
             let _guard = acquired.guard;
-            let runtime = config::runtime_settings();
-            app::run(config, AppMode::SmartForeground, runtime)?;
+            if let Some(port) = web_port {
+                web::start(cli.web_bind, port)?;
+            }
+            let runtime = config::runtime_settings().with_overrides(cli.poll_secs, cli.stale_secs);
+            app::run(
+                config,
+                AppMode::SmartForeground {
+                    log_lines: cli.log_lines,
+                },
+                runtime,
+            )?;
             Ok(0)
         }
     }
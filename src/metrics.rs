@@ -1,18 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::{Duration, Instant};
 
 use chrono::{DateTime, Local, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
-use crate::config;
-use crate::cost::{PricingSource, PricingStatus, format_presentable_cost};
-use crate::session::CodexSessionSnapshot;
+use crate::config::{self, Currency, MetricsWindow};
+use crate::cost::{
+    PricingSource, PricingStatus, format_presentable_cost, format_presentable_cost_in,
+};
+use crate::session::{CodexSessionSnapshot, language_for_file};
 use crate::util::{
     format_cost, format_tokens, human_duration, write_json_pretty_atomic, write_text_atomic,
 };
 
 const PERSIST_INTERVAL: Duration = Duration::from_secs(10);
+const DAILY_ROLLUP_RETENTION_DAYS: usize = 30;
+const RECENT_DAYS_SHOWN: usize = 7;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricsSnapshot {
@@ -23,6 +27,70 @@ pub struct MetricsSnapshot {
     pub cost_breakdown: CostBreakdown,
     pub by_model: Vec<ModelMetrics>,
     pub active_sessions: usize,
+    #[serde(default)]
+    pub budget_status: Option<BudgetStatus>,
+    #[serde(default)]
+    pub by_language: Vec<LanguageMetrics>,
+    #[serde(default)]
+    pub recent_days: Vec<DailyUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LanguageMetrics {
+    pub language: String,
+    pub edits: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailyUsage {
+    pub date: String,
+    pub cost_usd: f64,
+    pub total_tokens: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+struct DailyRollup {
+    days: Vec<DailyUsage>,
+    sessions: BTreeMap<String, SessionUsageMark>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct SessionUsageMark {
+    date: String,
+    cost_usd: f64,
+    total_tokens: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SessionUsage<'a> {
+    session_id: &'a str,
+    started_on: Option<String>,
+    cost_usd: f64,
+    total_tokens: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetStatus {
+    Approaching,
+    Exceeded,
+}
+
+impl BudgetStatus {
+    pub fn from_spend(cost_usd: f64, budget_usd: Option<f64>) -> Option<Self> {
+        let budget = budget_usd.filter(|value| value.is_finite() && *value > 0.0)?;
+        if !cost_usd.is_finite() {
+            return None;
+        }
+        if cost_usd >= budget {
+            Some(Self::Exceeded)
+        } else if cost_usd >= budget * 0.8 {
+            Some(Self::Approaching)
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -38,6 +106,8 @@ pub struct TokenTotals {
     pub input_tokens: u64,
     pub cached_input_tokens: u64,
     pub output_tokens: u64,
+    #[serde(default)]
+    pub reasoning_output_tokens: u64,
     pub total_tokens: u64,
     pub cache_hit_ratio: f64,
 }
@@ -73,6 +143,7 @@ pub struct PricingSourceCounts {
     pub catalog_exact: u32,
     pub catalog_alias: u32,
     pub user_override: u32,
+    pub user_catalog: u32,
     pub provider_reported: u32,
     pub unavailable: u32,
     pub legacy: u32,
@@ -84,6 +155,7 @@ impl PricingSourceCounts {
             PricingSource::Exact => &mut self.catalog_exact,
             PricingSource::Alias => &mut self.catalog_alias,
             PricingSource::Override => &mut self.user_override,
+            PricingSource::UserCatalog => &mut self.user_catalog,
             PricingSource::ProviderReported => &mut self.provider_reported,
             PricingSource::Unavailable => &mut self.unavailable,
             PricingSource::Partial | PricingSource::Fallback => &mut self.legacy,
@@ -102,11 +174,15 @@ struct SessionRecord {
     input_tokens: u64,
     cached_input_tokens: u64,
     output_tokens: u64,
+    reasoning_output_tokens: u64,
     input_cost_usd: f64,
     cache_write_cost_usd: f64,
     cached_input_cost_usd: f64,
     output_cost_usd: f64,
     cached_input_savings_usd: f64,
+    started_at: Option<DateTime<Utc>>,
+    last_seen_at: DateTime<Utc>,
+    edits_by_language: BTreeMap<&'static str, u32>,
 }
 
 pub struct MetricsTracker {
@@ -115,6 +191,10 @@ pub struct MetricsTracker {
     sessions: HashMap<String, SessionRecord>,
     last_persist_at: Option<Instant>,
     cached_snapshot: Option<MetricsSnapshot>,
+    monthly_budget_usd: Option<f64>,
+    window: MetricsWindow,
+    daily: Option<DailyRollup>,
+    persist: bool,
 }
 
 impl MetricsTracker {
@@ -125,11 +205,34 @@ impl MetricsTracker {
             sessions: HashMap::new(),
             last_persist_at: None,
             cached_snapshot: None,
+            monthly_budget_usd: None,
+            window: MetricsWindow::Lifetime,
+            daily: None,
+            persist: true,
         }
     }
 
+    pub fn set_monthly_budget_usd(&mut self, budget_usd: Option<f64>) {
+        self.monthly_budget_usd = budget_usd;
+    }
+
+    pub fn set_window(&mut self, window: MetricsWindow) {
+        self.window = window;
+    }
+
+    pub fn set_persistence(&mut self, enabled: bool) {
+        self.persist = enabled;
+    }
+
     pub fn update(&mut self, sessions: &[CodexSessionSnapshot]) {
         for session in sessions {
+            let mut edits_by_language: BTreeMap<&'static str, u32> = BTreeMap::new();
+            for (target, edits) in &session.edit_counts {
+                let counter = edits_by_language
+                    .entry(language_for_file(target))
+                    .or_default();
+                *counter = counter.saturating_add(*edits);
+            }
             let record = SessionRecord {
                 model_id: session
                     .model
@@ -142,11 +245,15 @@ impl MetricsTracker {
                 input_tokens: session.input_tokens_total,
                 cached_input_tokens: session.cached_input_tokens_total,
                 output_tokens: session.output_tokens_total,
+                reasoning_output_tokens: session.reasoning_output_tokens_total,
                 input_cost_usd: session.cost_breakdown.input_cost_usd,
                 cache_write_cost_usd: session.cost_breakdown.cache_write_cost_usd,
                 cached_input_cost_usd: session.cost_breakdown.cached_input_cost_usd,
                 output_cost_usd: session.cost_breakdown.output_cost_usd,
                 cached_input_savings_usd: session.cost_breakdown.cached_input_savings_usd,
+                started_at: session.started_at,
+                last_seen_at: DateTime::<Utc>::from(session.last_activity),
+                edits_by_language,
             };
             self.sessions.insert(session.session_id.clone(), record);
         }
@@ -157,27 +264,51 @@ impl MetricsTracker {
         self.cached_snapshot.as_ref()
     }
 
-    pub fn persist_if_due(&mut self) {
+    pub fn persist_if_due(&mut self, max_models_shown: usize) {
+        if !self.persist {
+            return;
+        }
         if let Some(last) = self.last_persist_at
             && last.elapsed() < PERSIST_INTERVAL
         {
             return;
         }
-        let Some(snapshot) = self.cached_snapshot.as_ref() else {
+        let Some(snapshot) = self.cached_snapshot.as_mut() else {
             return;
         };
 
         self.last_persist_at = Some(Instant::now());
+        let usage: Vec<SessionUsage<'_>> = self
+            .sessions
+            .iter()
+            .map(|(session_id, record)| SessionUsage {
+                session_id,
+                started_on: record.started_at.map(local_date),
+                cost_usd: valid_known_cost(record.known_cost_usd).unwrap_or(0.0),
+                total_tokens: record.input_tokens.saturating_add(record.output_tokens),
+            })
+            .collect();
+        let daily = self.daily.get_or_insert_with(load_daily_rollup);
+        record_daily_usage(daily, &local_date(Utc::now()), &usage);
+        snapshot.recent_days = recent_days(&daily.days);
+        persist_daily_rollup(daily);
         persist_json(snapshot);
-        persist_markdown(snapshot);
+        persist_markdown(snapshot, max_models_shown);
+        persist_csv(snapshot);
     }
 
     fn compute_snapshot(&self, active_sessions: usize) -> MetricsSnapshot {
         let mut totals = TokenTotals::default();
         let mut cost_breakdown = CostBreakdown::default();
         let mut by_model_map: HashMap<String, ModelMetrics> = HashMap::new();
+        let mut by_language_map: BTreeMap<&'static str, u32> = BTreeMap::new();
+        let now = Utc::now();
 
-        for record in self.sessions.values() {
+        for record in self
+            .sessions
+            .values()
+            .filter(|record| self.window_includes(record.last_seen_at, now))
+        {
             let known_cost = valid_known_cost(record.known_cost_usd);
             let pricing_status = effective_pricing_status(known_cost, record.pricing_status);
             if let Some(cost) = known_cost {
@@ -195,6 +326,9 @@ impl MetricsTracker {
                 .cached_input_tokens
                 .saturating_add(record.cached_input_tokens.min(record.input_tokens));
             totals.output_tokens = totals.output_tokens.saturating_add(record.output_tokens);
+            totals.reasoning_output_tokens = totals
+                .reasoning_output_tokens
+                .saturating_add(record.reasoning_output_tokens);
 
             if record.breakdown_reconciled {
                 cost_breakdown.input_cost_usd =
@@ -253,6 +387,11 @@ impl MetricsTracker {
                 .saturating_add(record.cached_input_tokens.min(record.input_tokens));
             entry.output_tokens = entry.output_tokens.saturating_add(record.output_tokens);
             entry.session_count = entry.session_count.saturating_add(1);
+
+            for (language, edits) in &record.edits_by_language {
+                let counter = by_language_map.entry(language).or_default();
+                *counter = counter.saturating_add(*edits);
+            }
         }
 
         totals.known_cost_usd = (totals
@@ -282,6 +421,17 @@ impl MetricsTracker {
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
 
+        let budget_status = BudgetStatus::from_spend(totals.cost_usd, self.monthly_budget_usd);
+
+        let mut by_language: Vec<LanguageMetrics> = by_language_map
+            .into_iter()
+            .map(|(language, edits)| LanguageMetrics {
+                language: language.to_string(),
+                edits,
+            })
+            .collect();
+        by_language.sort_by_key(|language| std::cmp::Reverse(language.edits));
+
         MetricsSnapshot {
             daemon_started_at: self.daemon_started_at,
             snapshot_at: Utc::now(),
@@ -290,6 +440,21 @@ impl MetricsTracker {
             cost_breakdown,
             by_model,
             active_sessions,
+            budget_status,
+            by_language,
+            recent_days: self
+                .daily
+                .as_ref()
+                .map(|daily| recent_days(&daily.days))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn window_includes(&self, last_seen_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        match self.window {
+            MetricsWindow::Lifetime => true,
+            MetricsWindow::RollingHour => last_seen_at >= now - chrono::Duration::hours(1),
+            MetricsWindow::SinceStart => last_seen_at >= self.daemon_started_at,
         }
     }
 }
@@ -355,9 +520,23 @@ fn aggregate_pricing_status(
     }
 }
 
+pub fn top_models(snapshot: &MetricsSnapshot, max_models_shown: usize) -> &[ModelMetrics] {
+    let shown = max_models_shown.max(1).min(snapshot.by_model.len());
+    &snapshot.by_model[..shown]
+}
+
 pub fn format_metrics_cost(totals: &TokenTotals) -> String {
-    format_presentable_cost(totals.known_cost_usd, totals.pricing_status)
-        .unwrap_or_else(|| "cost unavailable".to_string())
+    format_metrics_cost_in(totals, Currency::Usd, 1.0)
+}
+
+pub fn format_metrics_cost_in(totals: &TokenTotals, currency: Currency, usd_rate: f64) -> String {
+    format_presentable_cost_in(
+        totals.known_cost_usd,
+        totals.pricing_status,
+        currency,
+        usd_rate,
+    )
+    .unwrap_or_else(|| "cost unavailable".to_string())
 }
 
 impl Default for MetricsTracker {
@@ -366,22 +545,164 @@ impl Default for MetricsTracker {
     }
 }
 
+fn local_date(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local).format("%Y-%m-%d").to_string()
+}
+
+fn record_daily_usage(rollup: &mut DailyRollup, date: &str, usage: &[SessionUsage<'_>]) {
+    let mut cost_delta = 0.0;
+    let mut token_delta = 0_u64;
+    for session in usage {
+        let (cost, tokens) = match rollup.sessions.get(session.session_id) {
+            Some(mark) => (
+                (session.cost_usd - mark.cost_usd).max(0.0),
+                session.total_tokens.saturating_sub(mark.total_tokens),
+            ),
+            None if session.started_on.as_deref() == Some(date) => {
+                (session.cost_usd.max(0.0), session.total_tokens)
+            }
+            None => (0.0, 0),
+        };
+        cost_delta += cost;
+        token_delta = token_delta.saturating_add(tokens);
+        rollup.sessions.insert(
+            session.session_id.to_string(),
+            SessionUsageMark {
+                date: date.to_string(),
+                cost_usd: session.cost_usd,
+                total_tokens: session.total_tokens,
+            },
+        );
+    }
+
+    let days = &mut rollup.days;
+    match days.last_mut() {
+        Some(day) if day.date == date => {
+            day.cost_usd += cost_delta;
+            day.total_tokens = day.total_tokens.saturating_add(token_delta);
+        }
+        _ => days.push(DailyUsage {
+            date: date.to_string(),
+            cost_usd: cost_delta,
+            total_tokens: token_delta,
+        }),
+    }
+    let excess = days.len().saturating_sub(DAILY_ROLLUP_RETENTION_DAYS);
+    days.drain(..excess);
+    if let Some(oldest) = days.first().map(|day| day.date.clone()) {
+        rollup.sessions.retain(|_, mark| mark.date >= oldest);
+    }
+}
+
+fn recent_days(days: &[DailyUsage]) -> Vec<DailyUsage> {
+    days.iter().rev().take(RECENT_DAYS_SHOWN).cloned().collect()
+}
+
+fn daily_rollup_path() -> std::path::PathBuf {
+    config::profile_scoped_path(
+        &config::presence_config_home(),
+        "discord-presence-metrics-daily",
+        "json",
+    )
+}
+
+fn load_daily_rollup() -> DailyRollup {
+    std::fs::read_to_string(daily_rollup_path())
+        .ok()
+        .map(|raw| parse_daily_rollup(&raw))
+        .unwrap_or_default()
+}
+
+fn parse_daily_rollup(raw: &str) -> DailyRollup {
+    serde_json::from_str::<Vec<DailyUsage>>(raw)
+        .map(|days| DailyRollup {
+            days,
+            ..DailyRollup::default()
+        })
+        .or_else(|_| serde_json::from_str(raw))
+        .unwrap_or_default()
+}
+
+fn persist_daily_rollup(rollup: &DailyRollup) {
+    if let Err(err) = write_json_pretty_atomic(&daily_rollup_path(), rollup) {
+        warn!(error = %err, "failed to persist daily metrics rollup");
+    }
+}
+
 fn persist_json(snapshot: &MetricsSnapshot) {
-    let path = config::codex_home().join("discord-presence-metrics.json");
+    let path = config::profile_scoped_path(
+        &config::presence_config_home(),
+        "discord-presence-metrics",
+        "json",
+    );
     if let Err(err) = write_json_pretty_atomic(&path, snapshot) {
         warn!(error = %err, "failed to persist metrics JSON");
     }
 }
 
-fn persist_markdown(snapshot: &MetricsSnapshot) {
-    let path = config::codex_home().join("discord-presence-metrics.md");
-    let markdown = generate_markdown(snapshot);
+fn persist_markdown(snapshot: &MetricsSnapshot, max_models_shown: usize) {
+    let path = config::profile_scoped_path(
+        &config::presence_config_home(),
+        "discord-presence-metrics",
+        "md",
+    );
+    let markdown = generate_markdown(snapshot, max_models_shown);
     if let Err(err) = write_text_atomic(&path, &markdown) {
         warn!(error = %err, "failed to persist metrics markdown");
     }
 }
 
-fn generate_markdown(snapshot: &MetricsSnapshot) -> String {
+fn persist_csv(snapshot: &MetricsSnapshot) {
+    let path = config::profile_scoped_path(
+        &config::presence_config_home(),
+        "discord-presence-metrics",
+        "csv",
+    );
+    if let Err(err) = write_text_atomic(&path, &generate_csv(snapshot)) {
+        warn!(error = %err, "failed to persist metrics CSV");
+    }
+}
+
+fn generate_csv(snapshot: &MetricsSnapshot) -> String {
+    let mut csv =
+        String::from("model_id,sessions,input_tokens,cached_input_tokens,output_tokens,cost_usd\n");
+    let mut sessions = 0u32;
+    for model in &snapshot.by_model {
+        sessions = sessions.saturating_add(model.session_count);
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            csv_field(&model.model_id),
+            model.session_count,
+            model.input_tokens,
+            model.cached_input_tokens,
+            model.output_tokens,
+            csv_cost(model.known_cost_usd)
+        ));
+    }
+    csv.push_str(&format!(
+        "total,{},{},{},{},{}\n",
+        sessions,
+        snapshot.totals.input_tokens,
+        snapshot.totals.cached_input_tokens,
+        snapshot.totals.output_tokens,
+        csv_cost(snapshot.totals.known_cost_usd)
+    ));
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_cost(cost: Option<f64>) -> String {
+    cost.map(|value| format!("{value:.6}")).unwrap_or_default()
+}
+
+fn generate_markdown(snapshot: &MetricsSnapshot, max_models_shown: usize) -> String {
     let now_local = Local::now().format("%b %d, %Y %I:%M %p");
     let uptime = human_duration(Duration::from_secs(snapshot.uptime_seconds));
 
@@ -431,6 +752,12 @@ fn generate_markdown(snapshot: &MetricsSnapshot) -> String {
         "| Output Tokens | {} |\n",
         format_tokens(snapshot.totals.output_tokens)
     ));
+    if snapshot.totals.reasoning_output_tokens > 0 {
+        markdown.push_str(&format!(
+            "| Reasoning Tokens | {} |\n",
+            format_tokens(snapshot.totals.reasoning_output_tokens)
+        ));
+    }
     markdown.push('\n');
 
     markdown.push_str("## Cost Breakdown\n\n");
@@ -462,7 +789,7 @@ fn generate_markdown(snapshot: &MetricsSnapshot) -> String {
         markdown.push_str("## By Model\n\n");
         markdown.push_str("| Model | Sessions | Cost | Tokens |\n");
         markdown.push_str("|-------|----------|------|--------|\n");
-        for model in &snapshot.by_model {
+        for model in top_models(snapshot, max_models_shown) {
             let total_tokens = model.input_tokens + model.output_tokens;
             markdown.push_str(&format!(
                 "| {} | {} | {} | {} |\n",
@@ -476,6 +803,31 @@ fn generate_markdown(snapshot: &MetricsSnapshot) -> String {
         markdown.push('\n');
     }
 
+    if !snapshot.by_language.is_empty() {
+        markdown.push_str("## By Language\n\n");
+        markdown.push_str("| Language | Edits |\n");
+        markdown.push_str("|----------|-------|\n");
+        for language in &snapshot.by_language {
+            markdown.push_str(&format!("| {} | {} |\n", language.language, language.edits));
+        }
+        markdown.push('\n');
+    }
+
+    if !snapshot.recent_days.is_empty() {
+        markdown.push_str("## Recent Days\n\n");
+        markdown.push_str("| Date | Cost | Tokens |\n");
+        markdown.push_str("|------|------|--------|\n");
+        for day in &snapshot.recent_days {
+            markdown.push_str(&format!(
+                "| {} | {} | {} |\n",
+                day.date,
+                format_cost(day.cost_usd),
+                format_tokens(day.total_tokens)
+            ));
+        }
+        markdown.push('\n');
+    }
+
     markdown.push_str(&format!(
         "*Active sessions: {}*\n",
         snapshot.active_sessions
@@ -514,9 +866,11 @@ mod tests {
             session_total_tokens: Some(input + output),
             last_turn_tokens: Some(0),
             session_delta_tokens: Some(0),
+            resume_count: 0,
             input_tokens_total: input,
             cached_input_tokens_total: cached,
             output_tokens_total: output,
+            reasoning_output_tokens_total: 0,
             last_input_tokens: Some(0),
             last_cached_input_tokens: Some(0),
             last_output_tokens: Some(0),
@@ -542,6 +896,8 @@ mod tests {
             last_token_event_at: None,
             last_activity: SystemTime::now(),
             source_file: PathBuf::from("/test.jsonl"),
+            pid: None,
+            edit_counts: BTreeMap::new(),
         }
     }
 
@@ -565,6 +921,70 @@ mod tests {
         assert!((snapshot.totals.cost_usd - 0.08).abs() < 0.0001);
     }
 
+    #[test]
+    fn edits_are_read_from_session_counts_and_bucketed_by_language() {
+        let editing = |id: &str, edits: &[(&str, u32)]| {
+            let mut session = make_session(id, "gpt-5.5", 100, 0, 10, 0.01);
+            session.edit_counts = edits
+                .iter()
+                .map(|(target, count)| (target.to_string(), *count))
+                .collect();
+            session
+        };
+        let mut tracker = MetricsTracker::new();
+
+        tracker.update(&[
+            editing("s1", &[("main.rs", 1)]),
+            editing("s2", &[("app.py", 1)]),
+        ]);
+        tracker.update(&[
+            editing("s1", &[("main.rs", 1)]),
+            editing("s2", &[("app.py", 1)]),
+        ]);
+        tracker.update(&[
+            editing("s1", &[("main.rs", 1), ("lib.rs", 1)]),
+            editing("s2", &[("app.py", 1), ("Makefile", 1)]),
+        ]);
+
+        let snapshot = tracker.snapshot().expect("snapshot");
+        assert_eq!(
+            snapshot.by_language,
+            vec![
+                LanguageMetrics {
+                    language: "Rust".to_string(),
+                    edits: 2,
+                },
+                LanguageMetrics {
+                    language: "Other".to_string(),
+                    edits: 1,
+                },
+                LanguageMetrics {
+                    language: "Python".to_string(),
+                    edits: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rolling_hour_window_excludes_records_not_seen_within_the_hour() {
+        let mut tracker = MetricsTracker::new();
+        tracker.set_window(MetricsWindow::RollingHour);
+        let fresh = make_session("fresh", "gpt-5.5", 1_000, 0, 100, 0.02);
+        let mut stale = make_session("stale", "gpt-5.5", 4_000, 0, 400, 0.08);
+        stale.last_activity = SystemTime::now() - Duration::from_secs(2 * 60 * 60);
+        tracker.update(&[fresh.clone(), stale.clone()]);
+
+        let snapshot = tracker.snapshot().expect("snapshot");
+        assert_eq!(snapshot.totals.input_tokens, 1_000);
+        assert!((snapshot.totals.cost_usd - 0.02).abs() < 0.0001);
+
+        tracker.set_window(MetricsWindow::Lifetime);
+        tracker.update(&[fresh, stale]);
+        let snapshot = tracker.snapshot().expect("snapshot");
+        assert_eq!(snapshot.totals.input_tokens, 5_000);
+    }
+
     #[test]
     fn aggregates_multiple_models() {
         let mut tracker = MetricsTracker::new();
@@ -583,6 +1003,29 @@ mod tests {
         assert!((snapshot.totals.cache_hit_ratio - (500.0 / 3_000.0)).abs() < 0.0001);
     }
 
+    #[test]
+    fn csv_export_has_header_one_row_per_model_and_totals() {
+        let mut tracker = MetricsTracker::new();
+        tracker.update(&[
+            make_session("s1", "gpt-5.2-codex", 1_000, 200, 500, 0.04),
+            make_session("s2", "gpt-5.1-codex", 2_000, 300, 700, 0.05),
+            make_session("s3", "gpt-5.1-codex", 500, 0, 100, 0.01),
+        ]);
+        let snapshot = tracker.snapshot().expect("snapshot");
+
+        let csv = generate_csv(snapshot);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "model_id,sessions,input_tokens,cached_input_tokens,output_tokens,cost_usd"
+        );
+        assert_eq!(lines.len(), 1 + snapshot.by_model.len() + 1);
+        assert!(lines.contains(&"gpt-5.2-codex,1,1000,200,500,0.040000"));
+        assert!(lines.contains(&"gpt-5.1-codex,2,2500,300,800,0.060000"));
+        assert_eq!(lines[3], "total,3,3500,500,1300,0.100000");
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
     #[test]
     fn aggregate_is_partial_when_any_session_is_incomplete() {
         let mut tracker = MetricsTracker::new();
@@ -636,6 +1079,58 @@ mod tests {
         assert!((snapshot.by_model[0].cache_hit_ratio - 0.25).abs() < 0.0001);
     }
 
+    #[test]
+    fn top_models_are_capped_and_ordered_by_cost() {
+        let mut tracker = MetricsTracker::new();
+        tracker.update(&[
+            make_session("s1", "gpt-5.2-codex", 1_000, 0, 100, 0.02),
+            make_session("s2", "gpt-5.4", 1_000, 0, 100, 0.30),
+            make_session("s3", "gpt-5.5", 1_000, 0, 100, 0.10),
+        ]);
+        let snapshot = tracker.snapshot().expect("snapshot");
+
+        let shown: Vec<&str> = top_models(snapshot, 2)
+            .iter()
+            .map(|model| model.model_id.as_str())
+            .collect();
+        assert_eq!(shown, vec!["gpt-5.4", "gpt-5.5"]);
+        assert_eq!(top_models(snapshot, 10).len(), 3);
+        assert_eq!(top_models(snapshot, 0).len(), 1);
+
+        let markdown = generate_markdown(snapshot, 1);
+        assert!(markdown.contains("| gpt-5.4 |"));
+        assert!(!markdown.contains("| gpt-5.5 |"));
+    }
+
+    #[test]
+    fn budget_status_transitions_at_eighty_and_hundred_percent() {
+        let mut tracker = MetricsTracker::new();
+        tracker.set_monthly_budget_usd(Some(1.0));
+        tracker.update(&[make_session("s1", "gpt-5.4", 1_000, 0, 100, 0.5)]);
+        assert_eq!(tracker.snapshot().expect("snapshot").budget_status, None);
+
+        tracker.update(&[make_session("s1", "gpt-5.4", 1_000, 0, 100, 0.8)]);
+        assert_eq!(
+            tracker.snapshot().expect("snapshot").budget_status,
+            Some(BudgetStatus::Approaching)
+        );
+
+        tracker.update(&[make_session("s1", "gpt-5.4", 1_000, 0, 100, 1.25)]);
+        assert_eq!(
+            tracker.snapshot().expect("snapshot").budget_status,
+            Some(BudgetStatus::Exceeded)
+        );
+    }
+
+    #[test]
+    fn budget_status_is_absent_without_budget() {
+        let mut tracker = MetricsTracker::new();
+        tracker.update(&[make_session("s1", "gpt-5.4", 1_000, 0, 100, 50.0)]);
+        assert_eq!(tracker.snapshot().expect("snapshot").budget_status, None);
+        assert_eq!(BudgetStatus::from_spend(5.0, Some(0.0)), None);
+        assert_eq!(BudgetStatus::from_spend(5.0, Some(f64::NAN)), None);
+    }
+
     #[test]
     fn markdown_contains_expected_sections() {
         let snapshot = MetricsSnapshot {
@@ -656,6 +1151,7 @@ mod tests {
                 input_tokens: 100_000,
                 cached_input_tokens: 60_000,
                 output_tokens: 40_000,
+                reasoning_output_tokens: 0,
                 total_tokens: 140_000,
                 cache_hit_ratio: 0.6,
             },
@@ -685,14 +1181,177 @@ mod tests {
                 session_count: 1,
             }],
             active_sessions: 1,
+            budget_status: None,
+            by_language: vec![LanguageMetrics {
+                language: "Rust".to_string(),
+                edits: 3,
+            }],
+            recent_days: Vec::new(),
         };
 
-        let markdown = generate_markdown(&snapshot);
+        let markdown = generate_markdown(&snapshot, 5);
         assert!(markdown.contains("# Codex Metrics Report"));
         assert!(markdown.contains("## Totals"));
         assert!(markdown.contains("## Cost Breakdown"));
         assert!(markdown.contains("## By Model"));
+        assert!(markdown.contains("## By Language"));
+        assert!(markdown.contains("| Rust | 3 |"));
         assert!(markdown.contains("Cache Hit Ratio"));
         assert!(markdown.contains("Cached Input Savings"));
+        assert!(!markdown.contains("Reasoning Tokens"));
+        assert!(!markdown.contains("## Recent Days"));
+    }
+
+    #[test]
+    fn markdown_renders_recent_days_from_the_daily_rollup() {
+        let rollup: Vec<DailyUsage> = serde_json::from_str(
+            r#"[
+                {"date":"2026-10-14","cost_usd":1.5,"total_tokens":120000},
+                {"date":"2026-10-15","cost_usd":0.25,"total_tokens":8000}
+            ]"#,
+        )
+        .expect("rollup");
+        let mut tracker = MetricsTracker::new();
+        tracker.daily = Some(DailyRollup {
+            days: rollup,
+            ..DailyRollup::default()
+        });
+        tracker.update(&[make_session("s1", "gpt-5.4", 1_000, 0, 500, 0.5)]);
+
+        let snapshot = tracker.snapshot().expect("snapshot");
+        assert_eq!(snapshot.recent_days[0].date, "2026-10-15");
+        let markdown = generate_markdown(snapshot, 5);
+        assert!(markdown.contains("## Recent Days"), "{markdown}");
+        assert!(
+            markdown.contains("| 2026-10-14 | $1.50 | 120.0K |"),
+            "{markdown}"
+        );
+        assert!(
+            markdown.contains("| 2026-10-15 | $0.250 | 8.0K |"),
+            "{markdown}"
+        );
+    }
+
+    fn usage<'a>(
+        session_id: &'a str,
+        started_on: &str,
+        cost_usd: f64,
+        total_tokens: u64,
+    ) -> SessionUsage<'a> {
+        SessionUsage {
+            session_id,
+            started_on: Some(started_on.to_string()),
+            cost_usd,
+            total_tokens,
+        }
+    }
+
+    #[test]
+    fn daily_rollup_accrues_deltas_and_rolls_over_by_date() {
+        let mut rollup = DailyRollup::default();
+
+        record_daily_usage(
+            &mut rollup,
+            "2026-10-15",
+            &[usage("s1", "2026-10-15", 2.0, 10_000)],
+        );
+        record_daily_usage(
+            &mut rollup,
+            "2026-10-15",
+            &[usage("s1", "2026-10-15", 2.5, 14_000)],
+        );
+        record_daily_usage(
+            &mut rollup,
+            "2026-10-16",
+            &[usage("s1", "2026-10-15", 3.0, 20_000)],
+        );
+
+        assert_eq!(
+            rollup.days,
+            vec![
+                DailyUsage {
+                    date: "2026-10-15".to_string(),
+                    cost_usd: 2.5,
+                    total_tokens: 14_000,
+                },
+                DailyUsage {
+                    date: "2026-10-16".to_string(),
+                    cost_usd: 0.5,
+                    total_tokens: 6_000,
+                },
+            ]
+        );
+
+        for day in 0..40 {
+            record_daily_usage(&mut rollup, &format!("day-{day:02}"), &[]);
+        }
+        assert_eq!(rollup.days.len(), DAILY_ROLLUP_RETENTION_DAYS);
+        assert_eq!(recent_days(&rollup.days).len(), RECENT_DAYS_SHOWN);
+        assert_eq!(recent_days(&rollup.days)[0].date, "day-39");
+        assert!(rollup.sessions.is_empty());
+    }
+
+    #[test]
+    fn daily_rollup_counts_each_session_delta_once() {
+        let mut rollup = DailyRollup::default();
+        let today = "2026-10-16";
+
+        record_daily_usage(
+            &mut rollup,
+            today,
+            &[usage("old", "2026-10-10", 5.0, 50_000)],
+        );
+        assert_eq!(rollup.days[0].cost_usd, 0.0);
+        assert_eq!(rollup.days[0].total_tokens, 0);
+
+        record_daily_usage(
+            &mut rollup,
+            today,
+            &[
+                usage("old", "2026-10-10", 5.5, 54_000),
+                usage("new", today, 0.25, 2_000),
+            ],
+        );
+        record_daily_usage(&mut rollup, today, &[usage("new", today, 0.25, 2_000)]);
+        record_daily_usage(
+            &mut rollup,
+            today,
+            &[
+                usage("old", "2026-10-10", 5.5, 54_000),
+                usage("new", today, 0.25, 2_000),
+            ],
+        );
+
+        assert_eq!(rollup.days.len(), 1);
+        assert_eq!(rollup.days[0].cost_usd, 0.75);
+        assert_eq!(rollup.days[0].total_tokens, 6_000);
+
+        let reloaded = parse_daily_rollup(&serde_json::to_string(&rollup).expect("rollup json"));
+        assert_eq!(reloaded, rollup);
+        let legacy =
+            parse_daily_rollup(r#"[{"date":"2026-10-15","cost_usd":1.5,"total_tokens":120000}]"#);
+        assert_eq!(legacy.days.len(), 1);
+        assert!(legacy.sessions.is_empty());
+    }
+
+    #[test]
+    fn reasoning_tokens_are_totaled_and_reported_when_present() {
+        let mut tracker = MetricsTracker::new();
+        let mut first = make_session("s1", "gpt-5.4", 1_000, 0, 800, 0.5);
+        first.reasoning_output_tokens_total = 500;
+        let mut second = make_session("s2", "gpt-5.4", 1_000, 0, 400, 0.5);
+        second.reasoning_output_tokens_total = 250;
+        tracker.update(&[first, second]);
+
+        let snapshot = tracker.snapshot().expect("snapshot");
+        assert_eq!(snapshot.totals.output_tokens, 1_200);
+        assert_eq!(snapshot.totals.reasoning_output_tokens, 750);
+
+        let markdown = generate_markdown(snapshot, 5);
+        assert!(markdown.contains("| Output Tokens | 1.2K |"), "{markdown}");
+        assert!(
+            markdown.contains("| Reasoning Tokens | 750 |"),
+            "{markdown}"
+        );
     }
 }
@@ -222,9 +222,11 @@ fn row_to_snapshot(
         session_total_tokens: (token_total > 0).then_some(token_total),
         last_turn_tokens: None,
         session_delta_tokens: None,
+        resume_count: 0,
         input_tokens_total: input_total,
         cached_input_tokens_total: row.tokens_cache_read,
         output_tokens_total: output_total,
+        reasoning_output_tokens_total: row.tokens_reasoning,
         last_input_tokens: None,
         last_cached_input_tokens: None,
         last_output_tokens: None,
@@ -243,6 +245,8 @@ fn row_to_snapshot(
         last_token_event_at: millis_to_datetime(row.time_updated),
         last_activity: updated_at,
         source_file: db_path.to_path_buf(),
+        pid: None,
+        edit_counts: Default::default(),
     }))
 }
 
@@ -502,6 +506,7 @@ fn activity(
         last_effective_signal_at: observed_at,
         idle_candidate_at: None,
         pending_calls,
+        tool: None,
     }
 }
 
@@ -221,7 +221,7 @@ fn terminate_process(pid: u32, force: bool) -> Result<()> {
 }
 
 #[cfg(windows)]
-fn process_exists(pid: u32) -> bool {
+pub(crate) fn process_exists(pid: u32) -> bool {
     let output = crate::util::silent_command("tasklist")
         .arg("/FI")
         .arg(format!("PID eq {pid}"))
@@ -245,7 +245,7 @@ fn process_exists(pid: u32) -> bool {
 }
 
 #[cfg(not(windows))]
-fn process_exists(pid: u32) -> bool {
+pub(crate) fn process_exists(pid: u32) -> bool {
     Command::new("kill")
         .arg("-0")
         .arg(pid.to_string())
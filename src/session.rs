@@ -1,5 +1,5 @@
 use std::cmp::Reverse;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant, SystemTime};
 
@@ -8,11 +8,14 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 
-use crate::config::{PresenceSurface, PricingConfig};
+use crate::config::{
+    PresenceSurface, PricingConfig, PrivacyConfig, git_timeout, is_cross_namespace_sessions_root,
+};
 use crate::cost::{CostAttribution, PricingSource, PricingStatus, TokenCostBreakdown};
 pub use crate::model::{
     ContextSource as ContextWindowSource, ReasoningEffort, SessionSpeed, SpeedMode, SpeedSource,
 };
+use crate::process_guard::process_exists;
 pub use crate::telemetry::limits::{
     EffectiveLimitSelection, RateLimitEnvelope, RateLimitScope, RateLimits, UsageWindow,
 };
@@ -26,9 +29,9 @@ mod parser;
 
 use activity::SessionAccumulator;
 pub(crate) use activity::{
-    sanitize_domain_target, sanitize_file_target, summarize_command_for_presence,
+    language_for_file, sanitize_domain_target, sanitize_file_target, summarize_command_for_presence,
 };
-use parser::{fetch_git_branch, parse_session_file_cached};
+use parser::{GitBranchLookup, fetch_git_branch, parse_session_file_cached};
 #[cfg(test)]
 use parser::{parse_new_lines, parse_session_file, parse_utc_timestamp};
 
@@ -55,8 +58,11 @@ pub enum SessionActivityKind {
     Thinking,
     ReadingFile,
     EditingFile,
+    WritingTests,
     RunningCommand,
+    Archiving,
     WaitingInput,
+    AwaitingApproval,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -68,6 +74,8 @@ pub struct SessionActivitySnapshot {
     pub last_effective_signal_at: Option<DateTime<Utc>>,
     pub idle_candidate_at: Option<DateTime<Utc>>,
     pub pending_calls: usize,
+    #[serde(default)]
+    pub tool: Option<String>,
 }
 
 impl SessionActivitySnapshot {
@@ -76,8 +84,11 @@ impl SessionActivitySnapshot {
             SessionActivityKind::Thinking => "Thinking",
             SessionActivityKind::ReadingFile => "Reading",
             SessionActivityKind::EditingFile => "Editing",
+            SessionActivityKind::WritingTests => "Writing tests",
             SessionActivityKind::RunningCommand => "Running command",
+            SessionActivityKind::Archiving => "Archiving",
             SessionActivityKind::WaitingInput => "Waiting for input",
+            SessionActivityKind::AwaitingApproval => "Awaiting approval",
             SessionActivityKind::Idle => "Idle",
         }
     }
@@ -87,10 +98,32 @@ impl SessionActivitySnapshot {
             && let Some(target) = &self.target
             && !target.trim().is_empty()
         {
+            if self.tool.is_some() && self.kind == SessionActivityKind::RunningCommand {
+                return format!("Running {target}");
+            }
+            if self.kind == SessionActivityKind::AwaitingApproval {
+                return format!("Awaiting approval for {target}");
+            }
+            if self.kind == SessionActivityKind::WritingTests {
+                return format!("Writing tests in {target}");
+            }
             return format!("{} {}", self.action_text(), target);
         }
         self.action_text().to_string()
     }
+
+    pub fn apply_tool_activity_map(&mut self, map: &BTreeMap<String, SessionActivityKind>) {
+        let Some(tool) = self.tool.as_deref() else {
+            return;
+        };
+        let short = activity::mcp_tool_parts(tool).map(|(_, short)| short);
+        if let Some(kind) = map
+            .get(tool)
+            .or_else(|| short.and_then(|short| map.get(short)))
+        {
+            self.kind = kind.clone();
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,9 +143,11 @@ pub struct CodexSessionSnapshot {
     pub session_total_tokens: Option<u64>,
     pub last_turn_tokens: Option<u64>,
     pub session_delta_tokens: Option<u64>,
+    pub resume_count: u32,
     pub input_tokens_total: u64,
     pub cached_input_tokens_total: u64,
     pub output_tokens_total: u64,
+    pub reasoning_output_tokens_total: u64,
     pub last_input_tokens: Option<u64>,
     pub last_cached_input_tokens: Option<u64>,
     pub last_output_tokens: Option<u64>,
@@ -135,6 +170,10 @@ pub struct CodexSessionSnapshot {
     pub last_token_event_at: Option<DateTime<Utc>>,
     pub last_activity: SystemTime,
     pub source_file: PathBuf,
+    #[serde(default)]
+    pub pid: Option<u32>,
+    #[serde(default)]
+    pub edit_counts: BTreeMap<String, u32>,
 }
 
 impl CodexSessionSnapshot {
@@ -147,9 +186,15 @@ impl CodexSessionSnapshot {
     }
 }
 
-#[derive(Debug, Default)]
+const GIT_TIMEOUT_RETRY: Duration = Duration::from_secs(5);
+const PROCESS_LIVENESS_TTL: Duration = Duration::from_secs(30);
+pub const IDLE_SESSION_LIST_WINDOW: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Debug)]
 pub struct GitBranchCache {
     ttl: Duration,
+    timeout: Duration,
+    resolver: fn(&Path, Duration) -> GitBranchLookup,
     entries: HashMap<PathBuf, CachedBranch>,
 }
 
@@ -162,9 +207,65 @@ struct CachedBranch {
 #[derive(Debug, Default)]
 pub struct SessionParseCache {
     entries: HashMap<PathBuf, CachedSessionEntry>,
+    pass_stats: ParseCacheStats,
+    process_liveness: HashMap<u32, CachedLiveness>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CachedLiveness {
+    alive: bool,
+    expires_at: Instant,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseCacheStats {
+    pub entries: usize,
+    pub cache_hits: u64,
+    pub reparses: u64,
+    pub bytes_read: u64,
 }
 
 impl SessionParseCache {
+    pub fn stats(&self) -> ParseCacheStats {
+        ParseCacheStats {
+            entries: self.entries.len(),
+            ..self.pass_stats
+        }
+    }
+
+    fn begin_pass(&mut self) {
+        self.pass_stats = ParseCacheStats::default();
+        let now = Instant::now();
+        self.process_liveness
+            .retain(|_, cached| cached.expires_at > now);
+    }
+
+    fn process_alive(&mut self, pid: u32) -> bool {
+        self.process_alive_with(pid, Instant::now(), process_exists)
+    }
+
+    fn process_alive_with(
+        &mut self,
+        pid: u32,
+        now: Instant,
+        probe: impl FnOnce(u32) -> bool,
+    ) -> bool {
+        if let Some(cached) = self.process_liveness.get(&pid)
+            && cached.expires_at > now
+        {
+            return cached.alive;
+        }
+        let alive = probe(pid);
+        self.process_liveness.insert(
+            pid,
+            CachedLiveness {
+                alive,
+                expires_at: now + PROCESS_LIVENESS_TTL,
+            },
+        );
+        alive
+    }
+
     pub fn rate_limit_envelopes(&self) -> Vec<RateLimitEnvelope> {
         self.entries
             .values()
@@ -173,13 +274,48 @@ impl SessionParseCache {
             .collect()
     }
 
-    pub fn latest_limits_source(&self) -> Option<EffectiveLimitSelection> {
+    pub fn latest_limits_source(
+        &self,
+        active: Option<&CodexSessionSnapshot>,
+    ) -> Option<EffectiveLimitSelection> {
         latest_limits_source_from(
             self.entries
                 .values()
                 .filter_map(|entry| entry.snapshot.as_ref()),
+            active,
         )
     }
+
+    pub fn idle_sessions(
+        &self,
+        active: &[CodexSessionSnapshot],
+        window: Duration,
+    ) -> Vec<CodexSessionSnapshot> {
+        let cutoff = SystemTime::now()
+            .checked_sub(window)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let active_ids: HashSet<&str> = active
+            .iter()
+            .map(|session| session.session_id.as_str())
+            .collect();
+        let idle = self
+            .entries
+            .values()
+            .filter_map(|entry| {
+                let snapshot = entry.snapshot.as_ref()?;
+                let recency = session_recency(snapshot, entry.modified);
+                (recency >= cutoff && !active_ids.contains(snapshot.session_id.as_str())).then(
+                    || CodexSessionSnapshot {
+                        last_activity: recency,
+                        ..snapshot.clone()
+                    },
+                )
+            })
+            .collect();
+        let mut idle = dedupe_sessions_by_id(idle);
+        idle.sort_by_key(|session| Reverse(session.last_activity));
+        idle
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -187,6 +323,7 @@ pub struct SessionCollectionDiagnostics {
     pub session_files_seen: usize,
     pub dropped_stale: usize,
     pub dropped_outside_sticky: usize,
+    pub dropped_dead_process: usize,
 }
 
 #[derive(Debug)]
@@ -225,6 +362,8 @@ impl GitBranchCache {
     pub fn new(ttl: Duration) -> Self {
         Self {
             ttl,
+            timeout: git_timeout(),
+            resolver: fetch_git_branch,
             entries: HashMap::new(),
         }
     }
@@ -241,12 +380,15 @@ impl GitBranchCache {
             return cached.value.clone();
         }
 
-        let value = fetch_git_branch(project_path);
+        let (value, ttl) = match (self.resolver)(project_path, self.timeout) {
+            GitBranchLookup::Resolved(value) => (value, self.ttl),
+            GitBranchLookup::TimedOut => (None, self.ttl.min(GIT_TIMEOUT_RETRY)),
+        };
         self.entries.insert(
             key,
             CachedBranch {
                 value: value.clone(),
-                expires_at: Instant::now() + self.ttl,
+                expires_at: Instant::now() + ttl,
             },
         );
         value
@@ -306,6 +448,7 @@ pub fn collect_active_sessions_multi_with_diagnostics(
         .checked_sub(active_sticky_window)
         .unwrap_or(SystemTime::UNIX_EPOCH);
 
+    parse_cache.begin_pass();
     let mut sessions = Vec::new();
     let mut seen_paths: HashSet<PathBuf> = HashSet::new();
     let mut diagnostics = SessionCollectionDiagnostics::default();
@@ -314,6 +457,7 @@ pub fn collect_active_sessions_multi_with_diagnostics(
         if !sessions_root.exists() {
             continue;
         }
+        let check_pids = !is_cross_namespace_sessions_root(sessions_root);
 
         for entry in WalkDir::new(sessions_root)
             .into_iter()
@@ -347,8 +491,18 @@ pub fn collect_active_sessions_multi_with_diagnostics(
             )? {
                 let recency = session_recency(&snapshot, modified);
                 snapshot.last_activity = recency;
-                match session_inclusion_decision(&snapshot, recency, stale_cutoff, sticky_cutoff) {
+                match session_inclusion_decision(
+                    &snapshot,
+                    recency,
+                    stale_cutoff,
+                    sticky_cutoff,
+                    |pid| !check_pids || parse_cache.process_alive(pid),
+                ) {
                     SessionInclusionDecision::Include => sessions.push(snapshot),
+                    SessionInclusionDecision::DropDeadProcess => {
+                        diagnostics.dropped_dead_process =
+                            diagnostics.dropped_dead_process.saturating_add(1);
+                    }
                     SessionInclusionDecision::DropStale => {
                         diagnostics.dropped_stale = diagnostics.dropped_stale.saturating_add(1);
                     }
@@ -390,48 +544,85 @@ fn dedupe_sessions_by_id(sessions: Vec<CodexSessionSnapshot>) -> Vec<CodexSessio
     deduped
 }
 
-pub fn latest_limits_source(sessions: &[CodexSessionSnapshot]) -> Option<EffectiveLimitSelection> {
-    latest_limits_source_from(sessions.iter())
+pub fn latest_limits_source(
+    sessions: &[CodexSessionSnapshot],
+    active: Option<&CodexSessionSnapshot>,
+) -> Option<EffectiveLimitSelection> {
+    latest_limits_source_from(sessions.iter(), active)
 }
 
 fn latest_limits_source_from<'a>(
     sessions: impl IntoIterator<Item = &'a CodexSessionSnapshot>,
+    active: Option<&CodexSessionSnapshot>,
 ) -> Option<EffectiveLimitSelection> {
     let mut candidates: Vec<SessionLimitCandidate> = Vec::new();
+    let mut same_project: Vec<SessionLimitCandidate> = Vec::new();
+    let inherit_project = active
+        .filter(|session| !session_has_limits(session))
+        .map(|session| session.project_name.as_str());
     for session in sessions {
-        if session.rate_limit_envelopes.is_empty() {
-            if telemetry_limits_present(&session.limits) {
-                candidates.push(SessionLimitCandidate {
-                    session_id: session.session_id.clone(),
-                    session_last_activity: session.last_activity,
-                    envelope: RateLimitEnvelope {
-                        limit_id: None,
-                        limit_name: None,
-                        plan_type: None,
-                        observed_at: session.last_token_event_at,
-                        scope: RateLimitScope::Other,
-                        limits: session.limits.clone(),
-                        credits: None,
-                    },
-                });
-            }
-            continue;
+        let before = candidates.len();
+        collect_limit_candidates(session, &mut candidates);
+        if inherit_project == Some(session.project_name.as_str()) {
+            same_project.extend_from_slice(&candidates[before..]);
         }
+    }
 
-        for envelope in &session.rate_limit_envelopes {
-            if telemetry_limits_present(&envelope.limits) {
-                candidates.push(SessionLimitCandidate {
-                    session_id: session.session_id.clone(),
-                    session_last_activity: session.last_activity,
-                    envelope: envelope.clone(),
-                });
-            }
-        }
+    if same_project.is_empty() {
+        select_effective_limits_global_first(&candidates)
+    } else {
+        select_effective_limits_global_first(&same_project)
     }
+}
 
+pub fn session_limits_source(session: &CodexSessionSnapshot) -> Option<EffectiveLimitSelection> {
+    let mut candidates = Vec::new();
+    collect_limit_candidates(session, &mut candidates);
     select_effective_limits_global_first(&candidates)
 }
 
+fn session_has_limits(session: &CodexSessionSnapshot) -> bool {
+    telemetry_limits_present(&session.limits)
+        || session
+            .rate_limit_envelopes
+            .iter()
+            .any(|envelope| telemetry_limits_present(&envelope.limits))
+}
+
+fn collect_limit_candidates(
+    session: &CodexSessionSnapshot,
+    candidates: &mut Vec<SessionLimitCandidate>,
+) {
+    if session.rate_limit_envelopes.is_empty() {
+        if telemetry_limits_present(&session.limits) {
+            candidates.push(SessionLimitCandidate {
+                session_id: session.session_id.clone(),
+                session_last_activity: session.last_activity,
+                envelope: RateLimitEnvelope {
+                    limit_id: None,
+                    limit_name: None,
+                    plan_type: None,
+                    observed_at: session.last_token_event_at,
+                    scope: RateLimitScope::Other,
+                    limits: session.limits.clone(),
+                    credits: None,
+                },
+            });
+        }
+        return;
+    }
+
+    for envelope in &session.rate_limit_envelopes {
+        if telemetry_limits_present(&envelope.limits) {
+            candidates.push(SessionLimitCandidate {
+                session_id: session.session_id.clone(),
+                session_last_activity: session.last_activity,
+                envelope: envelope.clone(),
+            });
+        }
+    }
+}
+
 pub fn preferred_active_session(
     sessions: &[CodexSessionSnapshot],
 ) -> Option<&CodexSessionSnapshot> {
@@ -440,6 +631,30 @@ pub fn preferred_active_session(
         .max_by_key(|session| session_rank_key(session))
 }
 
+pub fn preferred_presence_session<'a>(
+    sessions: &'a [CodexSessionSnapshot],
+    privacy: &PrivacyConfig,
+) -> Option<&'a CodexSessionSnapshot> {
+    sessions
+        .iter()
+        .filter(|session| privacy.allows_project(&session.project_name))
+        .max_by_key(|session| session_rank_key(session))
+}
+
+pub fn pinned_presence_session<'a>(
+    sessions: &'a [CodexSessionSnapshot],
+    privacy: &PrivacyConfig,
+    pinned_session_id: Option<&str>,
+) -> Option<&'a CodexSessionSnapshot> {
+    pinned_session_id
+        .and_then(|pinned| {
+            sessions.iter().find(|session| {
+                session.session_id == pinned && privacy.allows_project(&session.project_name)
+            })
+        })
+        .or_else(|| preferred_presence_session(sessions, privacy))
+}
+
 pub fn limits_present(limits: &RateLimits) -> bool {
     telemetry_limits_present(limits)
 }
@@ -452,7 +667,7 @@ fn should_include_session(
     sticky_cutoff: SystemTime,
 ) -> bool {
     matches!(
-        session_inclusion_decision(snapshot, recency, stale_cutoff, sticky_cutoff),
+        session_inclusion_decision(snapshot, recency, stale_cutoff, sticky_cutoff, |_| true),
         SessionInclusionDecision::Include
     )
 }
@@ -462,6 +677,7 @@ enum SessionInclusionDecision {
     Include,
     DropStale,
     DropOutsideSticky,
+    DropDeadProcess,
 }
 
 fn session_inclusion_decision(
@@ -469,13 +685,17 @@ fn session_inclusion_decision(
     recency: SystemTime,
     stale_cutoff: SystemTime,
     sticky_cutoff: SystemTime,
+    process_alive: impl FnOnce(u32) -> bool,
 ) -> SessionInclusionDecision {
+    if recency < sticky_cutoff && recency < stale_cutoff {
+        return SessionInclusionDecision::DropOutsideSticky;
+    }
+    if !snapshot.pid.is_none_or(process_alive) {
+        return SessionInclusionDecision::DropDeadProcess;
+    }
     if recency >= stale_cutoff {
         return SessionInclusionDecision::Include;
     }
-    if recency < sticky_cutoff {
-        return SessionInclusionDecision::DropOutsideSticky;
-    }
     if snapshot
         .activity
         .as_ref()
@@ -491,8 +711,10 @@ fn session_activity_is_sticky_active(activity: &SessionActivitySnapshot) -> bool
     if activity.pending_calls > 0 {
         return true;
     }
-    matches!(activity.kind, SessionActivityKind::WaitingInput)
-        || is_working_activity_kind(&activity.kind)
+    matches!(
+        activity.kind,
+        SessionActivityKind::WaitingInput | SessionActivityKind::AwaitingApproval
+    ) || is_working_activity_kind(&activity.kind)
 }
 
 fn session_rank_key(snapshot: &CodexSessionSnapshot) -> (SystemTime, usize, u8, String) {
@@ -519,8 +741,10 @@ fn session_activity_priority(kind: &SessionActivityKind) -> u8 {
         SessionActivityKind::Thinking
         | SessionActivityKind::ReadingFile
         | SessionActivityKind::EditingFile
-        | SessionActivityKind::RunningCommand => 3,
-        SessionActivityKind::WaitingInput => 2,
+        | SessionActivityKind::WritingTests
+        | SessionActivityKind::RunningCommand
+        | SessionActivityKind::Archiving => 3,
+        SessionActivityKind::WaitingInput | SessionActivityKind::AwaitingApproval => 2,
         SessionActivityKind::Idle => 1,
     }
 }
@@ -531,7 +755,9 @@ fn is_working_activity_kind(kind: &SessionActivityKind) -> bool {
         SessionActivityKind::Thinking
             | SessionActivityKind::ReadingFile
             | SessionActivityKind::EditingFile
+            | SessionActivityKind::WritingTests
             | SessionActivityKind::RunningCommand
+            | SessionActivityKind::Archiving
     )
 }
 
@@ -622,9 +848,11 @@ mod tests {
             session_total_tokens: None,
             last_turn_tokens: None,
             session_delta_tokens: None,
+            resume_count: 0,
             input_tokens_total: 0,
             cached_input_tokens_total: 0,
             output_tokens_total: 0,
+            reasoning_output_tokens_total: 0,
             last_input_tokens: None,
             last_cached_input_tokens: None,
             last_output_tokens: None,
@@ -646,14 +874,45 @@ mod tests {
                 last_effective_signal_at: Some(Utc::now()),
                 idle_candidate_at: None,
                 pending_calls: 0,
+                tool: None,
             }),
             started_at: None,
             last_token_event_at: None,
             last_activity: SystemTime::now(),
             source_file: PathBuf::from("policy.jsonl"),
+            pid: None,
+            edit_counts: BTreeMap::new(),
         }
     }
 
+    #[test]
+    fn resumed_session_keeps_running_token_total_monotonic() {
+        let snapshot = parse_one(
+            r#"{"timestamp":"2026-02-09T16:33:13Z","type":"session_meta","payload":{"id":"abc-123","cwd":"C:\\repo\\app"}}
+{"timestamp":"2026-02-09T16:34:13Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"total_tokens":5000},"last_token_usage":{"total_tokens":300}}}}
+{"timestamp":"2026-02-09T17:00:00Z","type":"session_meta","payload":{"id":"abc-123","cwd":"C:\\repo\\app"}}
+{"timestamp":"2026-02-09T17:01:00Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"total_tokens":4000},"last_token_usage":{"total_tokens":200}}}}
+{"timestamp":"2026-02-09T17:02:00Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"total_tokens":4600},"last_token_usage":{"total_tokens":600}}}}"#,
+        );
+
+        assert_eq!(snapshot.resume_count, 1);
+        assert_eq!(snapshot.session_total_tokens, Some(9600));
+        assert_eq!(snapshot.session_delta_tokens, Some(600));
+    }
+
+    #[test]
+    fn large_token_total_drop_without_marker_counts_as_resume() {
+        let snapshot = parse_one(
+            r#"{"timestamp":"2026-02-09T16:33:13Z","type":"session_meta","payload":{"id":"abc-123"}}
+{"timestamp":"2026-02-09T16:34:13Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"total_tokens":8000}}}}
+{"timestamp":"2026-02-09T16:35:13Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"total_tokens":7900}}}}
+{"timestamp":"2026-02-09T16:36:13Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"total_tokens":100}}}}"#,
+        );
+
+        assert_eq!(snapshot.resume_count, 1);
+        assert_eq!(snapshot.session_total_tokens, Some(8000));
+    }
+
     #[test]
     fn parses_tokens_delta_and_remaining_limits() {
         let snapshot = parse_one(
@@ -723,7 +982,7 @@ mod tests {
         );
         assert_eq!(
             parse_cache
-                .latest_limits_source()
+                .latest_limits_source(None)
                 .and_then(|selected| selected.limits.primary)
                 .map(|window| window.window_minutes),
             Some(10_080)
@@ -903,6 +1162,17 @@ mod tests {
         assert_eq!(snapshot.last_cached_input_tokens, Some(100));
     }
 
+    #[test]
+    fn reasoning_output_tokens_are_parsed_from_token_totals() {
+        let snapshot = parse_one(
+            r#"{"timestamp":"2026-07-09T10:00:00Z","type":"turn_context","payload":{"cwd":"C:\\repo\\app","model":"gpt-5.4","effort":"high"}}
+{"timestamp":"2026-07-09T10:00:01Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1000,"cached_input_tokens":0,"output_tokens":600,"reasoning_output_tokens":450}}}}"#,
+        );
+
+        assert_eq!(snapshot.output_tokens_total, 600);
+        assert_eq!(snapshot.reasoning_output_tokens_total, 450);
+    }
+
     #[test]
     fn thread_settings_keep_fast_mode_scoped_to_the_session() {
         let snapshot = parse_one(
@@ -1049,7 +1319,7 @@ mod tests {
             .and_then(|activity| activity.target)
             .expect("target");
         assert!(target.is_char_boundary(target.len()));
-        assert!(target.len() <= 72);
+        assert!(target.chars().count() <= 72);
         assert!(!target.contains("private"));
     }
 
@@ -1165,6 +1435,135 @@ mod tests {
         assert_eq!(activity.to_text(true), "Reading ui.rs");
     }
 
+    #[test]
+    fn parses_reading_activity_from_grep_with_file() {
+        let snapshot = parse_one(
+            r#"{"type":"session_meta","payload":{"id":"grep","cwd":"/repo/app"}}
+{"timestamp":"2026-02-09T16:41:13Z","type":"response_item","payload":{"type":"function_call","name":"exec_command","arguments":"{\"cmd\":\"grep -n foo src/lib.rs\"}","call_id":"call_grep"}}"#,
+        );
+
+        let activity = snapshot.activity.expect("activity");
+        assert_eq!(activity.kind, SessionActivityKind::ReadingFile);
+        assert_eq!(activity.target.as_deref(), Some("lib.rs"));
+    }
+
+    #[test]
+    fn grep_keeps_quoted_patterns_as_one_argument() {
+        let snapshot = parse_one(
+            r#"{"type":"session_meta","payload":{"id":"grep-quoted","cwd":"/repo/app"}}
+{"timestamp":"2026-02-09T16:41:13Z","type":"response_item","payload":{"type":"function_call","name":"exec_command","arguments":"{\"cmd\":\"grep -n \\\"foo bar\\\" src/lib.rs\"}","call_id":"call_grep"}}"#,
+        );
+        let activity = snapshot.activity.expect("activity");
+        assert_eq!(activity.kind, SessionActivityKind::ReadingFile);
+        assert_eq!(activity.target.as_deref(), Some("lib.rs"));
+
+        let snapshot = parse_one(
+            r#"{"type":"session_meta","payload":{"id":"grep-quoted-stdin","cwd":"/repo/app"}}
+{"timestamp":"2026-02-09T16:41:13Z","type":"response_item","payload":{"type":"function_call","name":"exec_command","arguments":"{\"cmd\":\"grep \\\"foo bar\\\"\"}","call_id":"call_grep"}}"#,
+        );
+        let activity = snapshot.activity.expect("activity");
+        assert_eq!(activity.kind, SessionActivityKind::RunningCommand);
+        assert_eq!(activity.target.as_deref(), Some("grep"));
+    }
+
+    #[test]
+    fn grep_without_a_file_is_not_a_read() {
+        let snapshot = parse_one(
+            r#"{"type":"session_meta","payload":{"id":"grep-stdin","cwd":"/repo/app"}}
+{"timestamp":"2026-02-09T16:41:13Z","type":"response_item","payload":{"type":"function_call","name":"exec_command","arguments":"{\"cmd\":\"grep foo\"}","call_id":"call_grep"}}"#,
+        );
+
+        let activity = snapshot.activity.expect("activity");
+        assert_eq!(activity.kind, SessionActivityKind::RunningCommand);
+        assert_eq!(activity.target.as_deref(), Some("grep"));
+    }
+
+    #[test]
+    fn parses_reading_activity_from_awk_and_sed_file_targets() {
+        for (id, command, expected) in [
+            ("awk", "awk '{print $1}' data/report.csv", "report.csv"),
+            ("sed", "sed -n 's/a/b/p' src/main.rs", "main.rs"),
+            ("sed-e", "sed -e 's/a b/c/' notes.txt", "notes.txt"),
+        ] {
+            let snapshot = parse_one(&format!(
+                r#"{{"type":"session_meta","payload":{{"id":"{id}","cwd":"/repo/app"}}}}
+{{"timestamp":"2026-02-09T16:41:13Z","type":"response_item","payload":{{"type":"function_call","name":"exec_command","arguments":"{{\"cmd\":\"{command}\"}}","call_id":"call_script"}}}}"#
+            ));
+
+            let activity = snapshot.activity.expect("activity");
+            assert_eq!(activity.kind, SessionActivityKind::ReadingFile, "{command}");
+            assert_eq!(activity.target.as_deref(), Some(expected), "{command}");
+        }
+    }
+
+    #[test]
+    fn sed_in_place_is_an_edit_not_a_read() {
+        for (id, command, expected) in [
+            ("sed-i", "sed -i 's/a/b/' src/main.rs", "main.rs"),
+            (
+                "sed-i-bak",
+                "sed -i.bak 's/a/b/' config.toml",
+                "config.toml",
+            ),
+            (
+                "sed-in-place",
+                "sed --in-place -e 's/a b/c/' notes.txt",
+                "notes.txt",
+            ),
+        ] {
+            let snapshot = parse_one(&format!(
+                r#"{{"type":"session_meta","payload":{{"id":"{id}","cwd":"/repo/app"}}}}
+{{"timestamp":"2026-02-09T16:41:13Z","type":"response_item","payload":{{"type":"function_call","name":"exec_command","arguments":"{{\"cmd\":\"{command}\"}}","call_id":"call_script"}}}}"#
+            ));
+
+            let activity = snapshot.activity.expect("activity");
+            assert_eq!(activity.kind, SessionActivityKind::EditingFile, "{command}");
+            assert_eq!(activity.target.as_deref(), Some(expected), "{command}");
+        }
+    }
+
+    #[test]
+    fn awk_and_sed_without_a_file_are_not_reads() {
+        for command in ["awk '{print $1}'", "sed 's/a/b/'"] {
+            let snapshot = parse_one(&format!(
+                r#"{{"type":"session_meta","payload":{{"id":"stdin","cwd":"/repo/app"}}}}
+{{"timestamp":"2026-02-09T16:41:13Z","type":"response_item","payload":{{"type":"function_call","name":"exec_command","arguments":"{{\"cmd\":\"{command}\"}}","call_id":"call_script"}}}}"#
+            ));
+
+            let activity = snapshot.activity.expect("activity");
+            assert_eq!(
+                activity.kind,
+                SessionActivityKind::RunningCommand,
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_archiving_activity_from_tar_create() {
+        let snapshot = parse_one(
+            r#"{"type":"session_meta","payload":{"id":"tar","cwd":"/repo/app"}}
+{"timestamp":"2026-02-09T16:41:13Z","type":"response_item","payload":{"type":"function_call","name":"exec_command","arguments":"{\"cmd\":\"tar -czf out.tgz src/\"}","call_id":"call_tar"}}"#,
+        );
+
+        let activity = snapshot.activity.expect("activity");
+        assert_eq!(activity.kind, SessionActivityKind::Archiving);
+        assert_eq!(activity.target.as_deref(), Some("out.tgz"));
+        assert_eq!(activity.to_text(true), "Archiving out.tgz");
+    }
+
+    #[test]
+    fn parses_archiving_activity_from_unzip() {
+        let snapshot = parse_one(
+            r#"{"type":"session_meta","payload":{"id":"unzip","cwd":"/repo/app"}}
+{"timestamp":"2026-02-09T16:41:13Z","type":"response_item","payload":{"type":"function_call","name":"shell_command","arguments":"{\"command\":\"unzip data.zip\"}","call_id":"call_unzip"}}"#,
+        );
+
+        let activity = snapshot.activity.expect("activity");
+        assert_eq!(activity.kind, SessionActivityKind::Archiving);
+        assert_eq!(activity.target.as_deref(), Some("data.zip"));
+    }
+
     #[test]
     fn parses_editing_activity_from_apply_patch() {
         let snapshot = parse_one(
@@ -1178,6 +1577,116 @@ mod tests {
         assert_eq!(activity.to_text(true), "Editing session.rs");
     }
 
+    #[test]
+    fn edit_counts_accumulate_every_patched_file_and_in_place_edit() {
+        let snapshot = parse_one(
+            r#"{"type":"session_meta","payload":{"id":"edits","cwd":"/repo/app"}}
+{"timestamp":"2026-02-09T16:42:13Z","type":"response_item","payload":{"type":"custom_tool_call","name":"apply_patch","call_id":"call_a","input":"*** Begin Patch\n*** Update File: src/main.rs\n@@\n*** Add File: scripts/run.py\n+x\n*** End Patch\n"}}
+{"timestamp":"2026-02-09T16:42:14Z","type":"response_item","payload":{"type":"custom_tool_call_output","call_id":"call_a","output":"ok"}}
+{"timestamp":"2026-02-09T16:42:15Z","type":"response_item","payload":{"type":"custom_tool_call","name":"apply_patch","call_id":"call_b","input":"*** Begin Patch\n*** Update File: src/main.rs\n@@\n*** End Patch\n"}}
+{"timestamp":"2026-02-09T16:42:16Z","type":"response_item","payload":{"type":"function_call","name":"exec_command","arguments":"{\"cmd\":\"sed -i 's/a/b/' src/lib.rs\"}","call_id":"call_c"}}"#,
+        );
+
+        assert_eq!(
+            snapshot.edit_counts,
+            BTreeMap::from([
+                ("lib.rs".to_string(), 1),
+                ("main.rs".to_string(), 2),
+                ("run.py".to_string(), 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn patches_to_test_paths_report_writing_tests() {
+        let patch = |path: &str| {
+            parse_one(&format!(
+                r#"{{"type":"session_meta","payload":{{"id":"tests","cwd":"/repo/app"}}}}
+{{"timestamp":"2026-02-09T16:42:13Z","type":"response_item","payload":{{"type":"custom_tool_call","name":"apply_patch","call_id":"call_patch","input":"*** Begin Patch\n*** Update File: {path}\n@@\n*** End Patch\n"}}}}"#
+            ))
+            .activity
+            .expect("activity")
+        };
+
+        let tests = patch("tests/foo.rs");
+        assert_eq!(tests.kind, SessionActivityKind::WritingTests);
+        assert_eq!(tests.to_text(true), "Writing tests in foo.rs");
+        assert_eq!(patch("src/foo.rs").kind, SessionActivityKind::EditingFile);
+        for path in ["src/parser_test.rs", "test_api.py", "web/app.spec.ts"] {
+            assert_eq!(
+                patch(path).kind,
+                SessionActivityKind::WritingTests,
+                "{path}"
+            );
+        }
+        assert_eq!(
+            patch("src/testing.rs").kind,
+            SessionActivityKind::EditingFile
+        );
+    }
+
+    #[test]
+    fn project_name_ignores_trailing_cwd_separators() {
+        for (cwd, expected) in [
+            ("/home/me/proj/", "proj"),
+            ("C:\\\\repo\\\\app\\\\", "app"),
+            ("/home/me/proj", "proj"),
+            ("/", "unknown-project"),
+        ] {
+            let snapshot = parse_one(&format!(
+                r#"{{"type":"session_meta","payload":{{"id":"cwd","cwd":"{cwd}"}}}}"#
+            ));
+            assert_eq!(snapshot.project_name, expected, "{cwd}");
+        }
+    }
+
+    #[test]
+    fn fallback_session_id_strips_known_filename_patterns() {
+        let uuid = "0194a1b2-c3d4-7e5f-8a9b-0c1d2e3f4a5b";
+        assert_eq!(
+            activity::session_id_from_file_stem(&format!("rollout-2026-02-09T16-41-13-{uuid}")),
+            uuid
+        );
+        assert_eq!(
+            activity::session_id_from_file_stem(&format!("session-{uuid}")),
+            uuid
+        );
+        assert_eq!(activity::session_id_from_file_stem(uuid), uuid);
+        assert_eq!(
+            activity::session_id_from_file_stem("rollout-2026-02-09T16-41-13-nightly-run"),
+            "nightly-run"
+        );
+        assert_eq!(
+            activity::session_id_from_file_stem("session-2026-02-09"),
+            "session-2026-02-09"
+        );
+        assert_eq!(activity::session_id_from_file_stem("notes"), "notes");
+    }
+
+    #[test]
+    fn namespaced_mcp_tool_call_gets_readable_target_and_map_override() {
+        let snapshot = parse_one(
+            r#"{"type":"session_meta","payload":{"id":"mcp","cwd":"/repo/app"}}
+{"timestamp":"2026-02-09T16:43:13Z","type":"response_item","payload":{"type":"custom_tool_call","name":"docs.search","call_id":"call_mcp","input":"{}"}}"#,
+        );
+
+        let mut activity = snapshot.activity.expect("activity");
+        assert_eq!(activity.kind, SessionActivityKind::RunningCommand);
+        assert_eq!(activity.tool.as_deref(), Some("docs.search"));
+        assert_eq!(activity.to_text(true), "Running search (docs)");
+
+        let short = BTreeMap::from([("search".to_string(), SessionActivityKind::ReadingFile)]);
+        activity.apply_tool_activity_map(&short);
+        assert_eq!(activity.kind, SessionActivityKind::ReadingFile);
+
+        let full = BTreeMap::from([
+            ("search".to_string(), SessionActivityKind::ReadingFile),
+            ("docs.search".to_string(), SessionActivityKind::Thinking),
+        ]);
+        activity.apply_tool_activity_map(&full);
+        assert_eq!(activity.kind, SessionActivityKind::Thinking);
+    }
+
     #[test]
     fn commentary_keeps_existing_working_activity() {
         let call_ts = Utc::now().to_rfc3339();
@@ -1220,6 +1729,57 @@ mod tests {
         assert_eq!(activity.kind, SessionActivityKind::WaitingInput);
     }
 
+    #[test]
+    fn git_branch_timeout_returns_none_and_retries_after_short_cache() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn slow_resolver(_: &Path, timeout: Duration) -> GitBranchLookup {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(timeout);
+            GitBranchLookup::TimedOut
+        }
+
+        let tmp = TempDir::new().expect("temp dir");
+        let mut cache = GitBranchCache {
+            ttl: Duration::from_secs(30),
+            timeout: Duration::from_millis(5),
+            resolver: slow_resolver,
+            entries: HashMap::new(),
+        };
+
+        assert_eq!(cache.get(tmp.path()), None);
+        assert_eq!(cache.get(tmp.path()), None);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+        let cached = cache.entries.get(tmp.path()).expect("cached timeout");
+        assert!(cached.expires_at <= Instant::now() + GIT_TIMEOUT_RETRY);
+    }
+
+    #[test]
+    fn exec_approval_request_marks_awaiting_approval_until_call_completes() {
+        let call_ts = Utc::now().to_rfc3339();
+        let approval_ts = (Utc::now() + ChronoDuration::seconds(1)).to_rfc3339();
+        let output_ts = (Utc::now() + ChronoDuration::seconds(2)).to_rfc3339();
+        let pending = format!(
+            r#"{{"type":"session_meta","payload":{{"id":"approval","cwd":"C:\\repo\\app"}}}}
+{{"timestamp":"{call_ts}","type":"response_item","payload":{{"type":"function_call","name":"shell_command","arguments":"{{\"command\":\"cargo test\"}}","call_id":"call_1"}}}}
+{{"timestamp":"{approval_ts}","type":"event_msg","payload":{{"type":"exec_approval_request","call_id":"call_1","command":["cargo","test"],"cwd":"C:\\repo\\app"}}}}"#
+        );
+        let snapshot = parse_one(&pending);
+        let activity = snapshot.activity.expect("activity");
+        assert_eq!(activity.kind, SessionActivityKind::AwaitingApproval);
+        assert_eq!(activity.to_text(true), "Awaiting approval for cargo test");
+        assert_eq!(activity.pending_calls, 1);
+
+        let resolved = format!(
+            r#"{pending}
+{{"timestamp":"{output_ts}","type":"response_item","payload":{{"type":"function_call_output","call_id":"call_1"}}}}"#
+        );
+        let snapshot = parse_one(&resolved);
+        let activity = snapshot.activity.expect("activity");
+        assert_eq!(activity.kind, SessionActivityKind::Thinking);
+    }
+
     #[test]
     fn web_search_call_counts_as_running_activity() {
         let ts = Utc::now().to_rfc3339();
@@ -1306,9 +1866,11 @@ mod tests {
             session_total_tokens: None,
             last_turn_tokens: None,
             session_delta_tokens: None,
+            resume_count: 0,
             input_tokens_total: 0,
             cached_input_tokens_total: 0,
             output_tokens_total: 0,
+            reasoning_output_tokens_total: 0,
             last_input_tokens: None,
             last_cached_input_tokens: None,
             last_output_tokens: None,
@@ -1351,6 +1913,8 @@ mod tests {
             last_token_event_at: Utc.timestamp_opt(1000, 0).single(),
             last_activity: now,
             source_file: PathBuf::from("older.jsonl"),
+            pid: None,
+            edit_counts: BTreeMap::new(),
         };
         let newer = CodexSessionSnapshot {
             session_id: "newer".to_string(),
@@ -1367,9 +1931,11 @@ mod tests {
             session_total_tokens: None,
             last_turn_tokens: None,
             session_delta_tokens: None,
+            resume_count: 0,
             input_tokens_total: 0,
             cached_input_tokens_total: 0,
             output_tokens_total: 0,
+            reasoning_output_tokens_total: 0,
             last_input_tokens: None,
             last_cached_input_tokens: None,
             last_output_tokens: None,
@@ -1412,13 +1978,117 @@ mod tests {
             last_token_event_at: Utc.timestamp_opt(2000, 0).single(),
             last_activity: now,
             source_file: PathBuf::from("newer.jsonl"),
+            pid: None,
+            edit_counts: BTreeMap::new(),
         };
 
         let sessions = vec![older, newer];
-        let source = latest_limits_source(&sessions).expect("limits source");
+        let source = latest_limits_source(&sessions, None).expect("limits source");
         assert_eq!(source.source_session_id, "newer");
     }
 
+    #[test]
+    fn session_limits_source_follows_the_driving_session_not_the_latest() {
+        let with_limits = |session_id: &str, remaining: f64, observed: i64| {
+            let limits = RateLimits {
+                primary: Some(UsageWindow {
+                    used_percent: 100.0 - remaining,
+                    remaining_percent: remaining,
+                    window_minutes: 300,
+                    resets_at: None,
+                }),
+                secondary: None,
+            };
+            CodexSessionSnapshot {
+                session_id: session_id.to_string(),
+                limits: limits.clone(),
+                rate_limit_envelopes: vec![RateLimitEnvelope {
+                    limit_id: Some("codex".to_string()),
+                    limit_name: None,
+                    plan_type: None,
+                    observed_at: Utc.timestamp_opt(observed, 0).single(),
+                    scope: RateLimitScope::GlobalCodex,
+                    limits,
+                    credits: None,
+                }],
+                ..policy_snapshot(None)
+            }
+        };
+        let driving = with_limits("driving", 30.0, 1_000);
+        let sessions = vec![driving.clone(), with_limits("latest", 70.0, 2_000)];
+
+        let global = latest_limits_source(&sessions, Some(&driving)).expect("global source");
+        assert_eq!(global.source_session_id, "latest");
+
+        let followed = session_limits_source(&driving).expect("active source");
+        assert_eq!(followed.source_session_id, "driving");
+        assert_eq!(
+            followed
+                .limits
+                .primary
+                .map(|window| window.remaining_percent),
+            Some(30.0)
+        );
+        assert!(session_limits_source(&policy_snapshot(None)).is_none());
+    }
+
+    #[test]
+    fn active_session_without_limits_inherits_same_project_limits() {
+        let with_limits = |session_id: &str, project: &str, remaining: f64, observed: i64| {
+            let limits = RateLimits {
+                primary: Some(UsageWindow {
+                    used_percent: 100.0 - remaining,
+                    remaining_percent: remaining,
+                    window_minutes: 300,
+                    resets_at: None,
+                }),
+                secondary: None,
+            };
+            CodexSessionSnapshot {
+                session_id: session_id.to_string(),
+                project_name: project.to_string(),
+                limits: limits.clone(),
+                rate_limit_envelopes: vec![RateLimitEnvelope {
+                    limit_id: Some("codex".to_string()),
+                    limit_name: None,
+                    plan_type: None,
+                    observed_at: Utc.timestamp_opt(observed, 0).single(),
+                    scope: RateLimitScope::GlobalCodex,
+                    limits,
+                    credits: None,
+                }],
+                ..policy_snapshot(None)
+            }
+        };
+        let active = CodexSessionSnapshot {
+            session_id: "active".to_string(),
+            project_name: "app".to_string(),
+            ..policy_snapshot(Some(SessionActivityKind::Thinking))
+        };
+        let sessions = vec![
+            with_limits("app-old", "app", 40.0, 1_000),
+            with_limits("app-new", "app", 55.0, 2_000),
+            with_limits("other", "other", 90.0, 3_000),
+            active.clone(),
+        ];
+
+        let source = latest_limits_source(&sessions, Some(&active)).expect("limits source");
+        assert_eq!(source.source_session_id, "app-new");
+        assert_eq!(
+            source.limits.primary.map(|window| window.remaining_percent),
+            Some(55.0)
+        );
+
+        let unrelated = CodexSessionSnapshot {
+            project_name: "elsewhere".to_string(),
+            ..active
+        };
+        let source = latest_limits_source(&sessions, Some(&unrelated)).expect("limits source");
+        assert_eq!(source.source_session_id, "other");
+        let source = latest_limits_source(&sessions, None).expect("limits source");
+        assert_eq!(source.source_session_id, "other");
+    }
+
     #[test]
     fn sticky_policy_keeps_working_session_within_window() {
         let now = SystemTime::now();
@@ -1517,6 +2187,57 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn recorded_pid_liveness_decides_inclusion() {
+        let now = SystemTime::now();
+        let recency = now.checked_sub(Duration::from_secs(30)).expect("recency");
+        let stale_cutoff = now.checked_sub(Duration::from_secs(90)).expect("stale");
+        let sticky_cutoff = now
+            .checked_sub(Duration::from_secs(60 * 60))
+            .expect("sticky");
+        let mut snapshot = policy_snapshot(Some(SessionActivityKind::Thinking));
+        snapshot.pid = Some(4242);
+
+        assert_eq!(
+            session_inclusion_decision(&snapshot, recency, stale_cutoff, sticky_cutoff, |pid| {
+                pid == 4242
+            }),
+            SessionInclusionDecision::Include
+        );
+        assert_eq!(
+            session_inclusion_decision(&snapshot, recency, stale_cutoff, sticky_cutoff, |_| false),
+            SessionInclusionDecision::DropDeadProcess
+        );
+
+        snapshot.pid = None;
+        assert_eq!(
+            session_inclusion_decision(&snapshot, recency, stale_cutoff, sticky_cutoff, |_| false),
+            SessionInclusionDecision::Include
+        );
+    }
+
+    #[test]
+    fn process_liveness_is_cached_per_pid_until_the_ttl_expires() {
+        let mut cache = SessionParseCache::default();
+        let probes = std::cell::Cell::new(0);
+        let probe = |alive: bool| {
+            let probes = &probes;
+            move |_pid: u32| {
+                probes.set(probes.get() + 1);
+                alive
+            }
+        };
+        let now = Instant::now();
+
+        assert!(cache.process_alive_with(4242, now, probe(true)));
+        assert!(cache.process_alive_with(4242, now + Duration::from_secs(5), probe(false)));
+        assert_eq!(probes.get(), 1);
+
+        assert!(!cache.process_alive_with(4242, now + PROCESS_LIVENESS_TTL, probe(false)));
+        assert!(!cache.process_alive_with(7, now, probe(false)));
+        assert_eq!(probes.get(), 3);
+    }
+
     #[test]
     fn session_recency_uses_newest_activity_signal() {
         let file_modified = SystemTime::now()
@@ -1597,6 +2318,87 @@ mod tests {
         assert_eq!(active.session_id, "newest_waiting");
     }
 
+    #[test]
+    fn presence_session_skips_denylisted_projects() {
+        let now = SystemTime::now();
+        let mut private = policy_snapshot(Some(SessionActivityKind::Thinking));
+        private.session_id = "private".to_string();
+        private.project_name = "client-secret".to_string();
+        private.last_activity = now;
+        let mut public = policy_snapshot(Some(SessionActivityKind::Thinking));
+        public.session_id = "public".to_string();
+        public.project_name = "oss-tool".to_string();
+        public.last_activity = now.checked_sub(Duration::from_secs(60)).expect("older");
+        let sessions = vec![private, public];
+
+        let mut privacy = PrivacyConfig::default();
+        assert_eq!(
+            preferred_presence_session(&sessions, &privacy).map(|s| s.session_id.as_str()),
+            Some("private")
+        );
+
+        privacy.project_denylist = vec!["Client-Secret".to_string()];
+        assert_eq!(
+            preferred_presence_session(&sessions, &privacy).map(|s| s.session_id.as_str()),
+            Some("public")
+        );
+
+        privacy.project_denylist.push("oss-tool".to_string());
+        assert!(preferred_presence_session(&sessions, &privacy).is_none());
+    }
+
+    #[test]
+    fn presence_session_allowlist_only_publishes_listed_projects() {
+        let mut listed = policy_snapshot(Some(SessionActivityKind::Thinking));
+        listed.session_id = "listed".to_string();
+        listed.project_name = "stream-demo".to_string();
+        listed.last_activity = SystemTime::now()
+            .checked_sub(Duration::from_secs(60))
+            .expect("older");
+        let mut unlisted = policy_snapshot(Some(SessionActivityKind::Thinking));
+        unlisted.session_id = "unlisted".to_string();
+        unlisted.project_name = "work-repo".to_string();
+        let sessions = vec![listed, unlisted];
+
+        let privacy = PrivacyConfig {
+            project_allowlist: vec!["stream-demo".to_string()],
+            ..PrivacyConfig::default()
+        };
+        assert_eq!(
+            preferred_presence_session(&sessions, &privacy).map(|s| s.session_id.as_str()),
+            Some("listed")
+        );
+        assert!(preferred_presence_session(&sessions[1..], &privacy).is_none());
+    }
+
+    #[test]
+    fn pinned_session_drives_presence_until_it_disappears() {
+        let now = SystemTime::now();
+        let mut newest = policy_snapshot(Some(SessionActivityKind::Thinking));
+        newest.session_id = "newest".to_string();
+        newest.last_activity = now;
+        let mut pinned = policy_snapshot(Some(SessionActivityKind::Thinking));
+        pinned.session_id = "pinned".to_string();
+        pinned.last_activity = now.checked_sub(Duration::from_secs(60)).expect("older");
+        let sessions = vec![newest, pinned];
+        let privacy = PrivacyConfig::default();
+
+        assert_eq!(
+            pinned_presence_session(&sessions, &privacy, Some("pinned"))
+                .map(|s| s.session_id.as_str()),
+            Some("pinned")
+        );
+        assert_eq!(
+            pinned_presence_session(&sessions[..1], &privacy, Some("pinned"))
+                .map(|s| s.session_id.as_str()),
+            Some("newest")
+        );
+        assert_eq!(
+            pinned_presence_session(&sessions, &privacy, None).map(|s| s.session_id.as_str()),
+            Some("newest")
+        );
+    }
+
     #[test]
     fn ranking_tiebreaks_by_pending_then_activity_when_recency_equal() {
         let now = SystemTime::now();
@@ -1744,6 +2546,58 @@ mod tests {
         assert!(cache2.partial_line_buffer.is_empty());
     }
 
+    #[test]
+    fn parse_cache_stats_count_hits_and_reparses() {
+        let tmp = TempDir::new().expect("temp dir");
+        let file_path = tmp.path().join("session.jsonl");
+        std::fs::write(
+            &file_path,
+            "{\"type\":\"session_meta\",\"payload\":{\"id\":\"stats\",\"cwd\":\"/repo/app\"}}\n",
+        )
+        .expect("write initial");
+        let initial_len = std::fs::metadata(&file_path).expect("meta").len();
+
+        let mut git_cache = GitBranchCache::new(Duration::from_secs(30));
+        let mut parse_cache = SessionParseCache::default();
+        let mut parse = |parse_cache: &mut SessionParseCache| {
+            let meta = std::fs::metadata(&file_path).expect("metadata");
+            let modified = meta.modified().expect("modified");
+            parse_session_file_cached(
+                &file_path,
+                &meta,
+                modified,
+                &mut git_cache,
+                parse_cache,
+                &PricingConfig::default(),
+            )
+            .expect("parse");
+        };
+
+        parse(&mut parse_cache);
+        parse(&mut parse_cache);
+        let stats = parse_cache.stats();
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.reparses, 1);
+        assert_eq!(stats.cache_hits, 1);
+        assert_eq!(stats.bytes_read, initial_len);
+
+        let appended = "{\"timestamp\":\"2026-02-09T16:35:13Z\",\"type\":\"event_msg\",\"payload\":{\"type\":\"agent_reasoning\",\"text\":\"x\"}}\n";
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&file_path)
+            .expect("open append");
+        use std::io::Write as _;
+        file.write_all(appended.as_bytes()).expect("append");
+        drop(file);
+
+        parse_cache.begin_pass();
+        parse(&mut parse_cache);
+        let stats = parse_cache.stats();
+        assert_eq!(stats.cache_hits, 0);
+        assert_eq!(stats.reparses, 1);
+        assert_eq!(stats.bytes_read, appended.len() as u64);
+    }
+
     #[test]
     fn cached_parser_advances_cursor_with_appended_lines() {
         let tmp = TempDir::new().expect("temp dir");
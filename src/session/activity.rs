@@ -1,5 +1,5 @@
 use std::cmp::Reverse;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
@@ -22,7 +22,8 @@ use super::parser::{
     last_input_tokens_from_info, last_output_tokens_from_info, last_tokens_from_info, max_datetime,
     model_context_window_from_info, parse_utc_timestamp, str_at,
     total_cached_input_tokens_from_info, total_input_tokens_from_info,
-    total_output_tokens_from_info, total_tokens_from_info, turn_context_reasoning_effort,
+    total_output_tokens_from_info, total_reasoning_output_tokens_from_info, total_tokens_from_info,
+    turn_context_reasoning_effort,
 };
 use super::{
     CodexSessionSnapshot, GitBranchCache, ReasoningEffort, SessionActivityKind,
@@ -36,16 +37,22 @@ pub(super) struct SessionAccumulator {
     started_at: Option<DateTime<Utc>>,
     originator: Option<String>,
     source: Option<String>,
+    pid: Option<u32>,
     model: Option<String>,
     reasoning_effort: Option<ReasoningEffort>,
     approval_policy: Option<String>,
     sandbox_policy: Option<String>,
     pub(super) session_total_tokens: Option<u64>,
     previous_session_total_tokens: Option<u64>,
+    reported_session_total_tokens: Option<u64>,
+    resumed_token_offset: u64,
+    resume_pending: bool,
+    resume_count: u32,
     last_turn_tokens: Option<u64>,
     input_tokens_total: u64,
     cached_input_tokens_total: u64,
     output_tokens_total: u64,
+    reasoning_output_tokens_total: u64,
     last_input_tokens: Option<u64>,
     last_cached_input_tokens: Option<u64>,
     last_output_tokens: Option<u64>,
@@ -59,6 +66,7 @@ pub(super) struct SessionAccumulator {
     rate_limit_envelopes: HashMap<String, RateLimitEnvelope>,
     last_token_event_at: Option<DateTime<Utc>>,
     activity_tracker: ActivityTracker,
+    edit_counts: BTreeMap<String, u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -105,7 +113,9 @@ impl ActivityTracker {
         let idle_candidate_at = if self.pending_calls.is_empty()
             && !matches!(
                 kind,
-                SessionActivityKind::Idle | SessionActivityKind::WaitingInput
+                SessionActivityKind::Idle
+                    | SessionActivityKind::WaitingInput
+                    | SessionActivityKind::AwaitingApproval
             ) {
             last_active_at
         } else {
@@ -120,15 +130,24 @@ impl ActivityTracker {
             last_effective_signal_at: self.last_effective_signal_at,
             idle_candidate_at,
             pending_calls: self.pending_calls.len(),
+            tool: None,
         });
     }
 
+    fn tag_tool(&mut self, tool: Option<String>) {
+        if let Some(snapshot) = self.snapshot.as_mut() {
+            snapshot.tool = tool;
+        }
+    }
+
     fn note_commentary(&mut self, observed_at: Option<DateTime<Utc>>) {
         self.observe_effective_signal(observed_at);
         let should_promote = self.snapshot.as_ref().is_none_or(|snapshot| {
             matches!(
                 snapshot.kind,
-                SessionActivityKind::Idle | SessionActivityKind::WaitingInput
+                SessionActivityKind::Idle
+                    | SessionActivityKind::WaitingInput
+                    | SessionActivityKind::AwaitingApproval
             )
         });
         if should_promote {
@@ -170,10 +189,16 @@ impl ActivityTracker {
 
         if let Some(snapshot) = self.snapshot.as_mut() {
             snapshot.pending_calls = self.pending_calls.len();
+            if snapshot.kind == SessionActivityKind::AwaitingApproval {
+                snapshot.kind = SessionActivityKind::Thinking;
+                snapshot.target = None;
+            }
             if snapshot.pending_calls == 0
                 && !matches!(
                     snapshot.kind,
-                    SessionActivityKind::Idle | SessionActivityKind::WaitingInput
+                    SessionActivityKind::Idle
+                        | SessionActivityKind::WaitingInput
+                        | SessionActivityKind::AwaitingApproval
                 )
             {
                 snapshot.idle_candidate_at = snapshot.last_active_at.or(observed_at);
@@ -200,7 +225,9 @@ impl ActivityTracker {
 
         if matches!(
             snapshot.kind,
-            SessionActivityKind::Idle | SessionActivityKind::WaitingInput
+            SessionActivityKind::Idle
+                | SessionActivityKind::WaitingInput
+                | SessionActivityKind::AwaitingApproval
         ) {
             if matches!(snapshot.kind, SessionActivityKind::Idle) {
                 snapshot.target = None;
@@ -254,6 +281,8 @@ impl SessionAccumulator {
                         self.reset_for_new_session(incoming_session_id.clone());
                     } else if self.session_id.is_none() {
                         self.session_id = Some(incoming_session_id);
+                    } else if self.reported_session_total_tokens.is_some() {
+                        self.resume_pending = true;
                     }
                 }
                 let session_started = str_at(payload, &["timestamp"]).and_then(parse_utc_timestamp);
@@ -267,6 +296,13 @@ impl SessionAccumulator {
                 if self.source.is_none() {
                     self.source = str_at(payload, &["source"]);
                 }
+                if let Some(pid) = payload
+                    .get("pid")
+                    .and_then(Value::as_u64)
+                    .and_then(|pid| u32::try_from(pid).ok())
+                {
+                    self.pid = Some(pid);
+                }
             }
             Some("turn_context") => {
                 if self.cwd.is_none() {
@@ -320,6 +356,11 @@ impl SessionAccumulator {
                     if let Some(total_output_tokens) = total_output_tokens_from_info(payload) {
                         self.output_tokens_total = total_output_tokens;
                     }
+                    if let Some(total_reasoning_tokens) =
+                        total_reasoning_output_tokens_from_info(payload)
+                    {
+                        self.reasoning_output_tokens_total = total_reasoning_tokens;
+                    }
 
                     if let Some(last_input_tokens) = last_input_tokens_from_info(payload) {
                         self.last_input_tokens = Some(last_input_tokens);
@@ -346,9 +387,9 @@ impl SessionAccumulator {
                     }
 
                     if let Some(total_tokens) = total_tokens_from_info(payload) {
-                        self.session_total_tokens = Some(total_tokens);
+                        self.record_session_total(total_tokens);
                     } else if self.input_tokens_total > 0 || self.output_tokens_total > 0 {
-                        self.session_total_tokens = Some(
+                        self.record_session_total(
                             self.input_tokens_total
                                 .saturating_add(self.output_tokens_total),
                         );
@@ -397,6 +438,13 @@ impl SessionAccumulator {
                 Some("agent_message") => {
                     self.activity_tracker.note_commentary(event_timestamp);
                 }
+                Some("exec_approval_request") | Some("apply_patch_approval_request") => {
+                    self.activity_tracker.mark_activity(
+                        SessionActivityKind::AwaitingApproval,
+                        approval_request_target(payload),
+                        event_timestamp,
+                    );
+                }
                 _ => {}
             },
             Some("response_item") => match str_at(payload, &["type"]).as_deref() {
@@ -411,6 +459,12 @@ impl SessionAccumulator {
                     let name = str_at(payload, &["name"]).unwrap_or_default();
                     let arguments = str_at(payload, &["arguments"]).unwrap_or_default();
                     let classified = classify_function_call(&name, &arguments);
+                    if matches!(
+                        classified.kind,
+                        SessionActivityKind::EditingFile | SessionActivityKind::WritingTests
+                    ) {
+                        self.record_edits(classified.target.clone());
+                    }
                     self.activity_tracker.start_call(
                         str_at(payload, &["call_id"]),
                         classified,
@@ -421,11 +475,20 @@ impl SessionAccumulator {
                     let name = str_at(payload, &["name"]).unwrap_or_default();
                     let input = str_at(payload, &["input"]).unwrap_or_default();
                     let classified = classify_custom_tool_call(&name, &input);
+                    if name == "apply_patch" {
+                        self.record_edits(
+                            extract_patch_paths(&input)
+                                .map(|path| sanitize_file_target(path, 72))
+                                .collect::<BTreeSet<_>>(),
+                        );
+                    }
                     self.activity_tracker.start_call(
                         str_at(payload, &["call_id"]),
                         classified,
                         event_timestamp,
                     );
+                    self.activity_tracker
+                        .tag_tool(mcp_tool_parts(&name).map(|_| name.clone()));
                 }
                 Some("function_call_output") | Some("custom_tool_call_output") => {
                     self.activity_tracker
@@ -455,11 +518,35 @@ impl SessionAccumulator {
         }
     }
 
+    fn record_edits(&mut self, targets: impl IntoIterator<Item = String>) {
+        for target in targets {
+            let counter = self.edit_counts.entry(target).or_default();
+            *counter = counter.saturating_add(1);
+        }
+    }
+
     fn reset_for_new_session(&mut self, session_id: String) {
         *self = SessionAccumulator::default();
         self.session_id = Some(session_id);
     }
 
+    fn record_session_total(&mut self, reported_total: u64) {
+        if let Some(previous_reported) = self.reported_session_total_tokens
+            && reported_total < previous_reported
+            && (self.resume_pending || reported_total < previous_reported / 2)
+        {
+            self.resumed_token_offset = self.resumed_token_offset.saturating_add(previous_reported);
+            self.resume_count = self.resume_count.saturating_add(1);
+        }
+        self.resume_pending = false;
+        self.reported_session_total_tokens = Some(reported_total);
+        let running_total = self.resumed_token_offset.saturating_add(reported_total);
+        self.session_total_tokens = Some(
+            self.session_total_tokens
+                .map_or(running_total, |current| current.max(running_total)),
+        );
+    }
+
     fn update_model(&mut self, model: String, service_tier: Option<String>) {
         let normalized = canonical_model_key(&model);
         let current = self.model.as_deref().map(canonical_model_key);
@@ -539,18 +626,13 @@ impl SessionAccumulator {
         }
 
         let cwd = self.cwd.clone().unwrap_or_else(|| PathBuf::from("."));
-        let project_name = cwd
-            .file_name()
-            .and_then(|name| name.to_str())
-            .map(ToString::to_string)
-            .filter(|name| !name.is_empty())
-            .unwrap_or_else(|| "unknown-project".to_string());
+        let project_name = project_name_from_cwd(&cwd);
         let git_branch = git_cache.get(&cwd);
         let fallback_id = jsonl_path
             .file_stem()
             .and_then(|s| s.to_str())
-            .unwrap_or("unknown-session")
-            .to_string();
+            .map(session_id_from_file_stem)
+            .unwrap_or_else(|| "unknown-session".to_string());
         let mut cost = cost::compute_cost(
             if self.pricing_is_mixed_model {
                 ""
@@ -613,9 +695,11 @@ impl SessionAccumulator {
             session_total_tokens: self.session_total_tokens,
             last_turn_tokens: self.last_turn_tokens,
             session_delta_tokens,
+            resume_count: self.resume_count,
             input_tokens_total: self.input_tokens_total,
             cached_input_tokens_total: self.cached_input_tokens_total,
             output_tokens_total: self.output_tokens_total,
+            reasoning_output_tokens_total: self.reasoning_output_tokens_total,
             last_input_tokens: self.last_input_tokens,
             last_cached_input_tokens: self.last_cached_input_tokens,
             last_output_tokens: self.last_output_tokens,
@@ -634,10 +718,60 @@ impl SessionAccumulator {
             last_token_event_at: self.last_token_event_at,
             last_activity,
             source_file: jsonl_path.to_path_buf(),
+            pid: self.pid,
+            edit_counts: self.edit_counts.clone(),
         })
     }
 }
 
+const SESSION_FILE_PREFIXES: [&str; 2] = ["rollout-", "session-"];
+
+pub(super) fn session_id_from_file_stem(stem: &str) -> String {
+    let rest = SESSION_FILE_PREFIXES
+        .iter()
+        .find_map(|prefix| stem.strip_prefix(prefix))
+        .unwrap_or(stem);
+    if let Some(uuid) = rest
+        .len()
+        .checked_sub(36)
+        .and_then(|start| rest.get(start..))
+        .filter(|tail| is_uuid(tail))
+    {
+        return uuid.to_string();
+    }
+    let segments: Vec<&str> = rest.split('-').collect();
+    let dated = segments
+        .iter()
+        .take(6)
+        .take_while(|segment| is_timestamp_segment(segment))
+        .count();
+    let cleaned = if dated >= 3 {
+        segments[dated..].join("-")
+    } else {
+        rest.to_string()
+    };
+    if cleaned.is_empty() {
+        stem.to_string()
+    } else {
+        cleaned
+    }
+}
+
+fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, len)| {
+            group.len() == len && group.chars().all(|ch| ch.is_ascii_hexdigit())
+        })
+}
+
+fn is_timestamp_segment(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment.len() <= 5
+        && segment.chars().all(|ch| ch.is_ascii_digit() || ch == 'T')
+        && segment.chars().next().is_some_and(|ch| ch.is_ascii_digit())
+}
+
 fn speed_from_signal(model_id: &str, service_tier: Option<&str>) -> Option<SessionSpeed> {
     let model = resolve_model(model_id);
     if let Some(tier) = service_tier.map(str::trim).map(str::to_ascii_lowercase) {
@@ -669,6 +803,13 @@ fn classify_shell_command(arguments: &str) -> PendingActivity {
         };
     }
 
+    if let Some(path) = extract_in_place_edit_target(&command) {
+        return PendingActivity {
+            kind: SessionActivityKind::EditingFile,
+            target: Some(path),
+        };
+    }
+
     if let Some(path) = extract_read_target(&command) {
         return PendingActivity {
             kind: SessionActivityKind::ReadingFile,
@@ -676,12 +817,35 @@ fn classify_shell_command(arguments: &str) -> PendingActivity {
         };
     }
 
+    if let Some(target) = extract_archive_target(&command) {
+        return PendingActivity {
+            kind: SessionActivityKind::Archiving,
+            target,
+        };
+    }
+
     PendingActivity {
         kind: SessionActivityKind::RunningCommand,
         target: Some(summarize_command_for_presence(&command, 72)),
     }
 }
 
+fn approval_request_target(payload: &Value) -> Option<String> {
+    let command = match payload.get("command") {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" "),
+        Some(Value::String(command)) => command.clone(),
+        _ => return None,
+    };
+    if command.trim().is_empty() {
+        return None;
+    }
+    Some(summarize_command_for_presence(&command, 72))
+}
+
 fn classify_function_call(name: &str, arguments: &str) -> PendingActivity {
     match name {
         "shell_command" | "exec_command" => classify_shell_command(arguments),
@@ -703,16 +867,28 @@ fn classify_function_call(name: &str, arguments: &str) -> PendingActivity {
 fn classify_custom_tool_call(name: &str, input: &str) -> PendingActivity {
     match name {
         "apply_patch" => PendingActivity {
-            kind: SessionActivityKind::EditingFile,
+            kind: if extract_patch_path(input).is_some_and(is_test_path) {
+                SessionActivityKind::WritingTests
+            } else {
+                SessionActivityKind::EditingFile
+            },
             target: extract_patch_target(input),
         },
         _ => PendingActivity {
             kind: SessionActivityKind::RunningCommand,
-            target: None,
+            target: mcp_tool_parts(name).map(|(server, tool)| format!("{tool} ({server})")),
         },
     }
 }
 
+pub(crate) fn mcp_tool_parts(name: &str) -> Option<(&str, &str)> {
+    let (server, tool) = name.trim().split_once('.')?;
+    if server.is_empty() || tool.is_empty() {
+        return None;
+    }
+    Some((server, tool))
+}
+
 fn web_search_target(payload: &Value) -> Option<String> {
     let _ = payload;
     Some("web search".to_string())
@@ -811,6 +987,11 @@ fn extract_read_target(command: &str) -> Option<String> {
         "type ",
         "rg ",
         "rg --files ",
+        "grep ",
+        "egrep ",
+        "fgrep ",
+        "awk ",
+        "sed ",
         "Select-String ",
         "Get-ChildItem ",
     ];
@@ -834,6 +1015,17 @@ fn extract_read_target(command: &str) -> Option<String> {
         return extract_rg_target(command);
     }
 
+    if ["grep ", "egrep ", "fgrep "]
+        .iter()
+        .any(|prefix| command.starts_with(prefix))
+    {
+        return extract_grep_target(command);
+    }
+
+    if command.starts_with("awk ") || command.starts_with("sed ") {
+        return extract_script_file_target(command);
+    }
+
     if command.starts_with("Select-String ") {
         if let Some(path_target) = named_argument(command, "-Path") {
             return Some(path_target);
@@ -851,6 +1043,44 @@ fn extract_read_target(command: &str) -> Option<String> {
     None
 }
 
+fn extract_archive_target(command: &str) -> Option<Option<String>> {
+    let tokens: Vec<String> = command
+        .split_whitespace()
+        .map(clean_shell_token)
+        .filter(|token| !token.is_empty())
+        .collect();
+    let verb = command_verb(tokens.first()?);
+    if !matches!(verb.as_str(), "tar" | "zip" | "unzip" | "gzip") {
+        return None;
+    }
+
+    let args = &tokens[1..];
+    let target = if verb == "tar" {
+        tar_archive_argument(args)
+    } else {
+        args.iter().find(|token| !token.starts_with('-')).cloned()
+    };
+    Some(target.map(|value| sanitize_file_target(&value, 72)))
+}
+
+fn tar_archive_argument(args: &[String]) -> Option<String> {
+    for (idx, token) in args.iter().enumerate() {
+        if let Some(value) = token.strip_prefix("--file=") {
+            return Some(value.to_string());
+        }
+        let bundled = token.strip_prefix('-').unwrap_or(token);
+        let is_flag_bundle = token == "--file"
+            || (!token.starts_with("--")
+                && (idx == 0 || token.starts_with('-'))
+                && bundled.ends_with('f')
+                && bundled.chars().all(|ch| ch.is_ascii_alphabetic()));
+        if is_flag_bundle {
+            return args.get(idx + 1).cloned();
+        }
+    }
+    args.iter().find(|token| !token.starts_with('-')).cloned()
+}
+
 fn positional_argument_after(command: &str, prefix: &str) -> Option<String> {
     let rest = command.strip_prefix(prefix)?.trim();
     for token in rest.split_whitespace() {
@@ -965,22 +1195,211 @@ fn extract_rg_target(command: &str) -> Option<String> {
         .map(|target| sanitize_file_target(target, 72))
 }
 
-fn extract_patch_target(input: &str) -> Option<String> {
-    for line in input.lines() {
-        if let Some(path) = line.strip_prefix("*** Update File: ") {
-            return Some(sanitize_file_target(path.trim(), 72));
+fn extract_grep_target(command: &str) -> Option<String> {
+    let mut positional = Vec::new();
+    let mut pattern_given = false;
+    let mut skip_next = false;
+    for token in shell_words(command).into_iter().skip(1) {
+        if skip_next {
+            skip_next = false;
+            continue;
+        }
+        if token.starts_with("--") {
+            if token == "--regexp" || token == "--file" {
+                pattern_given = true;
+                skip_next = true;
+            } else if token.starts_with("--regexp=") || token.starts_with("--file=") {
+                pattern_given = true;
+            } else if token == "--max-count" || token == "--context" {
+                skip_next = true;
+            }
+            continue;
+        }
+        if token.starts_with('-') {
+            if token == "-e" || token == "-f" {
+                pattern_given = true;
+                skip_next = true;
+            } else if token == "-m" || token == "-A" || token == "-B" || token == "-C" {
+                skip_next = true;
+            }
+            continue;
+        }
+        positional.push(token);
+    }
+
+    let files = if pattern_given {
+        &positional[..]
+    } else {
+        positional.get(1..).unwrap_or_default()
+    };
+    files
+        .last()
+        .filter(|target| !target.is_empty())
+        .map(|target| sanitize_file_target(target, 72))
+}
+
+fn extract_in_place_edit_target(command: &str) -> Option<String> {
+    let command = command.trim();
+    if !command.starts_with("sed ") {
+        return None;
+    }
+    shell_words(command)
+        .iter()
+        .skip(1)
+        .any(|token| token.starts_with("-i") || token.starts_with("--in-place"))
+        .then(|| extract_script_file_target(command))
+        .flatten()
+}
+
+fn extract_script_file_target(command: &str) -> Option<String> {
+    let mut tokens = shell_words(command).into_iter();
+    let verb = tokens.next()?;
+    let mut positional = Vec::new();
+    let mut script_given = false;
+    let mut skip_next = false;
+    for token in tokens {
+        if skip_next {
+            skip_next = false;
+            continue;
         }
-        if let Some(path) = line.strip_prefix("*** Add File: ") {
-            return Some(sanitize_file_target(path.trim(), 72));
+        if token.is_empty() {
+            continue;
         }
-        if let Some(path) = line.strip_prefix("*** Delete File: ") {
-            return Some(sanitize_file_target(path.trim(), 72));
+        if !token.starts_with('-') || token == "-" {
+            positional.push(token);
+            continue;
         }
-        if let Some(path) = line.strip_prefix("*** Move to: ") {
-            return Some(sanitize_file_target(path.trim(), 72));
+        match (verb.as_str(), token.as_str()) {
+            ("awk", "-f") | ("sed", "-e" | "-f" | "--expression" | "--file") => {
+                script_given = true;
+                skip_next = true;
+            }
+            ("awk", "-v" | "-F") => skip_next = true,
+            ("sed", flag) if flag.starts_with("--expression=") || flag.starts_with("--file=") => {
+                script_given = true;
+            }
+            _ => {}
         }
     }
-    None
+
+    let files = if script_given {
+        &positional[..]
+    } else {
+        positional.get(1..).unwrap_or_default()
+    };
+    files
+        .last()
+        .filter(|target| *target != "-")
+        .map(|target| sanitize_file_target(target, 72))
+}
+
+fn shell_words(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut in_word = false;
+    for ch in command.chars() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => current.push(ch),
+            None if ch == '\'' || ch == '"' => {
+                quote = Some(ch);
+                in_word = true;
+            }
+            None if ch.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            None => {
+                current.push(ch);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+fn extract_patch_target(input: &str) -> Option<String> {
+    extract_patch_path(input).map(|path| sanitize_file_target(path, 72))
+}
+
+fn extract_patch_path(input: &str) -> Option<&str> {
+    extract_patch_paths(input).next()
+}
+
+fn extract_patch_paths(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().filter_map(|line| {
+        [
+            "*** Update File: ",
+            "*** Add File: ",
+            "*** Delete File: ",
+            "*** Move to: ",
+        ]
+        .iter()
+        .find_map(|prefix| line.strip_prefix(prefix))
+        .map(str::trim)
+    })
+}
+
+fn is_test_path(path: &str) -> bool {
+    let normalized = path.replace('\\', "/").to_ascii_lowercase();
+    let in_test_dir = normalized
+        .split('/')
+        .rev()
+        .skip(1)
+        .any(|dir| matches!(dir, "tests" | "test" | "__tests__"));
+    let file_name = portable_basename(&normalized);
+    in_test_dir
+        || file_name.starts_with("test_")
+        || [
+            "_test.rs",
+            "_test.go",
+            "_test.py",
+            ".spec.ts",
+            ".spec.tsx",
+            ".spec.js",
+            ".test.ts",
+            ".test.tsx",
+            ".test.js",
+        ]
+        .iter()
+        .any(|suffix| file_name.ends_with(suffix))
+}
+
+pub(crate) fn language_for_file(name: &str) -> &'static str {
+    let file_name = portable_basename(name.trim()).to_ascii_lowercase();
+    let Some((_, extension)) = file_name.rsplit_once('.') else {
+        return "Other";
+    };
+    match extension {
+        "rs" => "Rust",
+        "py" | "pyi" => "Python",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "swift" => "Swift",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "C++",
+        "cs" => "C#",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "sh" | "bash" | "zsh" | "ps1" => "Shell",
+        "sql" => "SQL",
+        "html" | "htm" => "HTML",
+        "css" | "scss" | "sass" => "CSS",
+        "md" | "mdx" => "Markdown",
+        "json" | "jsonl" => "JSON",
+        "toml" => "TOML",
+        "yaml" | "yml" => "YAML",
+        _ => "Other",
+    }
 }
 
 fn truncate_activity_target(input: String, max_len: usize) -> String {
@@ -1005,6 +1424,15 @@ pub(crate) fn sanitize_file_target(raw: &str, max_len: usize) -> String {
     truncate_activity_target(cleaned.to_string(), max_len)
 }
 
+fn project_name_from_cwd(cwd: &Path) -> String {
+    let raw = cwd.to_string_lossy();
+    let name = portable_basename(raw.trim_end_matches(['/', '\\']));
+    if name.is_empty() || name == "." || name == ".." {
+        return "unknown-project".to_string();
+    }
+    name.to_string()
+}
+
 fn portable_basename(raw: &str) -> &str {
     raw.rsplit(['/', '\\']).next().unwrap_or(raw)
 }
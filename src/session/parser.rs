@@ -1,7 +1,7 @@
 use std::fs::File;
 use std::io::{BufRead, BufReader, Seek, SeekFrom};
 use std::path::Path;
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -9,6 +9,7 @@ use serde_json::Value;
 
 use crate::config::PricingConfig;
 use crate::model;
+use crate::util::{CommandStdout, command_stdout_until};
 
 use super::activity::SessionAccumulator;
 use super::{
@@ -16,6 +17,8 @@ use super::{
     ReasoningEffort, SessionParseCache,
 };
 
+const GIT_WAIT_POLL: Duration = Duration::from_millis(10);
+
 #[cfg(test)]
 pub(super) fn parse_session_file(
     jsonl_path: &Path,
@@ -57,8 +60,10 @@ pub(super) fn parse_session_file_cached(
         && cached.modified == modified
         && let Some(snapshot) = cached.snapshot.clone()
     {
+        parse_cache.pass_stats.cache_hits = parse_cache.pass_stats.cache_hits.saturating_add(1);
         return Ok(Some(snapshot));
     }
+    let start_cursor = cached.cursor;
 
     let mut file = File::open(jsonl_path)
         .with_context(|| format!("failed to open session file {}", jsonl_path.display()))?;
@@ -73,12 +78,16 @@ pub(super) fn parse_session_file_cached(
     cached.cursor = reader.stream_position().unwrap_or(file_len);
     cached.file_len = file_len;
     cached.modified = modified;
+    let bytes_read = cached.cursor.saturating_sub(start_cursor);
 
     let snapshot =
         cached
             .accumulator
             .build_snapshot(jsonl_path, last_activity, git_cache, pricing_config);
     cached.snapshot = snapshot.clone();
+    parse_cache.pass_stats.reparses = parse_cache.pass_stats.reparses.saturating_add(1);
+    parse_cache.pass_stats.bytes_read =
+        parse_cache.pass_stats.bytes_read.saturating_add(bytes_read);
     Ok(snapshot)
 }
 
@@ -154,6 +163,13 @@ pub(super) fn total_output_tokens_from_info(payload: &Value) -> Option<u64> {
     uint_at(payload, &["info", "total_token_usage", "output_tokens"])
 }
 
+pub(super) fn total_reasoning_output_tokens_from_info(payload: &Value) -> Option<u64> {
+    uint_at(
+        payload,
+        &["info", "total_token_usage", "reasoning_output_tokens"],
+    )
+}
+
 pub(super) fn model_context_window_from_info(payload: &Value) -> Option<u64> {
     uint_at(payload, &["info", "model_context_window"])
 }
@@ -244,37 +260,39 @@ pub(super) fn parse_utc_timestamp(text: String) -> Option<DateTime<Utc>> {
         .ok()
 }
 
-pub(super) fn fetch_git_branch(project_path: &Path) -> Option<String> {
-    let output = crate::util::silent_command("git")
-        .arg("-C")
-        .arg(project_path)
-        .arg("rev-parse")
-        .arg("--abbrev-ref")
-        .arg("HEAD")
-        .output()
-        .ok()?;
-
-    if !output.status.success() {
-        return None;
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum GitBranchLookup {
+    Resolved(Option<String>),
+    TimedOut,
+}
 
-    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+pub(super) fn fetch_git_branch(project_path: &Path, timeout: Duration) -> GitBranchLookup {
+    let deadline = Instant::now() + timeout;
+    let branch = match git_stdout(
+        project_path,
+        &["rev-parse", "--abbrev-ref", "HEAD"],
+        deadline,
+    ) {
+        GitBranchLookup::Resolved(Some(branch)) => branch,
+        other => return other,
+    };
     if branch == "HEAD" {
-        let output = crate::util::silent_command("git")
-            .arg("-C")
-            .arg(project_path)
-            .arg("rev-parse")
-            .arg("--short")
-            .arg("HEAD")
-            .output()
-            .ok()?;
-        if !output.status.success() {
-            return None;
+        return git_stdout(project_path, &["rev-parse", "--short", "HEAD"], deadline);
+    }
+    GitBranchLookup::Resolved(Some(branch))
+}
+
+fn git_stdout(project_path: &Path, args: &[&str], deadline: Instant) -> GitBranchLookup {
+    let mut command = crate::util::silent_command("git");
+    command.arg("-C").arg(project_path).args(args);
+    match command_stdout_until(command, deadline, GIT_WAIT_POLL) {
+        CommandStdout::Success(stdout) => {
+            let text = String::from_utf8_lossy(&stdout).trim().to_string();
+            GitBranchLookup::Resolved((!text.is_empty()).then_some(text))
         }
-        let short = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        return (!short.is_empty()).then_some(short);
+        CommandStdout::Failed => GitBranchLookup::Resolved(None),
+        CommandStdout::TimedOut => GitBranchLookup::TimedOut,
     }
-    (!branch.is_empty()).then_some(branch)
 }
 
 pub(super) fn str_at(value: &Value, path: &[&str]) -> Option<String> {
@@ -66,7 +66,16 @@ impl DetectedPlanTier {
     }
 
     pub fn label(self, show_price: bool) -> String {
-        if show_price && let Some(monthly) = self.monthly_price_usd() {
+        self.label_with_price(show_price, None)
+    }
+
+    pub fn label_with_price(self, show_price: bool, custom_price_usd: Option<u32>) -> String {
+        let price = if self == Self::Unknown {
+            None
+        } else {
+            custom_price_usd.or(self.monthly_price_usd())
+        };
+        if show_price && let Some(monthly) = price {
             return format!("{} (${monthly}/month)", self.title());
         }
         self.title().to_string()
@@ -134,6 +143,14 @@ impl ResolvedPlan {
         self.tier.label(show_price)
     }
 
+    pub fn display_label(&self, plan: &OpenAiPlanDisplayConfig) -> String {
+        let custom_price_usd = plan
+            .custom_price_usd
+            .filter(|_| self.tier == DetectedPlanTier::from(plan.tier));
+        self.tier
+            .label_with_price(plan.show_price, custom_price_usd)
+    }
+
     pub fn status_label(&self) -> String {
         match self.source {
             DetectedPlanSource::Manual => format!("{} (manual)", self.tier.title()),
@@ -412,9 +429,11 @@ mod tests {
             session_total_tokens: Some(1),
             last_turn_tokens: Some(1),
             session_delta_tokens: Some(1),
+            resume_count: 0,
             input_tokens_total: 1,
             cached_input_tokens_total: 0,
             output_tokens_total: 0,
+            reasoning_output_tokens_total: 0,
             last_input_tokens: Some(1),
             last_cached_input_tokens: Some(0),
             last_output_tokens: Some(0),
@@ -465,6 +484,8 @@ mod tests {
             last_token_event_at: None,
             last_activity: SystemTime::now(),
             source_file: PathBuf::from("s.jsonl"),
+            pid: None,
+            edit_counts: Default::default(),
         }
     }
 
@@ -531,6 +552,7 @@ mod tests {
                 mode: OpenAiPlanMode::Manual,
                 tier: OpenAiPlanTier::Plus,
                 show_price: false,
+                custom_price_usd: None,
             },
         );
         assert_eq!(resolved.tier, DetectedPlanTier::Plus);
@@ -547,6 +569,7 @@ mod tests {
                 mode: OpenAiPlanMode::Manual,
                 tier: OpenAiPlanTier::Pro5x,
                 show_price: true,
+                custom_price_usd: None,
             },
         );
 
@@ -555,6 +578,45 @@ mod tests {
         assert_eq!(resolved.status_label(), "Pro 5x (manual)");
     }
 
+    #[test]
+    fn custom_plan_price_overrides_builtin_price_and_keeps_tier_name() {
+        let plan = OpenAiPlanDisplayConfig {
+            mode: OpenAiPlanMode::Manual,
+            tier: OpenAiPlanTier::Plus,
+            show_price: true,
+            custom_price_usd: Some(23),
+        };
+        let resolved = PlanDetector::new().resolve_from_sessions(&[], &plan);
+
+        assert_eq!(resolved.display_label(&plan), "Plus ($23/month)");
+        assert_eq!(plan.label(), "Plus ($23/month)");
+        assert_eq!(
+            resolved.display_label(&OpenAiPlanDisplayConfig {
+                show_price: false,
+                ..plan.clone()
+            }),
+            "Plus"
+        );
+        assert_eq!(ResolvedPlan::default().display_label(&plan), "Unknown");
+    }
+
+    #[test]
+    fn custom_plan_price_is_ignored_when_detected_tier_differs_from_configured_tier() {
+        let plan = OpenAiPlanDisplayConfig {
+            mode: OpenAiPlanMode::Auto,
+            tier: OpenAiPlanTier::Plus,
+            show_price: true,
+            custom_price_usd: Some(23),
+        };
+        let detected = ResolvedPlan {
+            tier: DetectedPlanTier::Pro20x,
+            source: DetectedPlanSource::Telemetry,
+            ..ResolvedPlan::default()
+        };
+
+        assert_eq!(detected.display_label(&plan), "Pro 20x ($200/month)");
+    }
+
     #[test]
     fn spark_is_pro_only() {
         assert!(is_model_allowed_for_plan(
@@ -1,20 +1,36 @@
+use std::collections::BTreeMap;
 use std::fmt::Write as _;
-use std::sync::{Mutex, OnceLock};
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once, OnceLock};
 use std::time::Duration;
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Sparkline, Wrap};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Sparkline, Wrap};
+use serde::{Deserialize, Serialize};
 
-use codex_presence_core::{CreditBalance, PresenceLayoutConfig, format_window_label};
+use codex_presence_core::{
+    CreditBalance, EffectiveLimitSelection, PresenceLayoutConfig, format_window_label,
+};
 
-use crate::config::{PlanPreset, PrivacyConfig, PrivacyField, TerminalLogoMode, plan_presets};
-use crate::cost::format_presentable_cost;
-use crate::metrics::{MetricsSnapshot, format_metrics_cost};
+use crate::config::{
+    self, BarChars, BarStyle, PlanPreset, PricingConfig, PrivacyConfig, PrivacyField, RecentSort,
+    TerminalLogoMode, UiTheme, plan_presets,
+};
+use crate::cost::{ModelPricing, PricingSource, format_presentable_cost_in, resolve_model_pricing};
+use crate::metrics::{BudgetStatus, MetricsSnapshot, format_metrics_cost_in, top_models};
 use crate::model::format_model_display;
-use crate::session::{CodexSessionSnapshot, RateLimits, SpeedMode, UsageWindow};
+use crate::session::{
+    CodexSessionSnapshot, RateLimits, SessionActivityKind, SessionActivitySnapshot, SpeedMode,
+    UsageWindow, pinned_presence_session,
+};
 use crate::util::{
-    format_cost, format_time_until, format_token_triplet, format_tokens, human_duration, truncate,
+    expand_template, format_cost_in, format_since_at, format_time_until, format_token_triplet,
+    format_tokens, human_duration, progress_bar_styled, truncate, write_json_pretty_atomic,
 };
 
 const FOOTER_ROWS: u16 = 1;
@@ -75,7 +91,11 @@ pub struct RenderData<'a> {
     pub show_activity: bool,
     pub show_activity_target: bool,
     pub presence_enabled: bool,
+    pub updates_frozen: bool,
+    pub help_open: bool,
+    pub show_all_sessions: bool,
     pub privacy: &'a PrivacyConfig,
+    pub pricing: &'a PricingConfig,
     pub presence_layout: &'a PresenceLayoutConfig,
     pub plan_display_label: &'a str,
     pub plan_status_label: &'a str,
@@ -83,9 +103,11 @@ pub struct RenderData<'a> {
     pub fast_active: bool,
     pub limits_source_label: &'a str,
     pub limits_updated_label: &'a str,
+    pub limits_source: Option<&'a EffectiveLimitSelection>,
     pub spark_plan_warning: Option<&'a str>,
     pub logo_mode: TerminalLogoMode,
     pub logo_path: Option<&'a str>,
+    pub banner_lines: Option<&'a [String]>,
     pub desktop_design_label: &'a str,
     pub banner_phase: u8,
     pub active: Option<&'a CodexSessionSnapshot>,
@@ -93,6 +115,25 @@ pub struct RenderData<'a> {
     pub effective_credits: Option<&'a CreditBalance>,
     pub metrics: Option<&'a MetricsSnapshot>,
     pub sessions: &'a [CodexSessionSnapshot],
+    pub idle_sessions: &'a [CodexSessionSnapshot],
+    pub recent_active_marker: &'a str,
+    pub recent_line_template: &'a str,
+    pub max_models_shown: usize,
+    pub show_aggregate_cost: bool,
+    pub activity_emoji: bool,
+    pub show_model_rates: bool,
+    pub min_bar_width: u16,
+    pub recent_cursor: Option<usize>,
+    pub pinned_session_id: Option<&'a str>,
+    pub unread_waiting: &'a [String],
+    pub token_history: &'a BTreeMap<String, Vec<u64>>,
+    pub no_color: bool,
+    pub theme: UiTheme,
+    pub recent_sort: RecentSort,
+    pub bar_style: BarStyle,
+    pub bar_chars: BarChars,
+    pub recent_filter: &'a str,
+    pub recent_filter_open: bool,
     pub plan_picker: Option<PlanPickerView>,
     pub privacy_picker: Option<PrivacyPickerView>,
 }
@@ -109,24 +150,40 @@ pub struct PrivacyPickerView {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum FooterMode {
+enum FooterMode<'a> {
     Normal,
     PlanPicker,
     PrivacyPicker,
+    RecentFilter(&'a str),
 }
 
 type UiTerminal = ratatui::DefaultTerminal;
 
 static TERMINAL: OnceLock<Mutex<Option<UiTerminal>>> = OnceLock::new();
+static ITERM_BADGE_SHOWN: AtomicBool = AtomicBool::new(false);
+static ITERM_BADGE_PANIC_HOOK: Once = Once::new();
 
-pub fn enter_terminal() -> Result<()> {
+pub fn enter_terminal(mouse: bool) -> Result<()> {
     let terminal = ratatui::init();
+    ITERM_BADGE_PANIC_HOOK.call_once(|| {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if ITERM_BADGE_SHOWN.swap(false, Ordering::Relaxed) {
+                write_iterm_badge("");
+            }
+            hook(info);
+        }));
+    });
     *terminal_cell().lock().expect("terminal lock") = Some(terminal);
+    if mouse {
+        crossterm::execute!(io::stdout(), EnableMouseCapture)?;
+    }
     Ok(())
 }
 
 pub fn leave_terminal() -> Result<()> {
     *terminal_cell().lock().expect("terminal lock") = None;
+    let _ = crossterm::execute!(io::stdout(), DisableMouseCapture);
     ratatui::restore();
     Ok(())
 }
@@ -137,7 +194,10 @@ pub fn draw(data: &RenderData<'_>) -> Result<()> {
         *guard = Some(ratatui::init());
     }
     if let Some(terminal) = guard.as_mut() {
-        terminal.draw(|frame| render_frame(frame, data))?;
+        terminal.draw(|frame| {
+            render_frame(frame, data);
+            apply_render_styling(frame.buffer_mut(), data);
+        })?;
     }
     Ok(())
 }
@@ -195,7 +255,7 @@ fn render_frame(frame: &mut Frame<'_>, data: &RenderData<'_>) {
                 Layout::vertical([Constraint::Length(9), Constraint::Min(8)]).split(columns[0]);
             let right =
                 Layout::vertical([Constraint::Length(9), Constraint::Min(8)]).split(columns[1]);
-            render_active(frame, left[0], data);
+            render_active(frame, left[0], layout, data);
             render_usage(frame, left[1], data);
             render_metrics(frame, right[0], data);
             render_recent(frame, right[1], layout, data);
@@ -207,23 +267,84 @@ fn render_frame(frame: &mut Frame<'_>, data: &RenderData<'_>) {
                 Constraint::Min(5),
             ])
             .split(body[1]);
-            render_active(frame, rows[0], data);
+            render_active(frame, rows[0], layout, data);
             render_usage(frame, rows[1], data);
             render_recent(frame, rows[2], layout, data);
         }
         UiLayoutMode::Minimal => {
             let rows = Layout::vertical([Constraint::Length(5), Constraint::Min(3)]).split(body[1]);
-            render_active(frame, rows[0], data);
+            render_active(frame, rows[0], layout, data);
             render_recent(frame, rows[1], layout, data);
         }
     }
 
+    let footer_mode = if data.recent_filter_open {
+        FooterMode::RecentFilter(data.recent_filter)
+    } else {
+        FooterMode::Normal
+    };
     render_footer(
         frame,
         root[1],
-        FooterMode::Normal,
+        footer_mode,
         data.desktop_design_label,
         data.presence_enabled,
+        data.updates_frozen,
+        data.privacy.enabled,
+    );
+    if data.help_open {
+        render_help_overlay(frame, root[0], layout);
+    }
+}
+
+const HELP_KEYS: [(&str, &str); 13] = [
+    ("↑/↓ PgUp/PgDn", "Move the recent-session cursor"),
+    ("Enter", "Pin or unpin the highlighted session"),
+    ("M", "Pause or resume Discord presence"),
+    ("F", "Freeze or resume presence updates"),
+    ("H", "Toggle privacy mode for this run"),
+    ("T", "Show or hide activity targets for this run"),
+    ("L", "Cycle the plan label for this run"),
+    ("V", "Open the presence composer"),
+    ("P", "Open the plan picker"),
+    ("D", "Switch the desktop design"),
+    ("/", "Filter recent sessions by project or branch"),
+    ("?", "Show or hide this help"),
+    ("q / Ctrl+C", "Quit"),
+];
+
+fn help_line(key: &str, description: &str, key_width: usize, width: usize) -> String {
+    let padding = key_width.saturating_sub(key.chars().count());
+    let line = format!("{key}{}  {description}", " ".repeat(padding));
+    truncate(&line, width)
+}
+
+fn render_help_overlay(frame: &mut Frame<'_>, area: Rect, layout: UiLayoutMode) {
+    let panel_area = match layout {
+        UiLayoutMode::Minimal => area,
+        UiLayoutMode::Full | UiLayoutMode::Compact => centered_rect(
+            area.width.min(64),
+            area.height.min(HELP_KEYS.len() as u16 + 3),
+            area,
+        ),
+    };
+    let key_width = HELP_KEYS
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0);
+    let inner_width = panel_area.width.saturating_sub(2) as usize;
+    let items: Vec<ListItem<'_>> = HELP_KEYS
+        .iter()
+        .map(|(key, description)| {
+            ListItem::new(help_line(key, description, key_width, inner_width))
+                .style(Style::default().fg(theme::TEXT))
+        })
+        .collect();
+    frame.render_widget(Clear, panel_area);
+    frame.render_widget(
+        List::new(items).block(panel("Keys · any key closes", Some(theme::TEXT))),
+        panel_area,
     );
 }
 
@@ -250,11 +371,11 @@ fn render_header(frame: &mut Frame<'_>, area: Rect, layout: UiLayoutMode, data:
     );
     match variant {
         BannerVariant::AsciiDual => {
-            for line in CODEX_ASCII
-                .iter()
+            for line in banner_rows(data.banner_lines, area.width.saturating_sub(2))
+                .into_iter()
                 .take(area.height.saturating_sub(3) as usize)
             {
-                lines.push(Line::from(Span::styled(*line, theme::title())));
+                lines.push(Line::from(Span::styled(line, theme::title())));
             }
             lines.push(Line::from(Span::styled(
                 header_subtitle(layout),
@@ -292,6 +413,142 @@ fn render_header(frame: &mut Frame<'_>, area: Rect, layout: UiLayoutMode, data:
     );
 }
 
+fn banner_rows(custom: Option<&[String]>, width: u16) -> Vec<&str> {
+    match custom {
+        Some(lines)
+            if !lines.is_empty()
+                && lines
+                    .iter()
+                    .all(|line| line.chars().count() <= usize::from(width)) =>
+        {
+            lines.iter().map(String::as_str).collect()
+        }
+        _ => CODEX_ASCII.to_vec(),
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BannerFile {
+    path: Option<String>,
+    lines: Option<Vec<String>>,
+}
+
+impl BannerFile {
+    pub fn lines_for(&mut self, path: Option<&str>) -> Option<&[String]> {
+        if self.path.as_deref() != path {
+            self.path = path.map(str::to_string);
+            self.lines = path.and_then(load_banner_lines);
+        }
+        self.lines.as_deref()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ITermBadge {
+    supported: bool,
+    shown: String,
+}
+
+impl ITermBadge {
+    pub fn detect() -> Self {
+        Self {
+            supported: cfg!(target_os = "macos")
+                && std::env::var("TERM_PROGRAM").is_ok_and(|value| value == "iTerm.app"),
+            shown: String::new(),
+        }
+    }
+
+    pub fn sync(&mut self, enabled: bool, text: &str) {
+        let target = if enabled { text } else { "" };
+        if self.supported && self.shown != target {
+            self.write(target);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        if self.supported && !self.shown.is_empty() {
+            self.write("");
+        }
+    }
+
+    fn write(&mut self, text: &str) {
+        write_iterm_badge(text);
+        ITERM_BADGE_SHOWN.store(!text.is_empty(), Ordering::Relaxed);
+        self.shown = text.to_string();
+    }
+}
+
+fn write_iterm_badge(text: &str) {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(iterm_badge_escape(text).as_bytes());
+    let _ = stdout.flush();
+}
+
+pub fn iterm_badge_escape(text: &str) -> String {
+    format!(
+        "\x1b]1337;SetBadgeFormat={}\x07",
+        base64_encode(text.replace('\\', "\\\\").as_bytes())
+    )
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = chunk.iter().enumerate().fold(0_u32, |acc, (index, byte)| {
+            acc | u32::from(*byte) << (16 - 8 * index)
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(ALPHABET[(triple >> (18 - 6 * index) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    pub recent_cursor: Option<usize>,
+    pub pinned_session_id: Option<String>,
+}
+
+impl UiState {
+    pub fn path() -> PathBuf {
+        config::profile_scoped_path(
+            &config::presence_config_home(),
+            "discord-presence-ui-state",
+            "json",
+        )
+    }
+
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        write_json_pretty_atomic(path, self)
+    }
+}
+
+fn load_banner_lines(path: &str) -> Option<Vec<String>> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let mut lines = text
+        .lines()
+        .map(|line| line.trim_end().to_string())
+        .collect::<Vec<_>>();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    (!lines.is_empty()).then_some(lines)
+}
+
 fn header_subtitle(layout: UiLayoutMode) -> &'static str {
     match layout {
         UiLayoutMode::Full => {
@@ -302,7 +559,7 @@ fn header_subtitle(layout: UiLayoutMode) -> &'static str {
     }
 }
 
-fn render_active(frame: &mut Frame<'_>, area: Rect, data: &RenderData<'_>) {
+fn render_active(frame: &mut Frame<'_>, area: Rect, layout: UiLayoutMode, data: &RenderData<'_>) {
     let mut lines = Vec::new();
     if !data.presence_enabled {
         lines.push(Line::from(Span::styled(
@@ -334,18 +591,52 @@ fn render_active(frame: &mut Frame<'_>, area: Rect, data: &RenderData<'_>) {
             ),
             Span::styled(" · ", theme::muted()),
             Span::styled(
-                presentable_cost(session),
+                presentable_cost(session, data.pricing),
                 Style::default().fg(theme::YELLOW),
             ),
         ]));
-        lines.push(Line::from(vec![
+        if data.show_model_rates
+            && let Some(pricing) = session
+                .model
+                .as_deref()
+                .and_then(|model| resolve_model_pricing(model, data.pricing).pricing)
+        {
+            lines.push(Line::from(vec![
+                Span::styled("rates ", theme::muted()),
+                Span::styled(
+                    pricing_rates_line(&pricing),
+                    Style::default().fg(theme::YELLOW),
+                ),
+            ]));
+        }
+        let mut token_line = vec![
             Span::styled("tokens ", theme::muted()),
             Span::raw(format_token_triplet(
                 session.session_delta_tokens,
                 session.last_turn_tokens,
                 session.session_total_tokens,
             )),
-        ]));
+        ];
+        if session.resume_count > 0 {
+            token_line.push(Span::styled(
+                format!(" · resumed {}x", session.resume_count),
+                theme::muted(),
+            ));
+        }
+        lines.push(Line::from(token_line));
+        if layout == UiLayoutMode::Full
+            && data.show_aggregate_cost
+            && let Some(total) =
+                aggregate_session_cost(data.sessions.iter().map(|item| item.total_cost_usd))
+        {
+            lines.push(Line::from(vec![
+                Span::styled("all sessions cost ", theme::muted()),
+                Span::styled(
+                    format_cost_in(total, data.pricing.display_currency, data.pricing.usd_rate),
+                    Style::default().fg(theme::YELLOW),
+                ),
+            ]));
+        }
         if let Some(context) = &session.context_window {
             let mut context_line = vec![
                 Span::styled("context ", theme::muted()),
@@ -354,7 +645,7 @@ fn render_active(frame: &mut Frame<'_>, area: Rect, data: &RenderData<'_>) {
                 Span::raw(format_tokens(context.window_tokens)),
                 Span::styled(
                     format!(" ({:.0}% free)", context.remaining_percent),
-                    Style::default().fg(limit_color(context.remaining_percent)),
+                    Style::default().fg(limit_color(context.remaining_percent, data.theme)),
                 ),
             ];
             if context.raw_window_tokens > 0 && context.raw_window_tokens != context.window_tokens {
@@ -368,9 +659,25 @@ fn render_active(frame: &mut Frame<'_>, area: Rect, data: &RenderData<'_>) {
         {
             lines.push(Line::from(vec![
                 Span::styled("activity ", theme::muted()),
-                Span::raw(activity.to_text(data.show_activity_target)),
+                Span::raw(activity_line_text(
+                    activity,
+                    data.show_activity_target,
+                    data.activity_emoji,
+                )),
+            ]));
+        }
+        if layout == UiLayoutMode::Full {
+            lines.push(Line::from(vec![
+                Span::styled("policy ", theme::muted()),
+                Span::raw(policy_line_text(
+                    session.approval_policy.as_deref(),
+                    session.sandbox_policy.as_deref(),
+                )),
             ]));
         }
+        if let Some(note) = limits_source_note(session, data.limits_source, data.sessions) {
+            lines.push(Line::from(Span::styled(note, theme::muted())));
+        }
     } else {
         lines.push(Line::from(Span::styled(
             "No active Codex session",
@@ -392,6 +699,63 @@ fn render_active(frame: &mut Frame<'_>, area: Rect, data: &RenderData<'_>) {
     );
 }
 
+fn limits_source_note(
+    active: &CodexSessionSnapshot,
+    source: Option<&EffectiveLimitSelection>,
+    sessions: &[CodexSessionSnapshot],
+) -> Option<String> {
+    let source = source?;
+    let project = |session_id: &str| {
+        sessions
+            .iter()
+            .find(|session| session.session_id == session_id)
+            .map(|session| session.project_name.as_str())
+            .unwrap_or("another session")
+    };
+    if source.window_sources.is_empty() {
+        if source.source_session_id == active.session_id {
+            return None;
+        }
+        return Some(format!(
+            "limits from {}",
+            truncate(project(&source.source_session_id), 28)
+        ));
+    }
+    let notes = source
+        .window_sources
+        .iter()
+        .filter(|window| window.session_id != active.session_id)
+        .map(|window| {
+            format!(
+                "{} limits from {}",
+                format_window_label(window.window_minutes),
+                truncate(project(&window.session_id), 20)
+            )
+        })
+        .collect::<Vec<_>>();
+    (!notes.is_empty()).then(|| notes.join(" · "))
+}
+
+fn pricing_rates_line(pricing: &ModelPricing) -> String {
+    let rate = |value: f64| {
+        let text = format!("{value:.4}");
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    };
+    format!(
+        "${}/M in, ${}/M out",
+        rate(pricing.input_per_million),
+        rate(pricing.output_per_million)
+    )
+}
+
+fn policy_line_text(approval_policy: Option<&str>, sandbox_policy: Option<&str>) -> String {
+    format!(
+        "{} | {}",
+        approval_policy.unwrap_or("n/a"),
+        sandbox_policy.unwrap_or("n/a")
+    )
+}
+
 fn render_usage(frame: &mut Frame<'_>, area: Rect, data: &RenderData<'_>) {
     let rows = Layout::vertical([
         Constraint::Length(3),
@@ -404,12 +768,12 @@ fn render_usage(frame: &mut Frame<'_>, area: Rect, data: &RenderData<'_>) {
     let primary_label = primary
         .map(|value| format_window_label(value.window_minutes))
         .unwrap_or_else(|| "quota unavailable".to_string());
-    render_usage_gauge(frame, rows[0], &primary_label, primary);
+    render_usage_gauge(frame, rows[0], &primary_label, primary, data);
     let secondary = limits.and_then(|value| value.secondary.as_ref());
     let secondary_label = secondary
         .map(|value| format_window_label(value.window_minutes))
         .unwrap_or_else(|| "additional quota unavailable".to_string());
-    render_usage_gauge(frame, rows[1], &secondary_label, secondary);
+    render_usage_gauge(frame, rows[1], &secondary_label, secondary, data);
     let warning = data
         .spark_plan_warning
         .unwrap_or("Context: observed JSONL, then local model cache, then bundled catalog.");
@@ -451,6 +815,7 @@ fn render_usage_gauge(
     area: Rect,
     label: &str,
     window: Option<&UsageWindow>,
+    data: &RenderData<'_>,
 ) {
     let used = window
         .map(|value| value.used_percent)
@@ -460,12 +825,26 @@ fn render_usage_gauge(
         .and_then(|value| value.resets_at)
         .map(|reset| format!("{label} · resets {}", format_time_until(Some(reset))))
         .unwrap_or_else(|| label.to_string());
-    let gauge = Gauge::default()
-        .block(panel(&title, Some(limit_color(100.0 - used))))
-        .gauge_style(Style::default().fg(limit_color(100.0 - used)))
-        .ratio(used / 100.0)
-        .label(format!("{used:.0}% used"));
-    frame.render_widget(gauge, area);
+    let color = limit_color(100.0 - used, data.theme);
+    let inner_width = area.width.saturating_sub(2);
+    if inner_width < data.min_bar_width {
+        frame.render_widget(
+            Paragraph::new(format!("{used:.0}% used"))
+                .style(Style::default().fg(color))
+                .block(panel(&title, Some(color))),
+            area,
+        );
+        return;
+    }
+    let label = format!(" {used:.0}% used");
+    let bar_width = usize::from(inner_width).saturating_sub(label.chars().count());
+    let bar = progress_bar_styled(used, bar_width, data.bar_style, data.bar_chars);
+    frame.render_widget(
+        Paragraph::new(format!("{bar}{label}"))
+            .style(Style::default().fg(color))
+            .block(panel(&title, Some(color))),
+        area,
+    );
 }
 
 fn render_metrics(frame: &mut Frame<'_>, area: Rect, data: &RenderData<'_>) {
@@ -479,24 +858,52 @@ fn render_metrics(frame: &mut Frame<'_>, area: Rect, data: &RenderData<'_>) {
     };
     let cache = metrics.totals.cache_hit_ratio * 100.0;
     let samples = sparkline_samples(metrics);
-    let inner = Layout::vertical([Constraint::Length(5), Constraint::Min(2)]).split(area);
-    let text = vec![
+    let budget_line = metrics.budget_status.map(budget_warning_line);
+    let reasoning_line = (metrics.totals.reasoning_output_tokens > 0).then(|| {
+        Line::from(vec![
+            Span::styled("output ", theme::muted()),
+            Span::raw(format_tokens(metrics.totals.output_tokens)),
+            Span::styled(" · reasoning ", theme::muted()),
+            Span::styled(
+                format_tokens(metrics.totals.reasoning_output_tokens),
+                Style::default().fg(theme::CYAN),
+            ),
+        ])
+    });
+    let fixed_lines =
+        3 + usize::from(budget_line.is_some()) + usize::from(reasoning_line.is_some());
+    let models = top_models(metrics, data.max_models_shown);
+    let models = &models[..metrics_model_rows(area.height, fixed_lines, models.len())];
+    let inner = Layout::vertical([
+        Constraint::Length(2 + (fixed_lines + models.len()) as u16),
+        Constraint::Min(2),
+    ])
+    .split(area);
+    let mut text = vec![
         Line::from(vec![
             Span::styled("cost ", theme::muted()),
             Span::styled(
-                format_metrics_cost(&metrics.totals),
+                format_metrics_cost_in(
+                    &metrics.totals,
+                    data.pricing.display_currency,
+                    data.pricing.usd_rate,
+                ),
                 Style::default().fg(theme::YELLOW),
             ),
             Span::styled(" · cache ", theme::muted()),
             Span::styled(
                 format!("{cache:.1}%"),
-                Style::default().fg(limit_color(cache)),
+                Style::default().fg(limit_color(cache, data.theme)),
             ),
         ]),
         Line::from(vec![
             Span::styled("saved ", theme::muted()),
             Span::styled(
-                format_cost(metrics.cost_breakdown.cached_input_savings_usd),
+                format_cost_in(
+                    metrics.cost_breakdown.cached_input_savings_usd,
+                    data.pricing.display_currency,
+                    data.pricing.usd_rate,
+                ),
                 Style::default().fg(theme::GREEN),
             ),
             Span::styled(" · uptime ", theme::muted()),
@@ -509,6 +916,27 @@ fn render_metrics(frame: &mut Frame<'_>, area: Rect, data: &RenderData<'_>) {
             Span::raw(metrics.totals.incomplete_sessions.to_string()),
         ]),
     ];
+    text.extend(reasoning_line);
+    text.extend(budget_line);
+    for model in models {
+        text.push(Line::from(vec![
+            Span::styled(
+                truncate(&format_model_display(&model.model_id, None, false), 24),
+                Style::default().fg(theme::PINK),
+            ),
+            Span::styled(" · ", theme::muted()),
+            Span::styled(
+                format_presentable_cost_in(
+                    model.known_cost_usd,
+                    model.pricing_status,
+                    data.pricing.display_currency,
+                    data.pricing.usd_rate,
+                )
+                .unwrap_or_else(|| "cost unavailable".to_string()),
+                Style::default().fg(theme::YELLOW),
+            ),
+        ]));
+    }
     frame.render_widget(
         Paragraph::new(text).block(panel("Usage", Some(theme::BORDER))),
         inner[0],
@@ -528,61 +956,355 @@ fn render_recent(frame: &mut Frame<'_>, area: Rect, layout: UiLayoutMode, data:
         UiLayoutMode::Compact => area.height.saturating_sub(2).min(4) as usize,
         UiLayoutMode::Minimal => area.height.saturating_sub(2).min(2) as usize,
     };
-    let items: Vec<ListItem<'_>> = data
-        .sessions
+    let mut sorted = visible_recent_sessions(data.sessions, data.recent_sort, data.recent_filter);
+    let active_rows = sorted.len();
+    sorted.extend(
+        data.idle_sessions
+            .iter()
+            .filter(|session| session_matches_filter(session, data.recent_filter)),
+    );
+    let visible = if sorted.len() > max_items && max_items > 1 {
+        max_items - 1
+    } else {
+        max_items
+    };
+    let cursor = clamp_recent_cursor(data.recent_cursor, active_rows);
+    let offset = recent_scroll_offset(cursor, sorted.len(), visible);
+    let now = Utc::now();
+    let presence_session_id = presence_session_id(data);
+    let mut items: Vec<ListItem<'_>> = sorted
         .iter()
-        .take(max_items)
-        .map(|session| {
+        .copied()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(index, session)| {
             let model = format_model_display(
                 session.model.as_deref().unwrap_or("unknown"),
                 session.reasoning_effort,
                 session.speed.mode == SpeedMode::Fast,
             );
-            let tokens = format_tokens(
-                session
-                    .session_total_tokens
-                    .unwrap_or(session.input_tokens_total + session.output_tokens_total),
-            );
-            ListItem::new(Line::from(vec![
-                Span::styled(
-                    truncate(&session.project_name, 22),
-                    Style::default().fg(theme::TEXT),
-                ),
+            let tokens = format_tokens(session_token_total(session));
+            let is_active = presence_session_id == Some(session.session_id.as_str());
+            let marker = if is_active {
+                format!("{} ", data.recent_active_marker)
+            } else {
+                " ".repeat(data.recent_active_marker.chars().count() + 1)
+            };
+            let mut row_style = recent_row_style(is_active, data.no_color);
+            if cursor == Some(index) {
+                row_style = row_style.reversed();
+            }
+            if !data.recent_line_template.trim().is_empty() {
+                let width = usize::from(area.width.saturating_sub(2))
+                    .saturating_sub(marker.chars().count());
+                return ListItem::new(Line::from(vec![
+                    Span::styled(marker, row_style),
+                    Span::styled(truncate(&recent_line_text(session, data), width), row_style),
+                ]));
+            }
+            let mut spans = vec![
+                Span::styled(marker, row_style),
+                Span::styled(truncate(&session.project_name, 22), row_style),
                 Span::styled(" · ", theme::muted()),
                 Span::styled(model, Style::default().fg(theme::PINK)),
                 Span::styled(" · ", theme::muted()),
                 Span::styled(tokens, Style::default().fg(theme::CYAN)),
-            ]))
+                Span::styled(" · ", theme::muted()),
+                Span::styled(
+                    session_elapsed_label(session.started_at, now),
+                    theme::muted(),
+                ),
+            ];
+            if layout == UiLayoutMode::Full {
+                spans.push(Span::styled(" · ", theme::muted()));
+                spans.push(Span::styled(last_event_label(session, now), theme::muted()));
+            }
+            if data.pinned_session_id == Some(session.session_id.as_str()) {
+                spans.push(Span::styled(" · pinned", theme::title()));
+            }
+            if data.unread_waiting.contains(&session.session_id) {
+                spans.push(Span::styled(" · waiting", theme::title()));
+            }
+            if index >= active_rows {
+                spans.push(Span::styled(" · idle", theme::muted()));
+            }
+            if layout == UiLayoutMode::Full
+                && let Some(history) = data
+                    .token_history
+                    .get(&session.session_id)
+                    .filter(|history| !history.is_empty())
+            {
+                spans.push(Span::styled(" · ", theme::muted()));
+                spans.push(Span::styled(
+                    token_sparkline(history),
+                    Style::default().fg(theme::CYAN),
+                ));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
-    let list = if items.is_empty() {
+    if let Some(summary) = recent_overflow_summary(&sorted, offset, visible) {
+        items.push(ListItem::new(Line::from(Span::styled(
+            summary,
+            theme::muted(),
+        ))));
+    }
+    let list = if items.is_empty() && !data.recent_filter.is_empty() {
+        List::new(vec![ListItem::new("No sessions match the filter")])
+    } else if items.is_empty() {
         List::new(vec![ListItem::new("No recent sessions yet")])
     } else {
         List::new(items)
     };
-    frame.render_widget(
-        list.block(panel("Recent sessions", Some(theme::BORDER))),
-        area,
-    );
+    let mut title = recent_header(offset, visible, sorted.len(), data.show_all_sessions);
+    if !data.recent_filter.is_empty() {
+        title.push_str(&format!(" · filter: {}", data.recent_filter));
+    }
+    frame.render_widget(list.block(panel(&title, Some(theme::BORDER))), area);
+}
+
+fn recent_header(offset: usize, visible: usize, total: usize, show_all: bool) -> String {
+    let mut title = if total > visible && visible > 0 {
+        let end = (offset + visible).min(total);
+        format!(
+            "Recent sessions {}-{end} of {total} · {} hidden",
+            offset + 1,
+            total - (end - offset)
+        )
+    } else if total > 0 {
+        format!("Recent sessions ({total})")
+    } else {
+        "Recent sessions".to_string()
+    };
+    if show_all {
+        title.push_str(" · show all");
+    }
+    title
+}
+
+fn presence_session_id<'a>(data: &RenderData<'a>) -> Option<&'a str> {
+    pinned_presence_session(data.sessions, data.privacy, data.pinned_session_id)
+        .map(|session| session.session_id.as_str())
+}
+
+fn recent_line_text(session: &CodexSessionSnapshot, data: &RenderData<'_>) -> String {
+    expand_template(data.recent_line_template, |name| match name {
+        "project" => Some(session.project_name.clone()),
+        "activity" => Some(
+            session
+                .activity
+                .as_ref()
+                .filter(|_| data.show_activity)
+                .map(|activity| activity.to_text(data.show_activity_target))
+                .unwrap_or_default(),
+        ),
+        "branch" => Some(session.git_branch.clone().unwrap_or_default()),
+        "last" => Some(
+            session
+                .last_turn_tokens
+                .map(format_tokens)
+                .unwrap_or_default(),
+        ),
+        "total" => Some(format_tokens(session_token_total(session))),
+        "cost" => Some(presentable_cost(session, data.pricing)),
+        _ => None,
+    })
+}
+
+pub fn sort_recent_sessions(
+    sessions: &[CodexSessionSnapshot],
+    sort: RecentSort,
+) -> Vec<&CodexSessionSnapshot> {
+    let mut sorted = sessions.iter().collect::<Vec<_>>();
+    match sort {
+        RecentSort::Rank => {}
+        RecentSort::Tokens => {
+            sorted.sort_by_key(|session| std::cmp::Reverse(session_token_total(session)))
+        }
+        RecentSort::Recency => {
+            sorted.sort_by_key(|session| std::cmp::Reverse(session.last_activity))
+        }
+        RecentSort::Cost => {
+            sorted.sort_by(|left, right| right.total_cost_usd.total_cmp(&left.total_cost_usd))
+        }
+    }
+    sorted
+}
+
+pub fn session_matches_filter(session: &CodexSessionSnapshot, filter: &str) -> bool {
+    let needle = filter.trim().to_lowercase();
+    needle.is_empty()
+        || session.project_name.to_lowercase().contains(&needle)
+        || session
+            .git_branch
+            .as_deref()
+            .is_some_and(|branch| branch.to_lowercase().contains(&needle))
+}
+
+pub fn visible_recent_sessions<'a>(
+    sessions: &'a [CodexSessionSnapshot],
+    sort: RecentSort,
+    filter: &str,
+) -> Vec<&'a CodexSessionSnapshot> {
+    let mut sorted = sort_recent_sessions(sessions, sort);
+    sorted.retain(|session| session_matches_filter(session, filter));
+    sorted
+}
+
+fn recent_overflow_summary(
+    sessions: &[&CodexSessionSnapshot],
+    offset: usize,
+    visible: usize,
+) -> Option<String> {
+    let omitted = sessions
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index < offset || *index >= offset + visible)
+        .map(|(_, session)| session_token_total(session))
+        .collect::<Vec<_>>();
+    if omitted.is_empty() || visible == 0 {
+        return None;
+    }
+    Some(format!(
+        "+{} more · {} tokens",
+        omitted.len(),
+        format_tokens(omitted.iter().sum())
+    ))
+}
+
+fn session_elapsed_label(started_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> String {
+    started_at
+        .map(|started_at| human_duration((now - started_at).to_std().unwrap_or_default()))
+        .unwrap_or_else(|| "n/a".to_string())
 }
 
-fn presentable_cost(session: &CodexSessionSnapshot) -> String {
-    format_presentable_cost(session.known_cost_usd, session.pricing_status)
-        .unwrap_or_else(|| "cost unavailable".to_string())
+fn last_event_label(session: &CodexSessionSnapshot, now: DateTime<Utc>) -> String {
+    let last_activity = DateTime::<Utc>::from(session.last_activity);
+    let last_event = session
+        .last_token_event_at
+        .map_or(last_activity, |at| at.max(last_activity));
+    format_since_at(Some(last_event), now)
+}
+
+fn session_token_total(session: &CodexSessionSnapshot) -> u64 {
+    session
+        .session_total_tokens
+        .unwrap_or(session.input_tokens_total + session.output_tokens_total)
+}
+
+const SPARKLINE_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+fn sparkline_block(value: u64, max: u64) -> char {
+    if max == 0 {
+        return SPARKLINE_BLOCKS[0];
+    }
+    let last = SPARKLINE_BLOCKS.len() - 1;
+    let index = (u128::from(value.min(max)) * last as u128 / u128::from(max)) as usize;
+    SPARKLINE_BLOCKS[index]
+}
+
+fn token_sparkline(values: &[u64]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|value| sparkline_block(*value, max))
+        .collect()
+}
+
+pub const RECENT_PAGE_ROWS: usize = 5;
+
+pub fn step_recent_cursor(cursor: Option<usize>, delta: isize, total: usize) -> Option<usize> {
+    let next = cursor.map_or(0, |cursor| cursor.saturating_add_signed(delta));
+    clamp_recent_cursor(Some(next), total)
+}
+
+fn clamp_recent_cursor(cursor: Option<usize>, total: usize) -> Option<usize> {
+    let last = total.checked_sub(1)?;
+    cursor.map(|cursor| cursor.min(last))
+}
+
+fn recent_scroll_offset(cursor: Option<usize>, total: usize, visible: usize) -> usize {
+    let visible = visible.max(1);
+    let cursor = clamp_recent_cursor(cursor, total);
+    cursor
+        .map_or(0, |cursor| (cursor + 1).saturating_sub(visible))
+        .min(total.saturating_sub(visible))
+}
+
+fn recent_row_style(is_active: bool, no_color: bool) -> Style {
+    match (is_active, no_color) {
+        (true, true) => Style::default().bold(),
+        (true, false) => Style::default().fg(theme::ACCENT).bold(),
+        (false, true) => Style::default(),
+        (false, false) => Style::default().fg(theme::TEXT),
+    }
+}
+
+fn aggregate_session_cost(costs: impl IntoIterator<Item = f64>) -> Option<f64> {
+    let mut costs = costs
+        .into_iter()
+        .filter(|cost| cost.is_finite() && *cost > 0.0)
+        .peekable();
+    costs.peek()?;
+    Some(costs.sum())
+}
+
+fn activity_emoji(kind: &SessionActivityKind) -> Option<&'static str> {
+    match kind {
+        SessionActivityKind::Thinking => Some("🧠"),
+        SessionActivityKind::ReadingFile => Some("📖"),
+        SessionActivityKind::EditingFile => Some("📝"),
+        SessionActivityKind::WritingTests => Some("🧪"),
+        SessionActivityKind::RunningCommand => Some("🔧"),
+        SessionActivityKind::Archiving => Some("📦"),
+        SessionActivityKind::WaitingInput => Some("⌛"),
+        SessionActivityKind::AwaitingApproval => Some("✋"),
+        SessionActivityKind::Idle => None,
+    }
+}
+
+fn activity_line_text(
+    activity: &SessionActivitySnapshot,
+    show_target: bool,
+    show_emoji: bool,
+) -> String {
+    let text = activity.to_text(show_target);
+    match activity_emoji(&activity.kind).filter(|_| show_emoji) {
+        Some(emoji) => format!("{emoji} {text}"),
+        None => text,
+    }
+}
+
+fn presentable_cost(session: &CodexSessionSnapshot, pricing: &PricingConfig) -> String {
+    if session.pricing_source == PricingSource::Unavailable {
+        return "n/a".to_string();
+    }
+    format_presentable_cost_in(
+        session.known_cost_usd,
+        session.pricing_status,
+        pricing.display_currency,
+        pricing.usd_rate,
+    )
+    .unwrap_or_else(|| "n/a".to_string())
 }
 
 fn render_footer(
     frame: &mut Frame<'_>,
     area: Rect,
-    mode: FooterMode,
+    mode: FooterMode<'_>,
     desktop_design_label: &str,
     presence_enabled: bool,
+    updates_frozen: bool,
+    privacy_enabled: bool,
 ) {
     let (left, right) = footer_parts(
         area.width as usize,
         mode,
         desktop_design_label,
         presence_enabled,
+        updates_frozen,
+        privacy_enabled,
     );
     let mut spans = vec![Span::styled(left, theme::muted())];
     if !right.is_empty() {
@@ -642,6 +1364,8 @@ fn render_plan_picker(
         FooterMode::PlanPicker,
         desktop_design_label,
         presence_enabled,
+        false,
+        false,
     );
 }
 
@@ -705,6 +1429,8 @@ fn render_privacy_picker(
         FooterMode::PrivacyPicker,
         desktop_design_label,
         presence_enabled,
+        false,
+        false,
     );
 }
 
@@ -751,7 +1477,8 @@ pub fn frame_signature(data: &RenderData<'_>) -> String {
     );
     let _ = write!(
         signature,
-        "privacy-picker:{}|",
+        "privacy-mode:{}|privacy-picker:{}|",
+        data.privacy.enabled,
         data.privacy_picker
             .map(|value| value.selected_index)
             .unwrap_or(usize::MAX)
@@ -763,11 +1490,47 @@ pub fn frame_signature(data: &RenderData<'_>) -> String {
             field.is_enabled(data.privacy)
         );
     }
+    let _ = write!(
+        signature,
+        "currency:{:?}:{}|recent-marker:{}|recent-template:{}|no-color:{}|frozen:{}|help:{}|show-all:{}|models:{}|aggregate:{}|emoji:{}|rates:{}|bar-min:{}|theme:{:?}|recent-sort:{:?}|recent-cursor:{:?}|pinned:{}|unread:{}|sparklines:{:?}|banner:{:?}|bar-style:{:?}|bar-chars:{:?}|filter:{}:{}|",
+        data.pricing.display_currency,
+        data.pricing.usd_rate,
+        data.recent_active_marker,
+        data.recent_line_template,
+        data.no_color,
+        data.updates_frozen,
+        data.help_open,
+        data.show_all_sessions,
+        data.max_models_shown,
+        data.show_aggregate_cost,
+        data.activity_emoji,
+        data.show_model_rates,
+        data.min_bar_width,
+        data.theme,
+        data.recent_sort,
+        data.recent_cursor,
+        data.pinned_session_id.unwrap_or(""),
+        data.unread_waiting.join(","),
+        data.token_history,
+        data.banner_lines,
+        data.bar_style,
+        data.bar_chars,
+        data.recent_filter_open,
+        data.recent_filter
+    );
+    for session in data.idle_sessions {
+        let _ = write!(signature, "idle:{}|", session.session_id);
+    }
     if let Some(active) = data.active {
         let _ = write!(
             signature,
-            "active:{}|{}|{}|{}|{}|{}|{}|",
+            "active:{}|{}|{}|{}|{}|{}|{}|{}|",
             active.session_id,
+            active
+                .activity
+                .as_ref()
+                .map(|activity| activity.to_text(data.show_activity_target))
+                .unwrap_or_default(),
             active.model.as_deref().unwrap_or(""),
             active
                 .reasoning_effort
@@ -784,17 +1547,29 @@ pub fn frame_signature(data: &RenderData<'_>) -> String {
     if let Some(metrics) = data.metrics {
         let _ = write!(
             signature,
-            "metrics:{:.6}|{:?}|{}|{}|{}|{}|{:.4}|",
+            "metrics:{:.6}|{:?}|{}|{}|{}|{}|{}|{:.4}|{:?}|",
             metrics.totals.cost_usd,
             metrics.totals.pricing_status,
             metrics.totals.incomplete_sessions,
             metrics.totals.input_tokens,
             metrics.totals.cached_input_tokens,
             metrics.totals.output_tokens,
+            metrics.totals.reasoning_output_tokens,
             metrics.totals.cache_hit_ratio,
+            metrics.budget_status,
         );
     }
     if let Some(limits) = data.effective_limits {
+        if let Some(source) = data.limits_source {
+            let _ = write!(signature, "limits-from:{}|", source.source_session_id);
+            for window in &source.window_sources {
+                let _ = write!(
+                    signature,
+                    "{}:{}|",
+                    window.window_minutes, window.session_id
+                );
+            }
+        }
         write_window_signature(&mut signature, "primary", limits.primary.as_ref());
         write_window_signature(&mut signature, "secondary", limits.secondary.as_ref());
     }
@@ -851,13 +1626,52 @@ fn select_banner_variant(
     }
 }
 
-fn limit_color(remaining_percent: f64) -> Color {
-    if remaining_percent >= 60.0 {
+fn limit_color(remaining_percent: f64, ui_theme: UiTheme) -> Color {
+    let color = if remaining_percent >= 60.0 {
         theme::TEXT
     } else if remaining_percent >= 25.0 {
         theme::MUTED
     } else {
         theme::TEXT
+    };
+    themed_color(color, ui_theme)
+}
+
+fn themed_color(color: Color, ui_theme: UiTheme) -> Color {
+    match ui_theme {
+        UiTheme::Dark => color,
+        UiTheme::Light => match color {
+            theme::TEXT | theme::ACCENT => theme::LIGHT_TEXT,
+            theme::MUTED => theme::LIGHT_MUTED,
+            theme::BORDER => theme::LIGHT_BORDER,
+            other => other,
+        },
+        UiTheme::None => Color::Reset,
+    }
+}
+
+fn apply_render_styling(buffer: &mut Buffer, data: &RenderData<'_>) {
+    if data.no_color {
+        strip_styling(buffer);
+    } else {
+        apply_theme(buffer, data.theme);
+    }
+}
+
+fn strip_styling(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        cell.set_style(Style::reset());
+    }
+}
+
+fn apply_theme(buffer: &mut Buffer, ui_theme: UiTheme) {
+    if ui_theme == UiTheme::Dark {
+        return;
+    }
+    for cell in &mut buffer.content {
+        let fg = themed_color(cell.fg, ui_theme);
+        let bg = themed_color(cell.bg, ui_theme);
+        cell.set_fg(fg).set_bg(bg);
     }
 }
 
@@ -883,6 +1697,22 @@ fn spinner(phase: u8) -> &'static str {
     FRAMES[phase as usize % FRAMES.len()]
 }
 
+fn budget_warning_line(status: BudgetStatus) -> Line<'static> {
+    let message = match status {
+        BudgetStatus::Approaching => "budget 80% reached",
+        BudgetStatus::Exceeded => "budget exceeded",
+    };
+    Line::from(Span::styled(
+        format!("! {message}"),
+        Style::default().fg(theme::ACCENT).bold(),
+    ))
+}
+
+fn metrics_model_rows(panel_height: u16, fixed_lines: usize, max_models: usize) -> usize {
+    let text_rows = panel_height.saturating_sub(4) as usize;
+    text_rows.saturating_sub(fixed_lines).min(max_models)
+}
+
 fn sparkline_samples(metrics: &MetricsSnapshot) -> Vec<u64> {
     let mut values: Vec<u64> = metrics
         .by_model
@@ -902,19 +1732,30 @@ fn sparkline_samples(metrics: &MetricsSnapshot) -> Vec<u64> {
 
 fn footer_parts(
     width: usize,
-    mode: FooterMode,
+    mode: FooterMode<'_>,
     desktop_design_label: &str,
     presence_enabled: bool,
+    updates_frozen: bool,
+    privacy_enabled: bool,
 ) -> (String, String) {
     let left = match mode {
         FooterMode::PlanPicker => "Plan selector: ↑/↓ choose · Enter apply · Esc close".to_string(),
+        FooterMode::RecentFilter(filter) => {
+            format!("Filter: {filter}_ · project or branch · Enter keep · Esc clear")
+        }
         FooterMode::PrivacyPicker => {
             "Composer: ↑/↓ choose · Shift+↑/↓ move · Space toggle · Esc close".to_string()
         }
         FooterMode::Normal => {
             let presence_state = if presence_enabled { "On" } else { "Paused" };
+            let freeze_state = if updates_frozen { "On" } else { "Off" };
+            let privacy_state = if privacy_enabled {
+                "Privacy: ON · "
+            } else {
+                ""
+            };
             format!(
-                "M presence: {presence_state} · V privacy · P plan · D design: {desktop_design_label} · Ctrl+C quit"
+                "{privacy_state}M presence: {presence_state} · V privacy · P plan · D design: {desktop_design_label} · F freeze: {freeze_state} · Ctrl+C quit"
             )
         }
     };
@@ -922,7 +1763,7 @@ fn footer_parts(
     if width <= 32 {
         (truncate(&left, width), String::new())
     } else {
-        let left_budget = width.saturating_sub(right.len() + 1);
+        let left_budget = width.saturating_sub(right.chars().count() + 1);
         (truncate(&left, left_budget), right)
     }
 }
@@ -960,6 +1801,10 @@ mod theme {
     pub const TEXT: Color = Color::Rgb(245, 245, 245);
     pub const MUTED: Color = Color::Rgb(150, 150, 150);
     pub const BORDER: Color = Color::Rgb(82, 82, 82);
+    pub const ACCENT: Color = Color::Rgb(255, 255, 255);
+    pub const LIGHT_TEXT: Color = Color::Rgb(20, 20, 20);
+    pub const LIGHT_MUTED: Color = Color::Rgb(105, 105, 105);
+    pub const LIGHT_BORDER: Color = Color::Rgb(170, 170, 170);
     pub const CYAN: Color = TEXT;
     pub const PINK: Color = TEXT;
     pub const GREEN: Color = TEXT;
@@ -982,8 +1827,35 @@ mod tests {
     use std::sync::LazyLock;
 
     static TEST_PRIVACY: LazyLock<PrivacyConfig> = LazyLock::new(PrivacyConfig::default);
+    static TEST_PRICING: LazyLock<PricingConfig> = LazyLock::new(PricingConfig::default);
     static TEST_PRESENCE_LAYOUT: LazyLock<PresenceLayoutConfig> =
         LazyLock::new(PresenceLayoutConfig::default);
+    static TEST_TOKEN_HISTORY: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+
+    #[test]
+    fn iterm_badge_escape_base64_encodes_the_badge_text() {
+        assert_eq!(
+            iterm_badge_escape("api · Reading lib.rs"),
+            "\x1b]1337;SetBadgeFormat=YXBpIMK3IFJlYWRpbmcgbGliLnJz\x07"
+        );
+        assert_eq!(
+            iterm_badge_escape("ab"),
+            "\x1b]1337;SetBadgeFormat=YWI=\x07"
+        );
+        assert_eq!(iterm_badge_escape(""), "\x1b]1337;SetBadgeFormat=\x07");
+        assert_eq!(
+            iterm_badge_escape(r"a\(b)"),
+            "\x1b]1337;SetBadgeFormat=YVxcKGIp\x07"
+        );
+    }
+
+    #[test]
+    fn aggregate_session_cost_sums_priced_sessions_and_skips_zero_cost() {
+        let total = aggregate_session_cost([1.25, 0.0, 0.5, f64::NAN, 2.0]).expect("total");
+        assert!((total - 3.75).abs() < f64::EPSILON);
+        assert_eq!(aggregate_session_cost([0.0, 0.0]), None);
+        assert_eq!(aggregate_session_cost([]), None);
+    }
 
     fn sample_render_data(plan_picker: Option<PlanPickerView>) -> RenderData<'static> {
         RenderData {
@@ -996,7 +1868,11 @@ mod tests {
             show_activity: true,
             show_activity_target: true,
             presence_enabled: true,
+            updates_frozen: false,
+            help_open: false,
+            show_all_sessions: false,
             privacy: &TEST_PRIVACY,
+            pricing: &TEST_PRICING,
             presence_layout: &TEST_PRESENCE_LAYOUT,
             plan_display_label: "Pro 20x ($200/month)",
             plan_status_label: "Pro 20x (manual)",
@@ -1004,9 +1880,11 @@ mod tests {
             fast_active: false,
             limits_source_label: "local",
             limits_updated_label: "just now",
+            limits_source: None,
             spark_plan_warning: None,
             logo_mode: TerminalLogoMode::Auto,
             logo_path: Some("assets/branding/codex-app.png"),
+            banner_lines: None,
             desktop_design_label: "Codex App",
             banner_phase: 0,
             active: None,
@@ -1014,6 +1892,25 @@ mod tests {
             effective_credits: None,
             metrics: None,
             sessions: &[],
+            idle_sessions: &[],
+            recent_active_marker: ">",
+            recent_line_template: "",
+            max_models_shown: 5,
+            show_aggregate_cost: false,
+            activity_emoji: false,
+            show_model_rates: false,
+            min_bar_width: 12,
+            recent_cursor: None,
+            pinned_session_id: None,
+            unread_waiting: &[],
+            token_history: &TEST_TOKEN_HISTORY,
+            no_color: false,
+            theme: UiTheme::Dark,
+            recent_sort: RecentSort::Rank,
+            bar_style: BarStyle::Ascii,
+            bar_chars: BarChars::ASCII,
+            recent_filter: "",
+            recent_filter_open: false,
             plan_picker,
             privacy_picker: None,
         }
@@ -1023,22 +1920,684 @@ mod tests {
         let backend = TestBackend::new(width, height);
         let mut terminal = Terminal::new(backend).expect("terminal");
         terminal
-            .draw(|frame| render_frame(frame, data))
+            .draw(|frame| {
+                render_frame(frame, data);
+                apply_render_styling(frame.buffer_mut(), data);
+            })
             .expect("draw");
         terminal.backend().to_string()
     }
 
+    #[test]
+    fn metrics_model_rows_fit_the_fixed_full_layout_panel() {
+        assert_eq!(metrics_model_rows(9, 3, 5), 2);
+        assert_eq!(metrics_model_rows(9, 4, 5), 1);
+        assert_eq!(metrics_model_rows(9, 5, 5), 0);
+        assert_eq!(metrics_model_rows(9, 3, 1), 1);
+        assert_eq!(metrics_model_rows(3, 3, 5), 0);
+    }
+
     #[test]
     fn header_rule_respects_requested_width() {
         let line = hr("Test", 24);
         assert_eq!(line.len(), 24);
     }
 
+    #[test]
+    fn recent_top_row_uses_accent_style_and_respects_no_color() {
+        let active = recent_row_style(true, false);
+        let other = recent_row_style(false, false);
+        assert_eq!(active.fg, Some(theme::ACCENT));
+        assert!(active.add_modifier.contains(Modifier::BOLD));
+        assert_ne!(other.fg, Some(theme::ACCENT));
+        assert!(!other.add_modifier.contains(Modifier::BOLD));
+
+        let plain_active = recent_row_style(true, true);
+        assert_eq!(plain_active.fg, None);
+        assert!(plain_active.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(recent_row_style(false, true).fg, None);
+    }
+
+    #[test]
+    fn usage_bar_is_suppressed_below_configured_width() {
+        let window = UsageWindow {
+            used_percent: 60.0,
+            remaining_percent: 40.0,
+            window_minutes: 300,
+            resets_at: None,
+        };
+        let render = |width: u16, min_bar_width: u16| {
+            let data = RenderData {
+                min_bar_width,
+                bar_style: BarStyle::Blocks,
+                bar_chars: BarChars::BLOCKS,
+                ..sample_render_data(None)
+            };
+            let mut terminal = Terminal::new(TestBackend::new(width, 3)).expect("terminal");
+            terminal
+                .draw(|frame| render_usage_gauge(frame, frame.area(), "5h", Some(&window), &data))
+                .expect("draw");
+            terminal.backend().to_string()
+        };
+
+        let narrow = render(16, 20);
+        assert!(narrow.contains("60% used"));
+        assert!(!narrow.contains('█'), "{narrow}");
+
+        let wide = render(30, 20);
+        assert!(wide.contains('█'), "{wide}");
+    }
+
+    #[test]
+    fn block_usage_gauge_draws_configured_fill_chars() {
+        let window = UsageWindow {
+            used_percent: 50.0,
+            remaining_percent: 50.0,
+            window_minutes: 300,
+            resets_at: None,
+        };
+        let data = RenderData {
+            bar_chars: BarChars {
+                filled: '=',
+                empty: '.',
+            },
+            ..sample_render_data(None)
+        };
+        let mut terminal = Terminal::new(TestBackend::new(30, 3)).expect("terminal");
+        terminal
+            .draw(|frame| render_usage_gauge(frame, frame.area(), "5h", Some(&window), &data))
+            .expect("draw");
+        let output = terminal.backend().to_string();
+        assert!(output.contains("==========......... 50% used"), "{output}");
+    }
+
+    #[test]
+    fn usage_gauge_without_color_emits_no_style_escapes() {
+        use ratatui::backend::{Backend, CrosstermBackend};
+
+        let window = UsageWindow {
+            used_percent: 90.0,
+            remaining_percent: 10.0,
+            window_minutes: 300,
+            resets_at: None,
+        };
+        let data = sample_render_data(None);
+        let mut terminal = Terminal::new(TestBackend::new(30, 3)).expect("terminal");
+        terminal
+            .draw(|frame| {
+                render_usage_gauge(frame, frame.area(), "5h", Some(&window), &data);
+                strip_styling(frame.buffer_mut());
+            })
+            .expect("draw");
+        let buffer = terminal.backend().buffer().clone();
+
+        let ansi = |cells: Vec<(u16, u16, &ratatui::buffer::Cell)>| {
+            let mut out = Vec::new();
+            CrosstermBackend::new(&mut out)
+                .draw(cells.into_iter())
+                .expect("ansi draw");
+            String::from_utf8(out).expect("utf8")
+        };
+        let trailer = ansi(Vec::new());
+        let output = ansi(
+            buffer
+                .content
+                .iter()
+                .enumerate()
+                .map(|(index, cell)| {
+                    let (x, y) = buffer.pos_of(index);
+                    (x, y, cell)
+                })
+                .collect(),
+        );
+        let body = output.strip_suffix(&trailer).expect("reset trailer");
+        assert!(body.contains("90% used"));
+        let styled = body.split("\x1b[").skip(1).any(|sequence| {
+            sequence
+                .find(|ch: char| !ch.is_ascii_digit() && ch != ';')
+                .is_some_and(|end| sequence[end..].starts_with('m'))
+        });
+        assert!(!styled, "{body:?}");
+    }
+
+    #[test]
+    fn limits_source_note_only_appears_for_another_session() {
+        let active = recent_session("api", 10, 0.0, 5);
+        let sessions = vec![active.clone(), recent_session("other-project", 10, 0.0, 1)];
+
+        let note = |source_id: &str, windows: &[(u64, &str)]| {
+            let selection = EffectiveLimitSelection {
+                source_session_id: source_id.to_string(),
+                source_limit_id: None,
+                source_scope: codex_presence_core::RateLimitScope::GlobalCodex,
+                observed_at: None,
+                limits: RateLimits::default(),
+                credits: None,
+                window_sources: windows
+                    .iter()
+                    .map(
+                        |(window_minutes, session_id)| codex_presence_core::LimitWindowSource {
+                            window_minutes: *window_minutes,
+                            session_id: session_id.to_string(),
+                        },
+                    )
+                    .collect(),
+            };
+            limits_source_note(&active, Some(&selection), &sessions)
+        };
+
+        assert_eq!(note("api", &[]), None);
+        assert_eq!(limits_source_note(&active, None, &sessions), None);
+        assert_eq!(
+            note("other-project", &[]).as_deref(),
+            Some("limits from other-project")
+        );
+        assert_eq!(
+            note("gone", &[]).as_deref(),
+            Some("limits from another session")
+        );
+        assert_eq!(
+            note("api", &[(300, "api"), (10_080, "other-project")]).as_deref(),
+            Some("7d limits from other-project")
+        );
+        assert_eq!(note("api", &[(300, "api"), (10_080, "api")]), None);
+    }
+
+    fn recent_session(
+        session_id: &str,
+        total_tokens: u64,
+        cost_usd: f64,
+        age_secs: u64,
+    ) -> CodexSessionSnapshot {
+        CodexSessionSnapshot {
+            session_id: session_id.to_string(),
+            cwd: std::path::PathBuf::from("."),
+            project_name: session_id.to_string(),
+            git_branch: None,
+            originator: None,
+            source: None,
+            model: None,
+            reasoning_effort: None,
+            speed: crate::model::SessionSpeed::default(),
+            approval_policy: None,
+            sandbox_policy: None,
+            session_total_tokens: Some(total_tokens),
+            last_turn_tokens: None,
+            session_delta_tokens: None,
+            resume_count: 0,
+            input_tokens_total: 0,
+            cached_input_tokens_total: 0,
+            output_tokens_total: 0,
+            reasoning_output_tokens_total: 0,
+            last_input_tokens: None,
+            last_cached_input_tokens: None,
+            last_output_tokens: None,
+            total_cost_usd: cost_usd,
+            known_cost_usd: None,
+            cost_breakdown: crate::cost::TokenCostBreakdown::default(),
+            pricing_source: crate::cost::PricingSource::Unavailable,
+            pricing_status: crate::cost::PricingStatus::Unavailable,
+            cost_attribution: crate::cost::CostAttribution::SingleModel,
+            cost_breakdown_reconciled: false,
+            context_window: None,
+            limits: RateLimits::default(),
+            rate_limit_envelopes: Vec::new(),
+            activity: None,
+            started_at: None,
+            last_token_event_at: None,
+            last_activity: std::time::SystemTime::now() - Duration::from_secs(age_secs),
+            source_file: std::path::PathBuf::from("recent.jsonl"),
+            pid: None,
+            edit_counts: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn pricing_rates_line_formats_per_million_rates() {
+        let pricing = ModelPricing {
+            input_per_million: 1.75,
+            cache_write_per_million: None,
+            cached_input_per_million: 0.175,
+            output_per_million: 14.0,
+        };
+        assert_eq!(pricing_rates_line(&pricing), "$1.75/M in, $14/M out");
+
+        let pricing = ModelPricing {
+            input_per_million: 0.25,
+            cache_write_per_million: None,
+            cached_input_per_million: 0.025,
+            output_per_million: 2.0,
+        };
+        assert_eq!(pricing_rates_line(&pricing), "$0.25/M in, $2/M out");
+    }
+
+    #[test]
+    fn policy_line_combines_approval_and_sandbox_with_fallbacks() {
+        assert_eq!(
+            policy_line_text(Some("on-request"), Some("workspace-write")),
+            "on-request | workspace-write"
+        );
+        assert_eq!(policy_line_text(None, Some("read-only")), "n/a | read-only");
+        assert_eq!(policy_line_text(Some("never"), None), "never | n/a");
+        assert_eq!(policy_line_text(None, None), "n/a | n/a");
+    }
+
+    #[test]
+    fn recent_overflow_summary_counts_sessions_beyond_the_display_cap() {
+        let sessions = (0..30)
+            .map(|index| recent_session(&format!("session-{index}"), 1_000, 0.0, index))
+            .collect::<Vec<_>>();
+        let sorted = sort_recent_sessions(&sessions, RecentSort::Rank);
+
+        assert_eq!(
+            recent_overflow_summary(&sorted, 0, 7).as_deref(),
+            Some("+23 more · 23.0K tokens")
+        );
+        assert_eq!(
+            recent_overflow_summary(&sorted, 5, 7).as_deref(),
+            Some("+23 more · 23.0K tokens")
+        );
+        assert_eq!(recent_overflow_summary(&sorted, 0, 30), None);
+    }
+
+    #[test]
+    fn ui_state_round_trips_and_falls_back_when_corrupt() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("ui-state.json");
+        assert_eq!(UiState::load(&path), UiState::default());
+
+        let state = UiState {
+            recent_cursor: Some(3),
+            pinned_session_id: Some("session-7".to_string()),
+        };
+        state.save(&path).expect("save");
+        assert_eq!(UiState::load(&path), state);
+
+        std::fs::write(&path, "{not json").expect("corrupt");
+        assert_eq!(UiState::load(&path), UiState::default());
+    }
+
+    #[test]
+    fn frame_signature_tracks_activity_target_visibility() {
+        let mut session = recent_session("api", 1_000, 0.0, 0);
+        session.activity = Some(crate::session::SessionActivitySnapshot {
+            kind: crate::session::SessionActivityKind::EditingFile,
+            target: Some("src/secret.rs".to_string()),
+            ..crate::session::SessionActivitySnapshot::default()
+        });
+        let shown = RenderData {
+            active: Some(&session),
+            ..sample_render_data(None)
+        };
+        let hidden = RenderData {
+            active: Some(&session),
+            show_activity_target: false,
+            ..sample_render_data(None)
+        };
+
+        assert!(frame_signature(&shown).contains("src/secret.rs"));
+        assert!(!frame_signature(&hidden).contains("src/secret.rs"));
+        assert_ne!(frame_signature(&shown), frame_signature(&hidden));
+    }
+
+    #[test]
+    fn last_event_label_uses_the_latest_activity_signal() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).expect("now");
+        let mut session = recent_session("api", 1_000, 0.0, 0);
+        session.last_activity = (now - chrono::Duration::seconds(125)).into();
+        assert_eq!(last_event_label(&session, now), "2m 5s ago");
+
+        session.last_token_event_at = Some(now - chrono::Duration::seconds(10));
+        assert_eq!(last_event_label(&session, now), "10s ago");
+
+        session.last_token_event_at = Some(now + chrono::Duration::seconds(5));
+        assert_eq!(last_event_label(&session, now), "just now");
+    }
+
+    #[test]
+    fn recent_filter_matches_project_or_branch_case_insensitively() {
+        let mut api = recent_session("payments-api", 1_000, 0.0, 0);
+        api.git_branch = Some("feature/Checkout".to_string());
+        let web = recent_session("web-client", 2_000, 0.0, 0);
+        let sessions = vec![api, web];
+        let ids = |filter| {
+            visible_recent_sessions(&sessions, RecentSort::Tokens, filter)
+                .into_iter()
+                .map(|session| session.session_id.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(""), ["web-client", "payments-api"]);
+        assert_eq!(ids("API"), ["payments-api"]);
+        assert_eq!(ids(" checkout "), ["payments-api"]);
+        assert_eq!(ids("client"), ["web-client"]);
+        assert!(ids("mobile").is_empty());
+    }
+
+    #[test]
+    fn recent_header_counts_sessions_and_hidden_rows() {
+        assert_eq!(recent_header(0, 5, 0, false), "Recent sessions");
+        assert_eq!(recent_header(0, 5, 3, false), "Recent sessions (3)");
+        assert_eq!(
+            recent_header(0, 3, 10, false),
+            "Recent sessions 1-3 of 10 · 7 hidden"
+        );
+        assert_eq!(
+            recent_header(8, 3, 10, true),
+            "Recent sessions 9-10 of 10 · 8 hidden · show all"
+        );
+    }
+
+    #[test]
+    fn presence_row_follows_the_driving_session_across_sort_orders() {
+        let mut driving = recent_session("driving", 1_000, 0.10, 0);
+        driving.activity = Some(SessionActivitySnapshot {
+            kind: SessionActivityKind::RunningCommand,
+            pending_calls: 1,
+            ..SessionActivitySnapshot::default()
+        });
+        let sessions = vec![
+            recent_session("big", 9_000, 5.00, 600),
+            driving,
+            recent_session("old", 2_000, 0.20, 1_200),
+        ];
+        let marked = |sort, pinned| {
+            let data = RenderData {
+                sessions: &sessions,
+                recent_sort: sort,
+                pinned_session_id: pinned,
+                ..sample_render_data(None)
+            };
+            let presence = presence_session_id(&data);
+            sort_recent_sessions(&sessions, sort)
+                .into_iter()
+                .filter(|session| presence == Some(session.session_id.as_str()))
+                .map(|session| session.session_id.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        for sort in [
+            RecentSort::Rank,
+            RecentSort::Tokens,
+            RecentSort::Recency,
+            RecentSort::Cost,
+        ] {
+            assert_eq!(marked(sort, None), ["driving"], "{sort:?}");
+            assert_eq!(marked(sort, Some("old")), ["old"], "{sort:?}");
+        }
+        assert_eq!(
+            sort_recent_sessions(&sessions, RecentSort::Tokens)[0].session_id,
+            "big"
+        );
+    }
+
+    #[test]
+    fn recent_line_template_renders_chosen_fields_in_order() {
+        let mut session = recent_session("api", 12_500, 0.0, 10);
+        session.git_branch = Some("main".to_string());
+        session.last_turn_tokens = Some(800);
+        let sessions = vec![session];
+        let data = RenderData {
+            sessions: &sessions,
+            recent_line_template: "{total} on {branch} [{project}] last {last} {nope}",
+            ..sample_render_data(None)
+        };
+
+        assert_eq!(
+            recent_line_text(&sessions[0], &data),
+            "12.5K on main [api] last 800 {nope}"
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 4)).expect("terminal");
+        terminal
+            .draw(|frame| render_recent(frame, frame.area(), UiLayoutMode::Full, &data))
+            .expect("draw");
+        let output = terminal.backend().to_string();
+        assert!(output.contains("> 12.5K on main [api] las..."), "{output}");
+    }
+
+    #[test]
+    fn recent_sort_orders_sessions_without_changing_rank_order() {
+        let sessions = vec![
+            recent_session("ranked-first", 1_000, 0.50, 30),
+            recent_session("most-tokens", 9_000, 0.10, 20),
+            recent_session("most-recent", 2_000, 0.20, 0),
+            recent_session("most-cost", 3_000, 4.00, 60),
+        ];
+        let order = |sort| {
+            sort_recent_sessions(&sessions, sort)
+                .into_iter()
+                .map(|session| session.session_id.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            order(RecentSort::Rank),
+            ["ranked-first", "most-tokens", "most-recent", "most-cost"]
+        );
+        assert_eq!(
+            order(RecentSort::Tokens),
+            ["most-tokens", "most-cost", "most-recent", "ranked-first"]
+        );
+        assert_eq!(
+            order(RecentSort::Recency),
+            ["most-recent", "most-tokens", "ranked-first", "most-cost"]
+        );
+        assert_eq!(
+            order(RecentSort::Cost),
+            ["most-cost", "ranked-first", "most-recent", "most-tokens"]
+        );
+        assert_eq!(sessions[0].session_id, "ranked-first");
+    }
+
+    #[test]
+    fn active_cost_shows_zero_for_priced_sessions_and_na_for_unpriced() {
+        let pricing = PricingConfig::default();
+        let unpriced = recent_session("unpriced", 4_000, 0.0, 0);
+        assert_eq!(presentable_cost(&unpriced, &pricing), "n/a");
+
+        let mut free = recent_session("free", 4_000, 0.0, 0);
+        free.pricing_source = PricingSource::Exact;
+        free.pricing_status = crate::cost::PricingStatus::Exact;
+        free.known_cost_usd = Some(0.0);
+        assert_eq!(presentable_cost(&free, &pricing), "$0.00");
+
+        free.known_cost_usd = None;
+        free.pricing_status = crate::cost::PricingStatus::Unavailable;
+        assert_eq!(presentable_cost(&free, &pricing), "n/a");
+    }
+
+    #[test]
+    fn activity_emoji_flag_prefixes_kind_glyph() {
+        let activity = |kind| SessionActivitySnapshot {
+            kind,
+            ..SessionActivitySnapshot::default()
+        };
+        for (kind, glyph) in [
+            (SessionActivityKind::Thinking, "🧠"),
+            (SessionActivityKind::ReadingFile, "📖"),
+            (SessionActivityKind::EditingFile, "📝"),
+            (SessionActivityKind::WritingTests, "🧪"),
+            (SessionActivityKind::RunningCommand, "🔧"),
+            (SessionActivityKind::WaitingInput, "⌛"),
+            (SessionActivityKind::AwaitingApproval, "✋"),
+        ] {
+            let activity = activity(kind);
+            let plain = activity_line_text(&activity, true, false);
+            let decorated = activity_line_text(&activity, true, true);
+            assert_eq!(decorated, format!("{glyph} {plain}"));
+            assert!(!decorated.contains('\u{FE0F}'), "{decorated}");
+            assert_eq!(
+                Span::raw(decorated.as_str()).width(),
+                Span::raw(plain.as_str()).width() + 3,
+                "{decorated}"
+            );
+        }
+        let idle = activity(SessionActivityKind::Idle);
+        assert_eq!(
+            activity_line_text(&idle, true, true),
+            activity_line_text(&idle, true, false)
+        );
+        assert_eq!(Span::raw("🧠 Thinking").width(), 11);
+
+        let editing = SessionActivitySnapshot {
+            kind: SessionActivityKind::EditingFile,
+            target: Some("main.rs".to_string()),
+            ..SessionActivitySnapshot::default()
+        };
+        let mut terminal = Terminal::new(TestBackend::new(24, 1)).expect("terminal");
+        terminal
+            .draw(|frame| {
+                frame.render_widget(
+                    Paragraph::new(format!("{}|", activity_line_text(&editing, true, true))),
+                    frame.area(),
+                );
+            })
+            .expect("draw");
+        assert_eq!(terminal.backend().buffer()[(18, 0)].symbol(), "|");
+    }
+
+    #[test]
+    fn help_lines_align_keys_and_respect_width() {
+        assert_eq!(
+            help_line("M", "Pause presence", 5, 40),
+            "M      Pause presence"
+        );
+        assert_eq!(help_line("Enter", "Pin", 5, 40), "Enter  Pin");
+        assert_eq!(
+            help_line("q / Ctrl+C", "Quit the dashboard", 13, 12),
+            "q / Ctrl+..."
+        );
+    }
+
+    #[test]
+    fn recent_elapsed_label_formats_known_start_and_missing_start() {
+        let now = DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+            .expect("now")
+            .with_timezone(&Utc);
+        let started = now - chrono::Duration::seconds(3_725);
+
+        assert_eq!(session_elapsed_label(Some(started), now), "1h 2m");
+        assert_eq!(
+            session_elapsed_label(Some(now - chrono::Duration::seconds(42)), now),
+            "42s"
+        );
+        assert_eq!(
+            session_elapsed_label(Some(now + chrono::Duration::seconds(5)), now),
+            "0s"
+        );
+        assert_eq!(session_elapsed_label(None, now), "n/a");
+    }
+
+    #[test]
+    fn sparkline_maps_token_deltas_to_block_heights() {
+        assert_eq!(sparkline_block(0, 0), '▁');
+        assert_eq!(sparkline_block(0, 700), '▁');
+        assert_eq!(sparkline_block(350, 700), '▄');
+        assert_eq!(sparkline_block(700, 700), '█');
+        assert_eq!(sparkline_block(900, 700), '█');
+        assert_eq!(token_sparkline(&[0, 100, 700]), "▁▂█");
+        assert_eq!(token_sparkline(&[]), "");
+    }
+
+    #[test]
+    fn recent_cursor_steps_are_clamped_to_the_session_count() {
+        assert_eq!(step_recent_cursor(None, 1, 10), Some(0));
+        assert_eq!(step_recent_cursor(Some(0), -1, 10), Some(0));
+        assert_eq!(step_recent_cursor(Some(0), 1, 10), Some(1));
+        assert_eq!(
+            step_recent_cursor(Some(8), RECENT_PAGE_ROWS as isize, 10),
+            Some(9)
+        );
+        assert_eq!(
+            step_recent_cursor(Some(3), -(RECENT_PAGE_ROWS as isize), 10),
+            Some(0)
+        );
+        assert_eq!(step_recent_cursor(Some(4), 1, 0), None);
+
+        assert_eq!(recent_scroll_offset(Some(9), 10, 4), 6);
+        assert_eq!(recent_scroll_offset(Some(5), 10, 4), 2);
+        assert_eq!(recent_scroll_offset(Some(2), 10, 4), 0);
+        assert_eq!(recent_scroll_offset(Some(50), 10, 4), 6);
+        assert_eq!(recent_scroll_offset(Some(5), 3, 4), 0);
+        assert_eq!(recent_scroll_offset(None, 10, 4), 0);
+
+        let (total, visible) = (10, 4);
+        let mut cursor = None;
+        for delta in [1, 3, RECENT_PAGE_ROWS as isize, 99, -2, -99] {
+            cursor = step_recent_cursor(cursor, delta, total);
+            let selected = cursor.expect("cursor");
+            let offset = recent_scroll_offset(cursor, total, visible);
+            assert!(
+                (offset..offset + visible).contains(&selected),
+                "cursor {selected} outside rows {offset}..{}",
+                offset + visible
+            );
+        }
+    }
+
     #[test]
     fn limit_color_thresholds() {
-        assert_eq!(limit_color(80.0), theme::TEXT);
-        assert_eq!(limit_color(45.0), theme::MUTED);
-        assert_eq!(limit_color(12.0), theme::TEXT);
+        assert_eq!(limit_color(80.0, UiTheme::Dark), theme::TEXT);
+        assert_eq!(limit_color(45.0, UiTheme::Dark), theme::MUTED);
+        assert_eq!(limit_color(12.0, UiTheme::Dark), theme::TEXT);
+    }
+
+    #[test]
+    fn light_and_dark_themes_pick_distinct_limit_colors() {
+        for remaining in [80.0, 45.0, 12.0] {
+            assert_ne!(
+                limit_color(remaining, UiTheme::Light),
+                limit_color(remaining, UiTheme::Dark)
+            );
+        }
+        assert_eq!(limit_color(45.0, UiTheme::Light), theme::LIGHT_MUTED);
+    }
+
+    #[test]
+    fn none_theme_renders_without_colors() {
+        let mut data = sample_render_data(None);
+        data.theme = UiTheme::None;
+        assert_eq!(limit_color(12.0, UiTheme::None), Color::Reset);
+
+        let backend = TestBackend::new(120, 40);
+        let mut terminal = Terminal::new(backend).expect("terminal");
+        terminal
+            .draw(|frame| {
+                render_frame(frame, &data);
+                apply_render_styling(frame.buffer_mut(), &data);
+            })
+            .expect("draw");
+        assert!(
+            terminal
+                .backend()
+                .buffer()
+                .content
+                .iter()
+                .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+        );
+    }
+
+    #[test]
+    fn banner_file_lines_replace_ascii_only_when_they_fit() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("banner.txt");
+        std::fs::write(&path, "  MY STREAM  \n  ---------\n\n\n").expect("write");
+        let path = path.to_str().expect("utf8 path");
+
+        let mut banner = BannerFile::default();
+        let lines = banner.lines_for(Some(path)).expect("banner lines");
+        assert_eq!(lines, ["  MY STREAM", "  ---------"]);
+        assert_eq!(banner_rows(Some(lines), 40), ["  MY STREAM", "  ---------"]);
+        assert_eq!(banner_rows(Some(lines), 8), CODEX_ASCII.to_vec());
+        assert_eq!(banner_rows(None, 120), CODEX_ASCII.to_vec());
+
+        assert!(
+            banner
+                .lines_for(Some("/definitely/missing/banner.txt"))
+                .is_none()
+        );
+        assert!(banner.lines_for(None).is_none());
     }
 
     #[test]
@@ -1138,13 +2697,14 @@ mod tests {
 
     #[test]
     fn footer_parts_never_overlap() {
-        let (left, right) = footer_parts(84, FooterMode::Normal, "Codex App", true);
-        assert!(left.len() + 1 + right.len() <= 84);
+        let (left, right) = footer_parts(84, FooterMode::Normal, "Codex App", true, false, false);
+        assert!(left.chars().count() + 1 + right.chars().count() <= 84);
         assert!(left.contains("D design"));
 
-        let (left_small, right_small) = footer_parts(20, FooterMode::Normal, "Codex App", true);
+        let (left_small, right_small) =
+            footer_parts(20, FooterMode::Normal, "Codex App", true, false, false);
         assert_eq!(right_small, "");
-        assert_eq!(left_small, "M presence: On ·...");
+        assert_eq!(left_small, "M presence: On · ...");
     }
 
     #[test]
@@ -1197,23 +2757,36 @@ mod tests {
 
     #[test]
     fn footer_parts_change_when_plan_picker_is_open() {
-        let (left, _right) = footer_parts(80, FooterMode::PlanPicker, "Codex App", true);
+        let (left, _right) =
+            footer_parts(80, FooterMode::PlanPicker, "Codex App", true, false, false);
         assert!(left.contains("Plan selector"));
     }
 
     #[test]
     fn footer_names_the_selected_desktop_design() {
-        let (left, _right) = footer_parts(120, FooterMode::Normal, "ChatGPT App", true);
+        let (left, _right) =
+            footer_parts(120, FooterMode::Normal, "ChatGPT App", true, false, false);
         assert!(left.contains("D design: ChatGPT App"));
     }
 
     #[test]
     fn footer_exposes_master_presence_state_and_shortcut() {
-        let (running, _right) = footer_parts(140, FooterMode::Normal, "ChatGPT App", true);
+        let (running, _right) =
+            footer_parts(140, FooterMode::Normal, "ChatGPT App", true, false, false);
         assert!(running.contains("M presence: On"));
 
-        let (paused, _right) = footer_parts(140, FooterMode::Normal, "ChatGPT App", false);
+        let (paused, _right) =
+            footer_parts(140, FooterMode::Normal, "ChatGPT App", false, false, false);
         assert!(paused.contains("M presence: Paused"));
+
+        let (frozen, _right) =
+            footer_parts(140, FooterMode::Normal, "ChatGPT App", true, true, false);
+        assert!(frozen.contains("F freeze: On"));
+        assert!(running.contains("F freeze: Off"));
+        assert!(!running.contains("Privacy: ON"));
+
+        let private = footer_parts(140, FooterMode::Normal, "ChatGPT App", true, false, true).0;
+        assert!(private.starts_with("Privacy: ON · M presence: On"));
     }
 
     #[test]
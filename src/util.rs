@@ -1,18 +1,59 @@
 use std::io::{self, Write};
 use std::path::Path;
-use std::process::Command;
-use std::time::Duration;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use chrono::{DateTime, Local, Utc};
 use serde::Serialize;
 use tempfile::NamedTempFile;
+use tracing::Subscriber;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{EnvFilter, fmt};
 
+use crate::config::{BarChars, BarStyle, Currency};
 use crate::model::{ReasoningEffort, SpeedMode, model_requests_fast, resolve_model};
 
-pub fn setup_tracing() {
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
-    let _ = fmt().with_env_filter(filter).without_time().try_init();
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum LogFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+pub fn log_level_directive(quiet: bool, verbose: u8) -> &'static str {
+    match (quiet, verbose) {
+        (true, _) => "error",
+        (false, 0) => "info",
+        (false, 1) => "debug",
+        (false, _) => "trace",
+    }
+}
+
+pub fn setup_tracing(format: LogFormat, default_level: &str) {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_level));
+    match format {
+        LogFormat::Human => {
+            let _ = fmt().with_env_filter(filter).without_time().try_init();
+        }
+        LogFormat::Json => {
+            let _ = json_subscriber(filter, io::stdout).try_init();
+        }
+    }
+}
+
+fn json_subscriber<W>(filter: EnvFilter, make_writer: W) -> impl Subscriber + Send + Sync
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    fmt()
+        .json()
+        .with_env_filter(filter)
+        .with_ansi(false)
+        .with_writer(make_writer)
+        .finish()
 }
 
 pub fn silent_command(program: &str) -> Command {
@@ -31,6 +72,44 @@ pub fn silent_command(program: &str) -> Command {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandStdout {
+    Success(Vec<u8>),
+    Failed,
+    TimedOut,
+}
+
+pub fn command_stdout_until(
+    mut command: Command,
+    deadline: Instant,
+    poll: Duration,
+) -> CommandStdout {
+    let Ok(mut child) = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return CommandStdout::Failed;
+    };
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => thread::sleep(poll),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return CommandStdout::TimedOut;
+            }
+            Err(_) => return CommandStdout::Failed,
+        }
+    }
+    match child.wait_with_output() {
+        Ok(output) if output.status.success() => CommandStdout::Success(output.stdout),
+        _ => CommandStdout::Failed,
+    }
+}
+
 pub fn format_tokens(tokens: u64) -> String {
     if tokens >= 1_000_000 {
         format!("{:.1}M", tokens as f64 / 1_000_000.0)
@@ -42,15 +121,26 @@ pub fn format_tokens(tokens: u64) -> String {
 }
 
 pub fn format_cost(cost_usd: f64) -> String {
-    if !cost_usd.is_finite() || cost_usd <= 0.0 {
-        return "$0.00".to_string();
+    format_cost_in(cost_usd, Currency::Usd, 1.0)
+}
+
+pub fn format_cost_in(cost_usd: f64, currency: Currency, usd_rate: f64) -> String {
+    let (currency, rate) = if usd_rate.is_finite() && usd_rate > 0.0 {
+        (currency, usd_rate)
+    } else {
+        (Currency::Usd, 1.0)
+    };
+    let symbol = currency.symbol();
+    let amount = cost_usd * rate;
+    if !amount.is_finite() || amount <= 0.0 {
+        return format!("{symbol}0.00");
     }
-    if cost_usd < 0.01 {
-        format!("${cost_usd:.4}")
-    } else if cost_usd < 1.0 {
-        format!("${cost_usd:.3}")
+    if amount < 0.01 {
+        format!("{symbol}{amount:.4}")
+    } else if amount < 1.0 {
+        format!("{symbol}{amount:.3}")
     } else {
-        format!("${cost_usd:.2}")
+        format!("{symbol}{amount:.2}")
     }
 }
 
@@ -172,21 +262,75 @@ pub fn human_duration(duration: Duration) -> String {
     }
 }
 
+const PARTIAL_BLOCKS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
 pub fn progress_bar(percent: f64, width: usize) -> String {
+    progress_bar_styled(percent, width, BarStyle::Ascii, BarChars::ASCII)
+}
+
+pub fn progress_bar_styled(percent: f64, width: usize, style: BarStyle, chars: BarChars) -> String {
     let pct = percent.clamp(0.0, 100.0);
+    if style == BarStyle::Blocks && chars.is_full_block() {
+        let eighths = ((pct / 100.0) * width as f64 * 8.0).round() as usize;
+        let filled = eighths / 8;
+        let partial = (eighths % 8)
+            .checked_sub(1)
+            .map(|index| PARTIAL_BLOCKS[index]);
+        let empty = width.saturating_sub(filled + usize::from(partial.is_some()));
+        let mut bar = "█".repeat(filled);
+        bar.extend(partial);
+        bar.extend(std::iter::repeat_n(chars.empty, empty));
+        return bar;
+    }
     let filled = ((pct / 100.0) * width as f64).round() as usize;
     let empty = width.saturating_sub(filled);
-    format!("{}{}", "#".repeat(filled), "-".repeat(empty))
+    std::iter::repeat_n(chars.filled, filled)
+        .chain(std::iter::repeat_n(chars.empty, empty))
+        .collect()
+}
+
+pub fn template_placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        names.push(&after[..end]);
+        rest = &after[end + 1..];
+    }
+    names
+}
+
+pub fn expand_template(template: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        match lookup(&after[..end]) {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    out
 }
 
 pub fn truncate(input: &str, max_len: usize) -> String {
-    if input.len() <= max_len {
+    if input.chars().count() <= max_len {
         return input.to_string();
     }
     if max_len <= 3 {
-        return safe_prefix(input, max_len).to_string();
+        return char_prefix(input, max_len).to_string();
     }
-    format!("{}...", safe_prefix(input, max_len - 3))
+    format!("{}...", char_prefix(input, max_len - 3))
 }
 
 pub fn now_local() -> String {
@@ -194,10 +338,13 @@ pub fn now_local() -> String {
 }
 
 pub fn format_since(target: Option<DateTime<Utc>>) -> String {
+    format_since_at(target, Utc::now())
+}
+
+pub fn format_since_at(target: Option<DateTime<Utc>>, now: DateTime<Utc>) -> String {
     let Some(target) = target else {
         return "n/a".to_string();
     };
-    let now = Utc::now();
     if target >= now {
         return "just now".to_string();
     }
@@ -221,11 +368,11 @@ pub fn write_json_pretty_atomic<T: Serialize>(path: &Path, value: &T) -> io::Res
     write_text_atomic(path, &json)
 }
 
-fn safe_prefix(input: &str, max_len: usize) -> &str {
-    let mut end = max_len.min(input.len());
-    while end > 0 && !input.is_char_boundary(end) {
-        end -= 1;
-    }
+fn char_prefix(input: &str, max_chars: usize) -> &str {
+    let end = input
+        .char_indices()
+        .nth(max_chars)
+        .map_or(input.len(), |(index, _)| index);
     &input[..end]
 }
 
@@ -233,11 +380,112 @@ fn safe_prefix(input: &str, max_len: usize) -> &str {
 mod tests {
     use super::*;
 
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct CapturedLog(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for CapturedLog {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().expect("log buffer").extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
     #[test]
-    fn token_formatting() {
-        assert_eq!(format_tokens(999), "999");
-        assert_eq!(format_tokens(1_500), "1.5K");
-        assert_eq!(format_tokens(1_500_000), "1.5M");
+    fn quiet_and_verbose_counts_map_to_log_levels() {
+        assert_eq!(log_level_directive(false, 0), "info");
+        assert_eq!(log_level_directive(true, 0), "error");
+        assert_eq!(log_level_directive(false, 1), "debug");
+        assert_eq!(log_level_directive(false, 2), "trace");
+        assert_eq!(log_level_directive(false, 5), "trace");
+    }
+
+    #[test]
+    fn json_log_format_emits_one_parseable_object_per_line() {
+        let captured = CapturedLog::default();
+        let writer = captured.clone();
+        let subscriber = json_subscriber(EnvFilter::new("info"), move || writer.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(sessions = 2_u64, "presence updated");
+        });
+
+        let output =
+            String::from_utf8(captured.0.lock().expect("log buffer").clone()).expect("utf8 log");
+        let line: serde_json::Value = serde_json::from_str(output.trim()).expect("json line");
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["fields"]["message"], "presence updated");
+        assert_eq!(line["fields"]["sessions"], 2);
+        assert!(line["timestamp"].is_string(), "{line}");
+    }
+
+    #[test]
+    fn progress_bar_ascii_and_block_styles() {
+        assert_eq!(progress_bar(50.0, 10), "#####-----");
+        for (percent, width, ascii, blocks) in [
+            (0.0, 4, "----", "░░░░"),
+            (100.0, 4, "####", "████"),
+            (150.0, 3, "###", "███"),
+            (50.0, 10, "#####-----", "█████░░░░░"),
+            (33.0, 6, "##----", "██░░░░"),
+            (30.0, 6, "##----", "█▊░░░░"),
+            (12.5, 2, "--", "▎░"),
+        ] {
+            assert_eq!(
+                progress_bar_styled(percent, width, BarStyle::Ascii, BarStyle::Ascii.chars()),
+                ascii,
+                "ascii {percent}% x{width}"
+            );
+            assert_eq!(
+                progress_bar_styled(percent, width, BarStyle::Blocks, BarStyle::Blocks.chars()),
+                blocks,
+                "blocks {percent}% x{width}"
+            );
+        }
+        for width in [1, 7, 20] {
+            for style in [BarStyle::Ascii, BarStyle::Blocks] {
+                assert_eq!(
+                    progress_bar_styled(42.0, width, style, style.chars())
+                        .chars()
+                        .count(),
+                    width
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn progress_bar_uses_configured_fill_chars() {
+        let chars = BarChars {
+            filled: '=',
+            empty: '.',
+        };
+        for style in [BarStyle::Ascii, BarStyle::Blocks] {
+            assert_eq!(progress_bar_styled(40.0, 10, style, chars), "====......");
+            for (percent, width) in [(0.0, 5), (33.0, 7), (66.6, 13), (100.0, 9)] {
+                let bar = progress_bar_styled(percent, width, style, chars);
+                let filled = bar.chars().filter(|ch| *ch == '=').count();
+                let empty = bar.chars().filter(|ch| *ch == '.').count();
+                assert_eq!(filled + empty, width, "{bar}");
+            }
+        }
+
+        let dotted_blocks = BarChars {
+            filled: '█',
+            empty: '·',
+        };
+        assert_eq!(
+            progress_bar_styled(30.0, 6, BarStyle::Blocks, dotted_blocks),
+            "█▊····"
+        );
+        assert_eq!(
+            progress_bar_styled(30.0, 6, BarStyle::Ascii, dotted_blocks),
+            "██····"
+        );
     }
 
     #[test]
@@ -260,6 +508,22 @@ mod tests {
         assert_eq!(format_cost(12.3456), "$12.35");
     }
 
+    #[test]
+    fn cost_formatting_applies_currency_symbol_and_rate() {
+        assert_eq!(format_cost_in(1.0, Currency::Eur, 0.92), "€0.920");
+        assert_eq!(format_cost_in(12.3456, Currency::Gbp, 0.5), "£6.17");
+        assert_eq!(format_cost_in(0.001, Currency::Eur, 2.0), "€0.0020");
+        assert_eq!(format_cost_in(0.0, Currency::Gbp, 0.8), "£0.00");
+        assert_eq!(format_cost_in(2.5, Currency::Usd, 1.0), format_cost(2.5));
+    }
+
+    #[test]
+    fn cost_formatting_falls_back_to_usd_for_invalid_rates() {
+        assert_eq!(format_cost_in(2.0, Currency::Eur, 0.0), "$2.00");
+        assert_eq!(format_cost_in(2.0, Currency::Gbp, f64::NAN), "$2.00");
+        assert_eq!(format_cost_in(2.0, Currency::Eur, -0.9), "$2.00");
+    }
+
     #[test]
     fn model_name_formatting() {
         assert_eq!(format_model_name("gpt-5.3-codex"), "GPT-5.3-Codex");
@@ -280,7 +544,23 @@ mod tests {
 
     #[test]
     fn truncate_is_utf8_safe() {
-        assert_eq!(truncate("⚡ GPT-5.4", 6), "⚡...");
+        assert_eq!(truncate("⚡ GPT-5.4", 6), "⚡ G...");
+    }
+
+    #[test]
+    fn truncate_counts_characters_around_multibyte_cut_points() {
+        assert_eq!(truncate("café-au-lait", 7), "café...");
+        assert_eq!(truncate("日本語のプロジェクト", 6), "日本語...");
+        assert_eq!(truncate("日本語", 3), "日本語");
+        assert_eq!(truncate("日本語です", 2), "日本");
+        assert_eq!(truncate("🚀🚀🚀🚀🚀", 4), "🚀...");
+        assert_eq!(truncate("résumé", 0), "");
+
+        for text in ["añb😀ç日本", "ééééééé", "🧠📖✏️⚙️📦"] {
+            for max in 0..=text.chars().count() + 1 {
+                assert!(truncate(text, max).chars().count() <= max);
+            }
+        }
     }
 
     #[test]
@@ -294,6 +574,41 @@ mod tests {
         assert_eq!(std::fs::read_to_string(path).expect("read"), "second");
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn command_stdout_until_reports_success_failure_and_timeout() {
+        let poll = Duration::from_millis(5);
+        let far = Instant::now() + Duration::from_secs(10);
+
+        let mut command = Command::new("sh");
+        command.args(["-c", "printf ok"]);
+        assert_eq!(
+            command_stdout_until(command, far, poll),
+            CommandStdout::Success(b"ok".to_vec())
+        );
+
+        let mut command = Command::new("sh");
+        command.args(["-c", "exit 3"]);
+        assert_eq!(
+            command_stdout_until(command, far, poll),
+            CommandStdout::Failed
+        );
+
+        let mut command = Command::new("sleep");
+        command.arg("5");
+        let started = Instant::now();
+        assert_eq!(
+            command_stdout_until(command, started + Duration::from_millis(50), poll),
+            CommandStdout::TimedOut
+        );
+        assert!(started.elapsed() < Duration::from_secs(4));
+
+        assert_eq!(
+            command_stdout_until(Command::new("/definitely/missing/binary"), far, poll),
+            CommandStdout::Failed
+        );
+    }
+
     #[test]
     fn background_windows_commands_use_silent_launcher() {
         let app = include_str!("app.rs");
@@ -0,0 +1,220 @@
+use std::io::{Read, Write};
+use std::net::{IpAddr, TcpListener, TcpStream};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::{debug, info};
+
+use crate::discord::PresencePresentation;
+use crate::metrics::MetricsSnapshot;
+use crate::session::RateLimits;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_REQUEST_BYTES: usize = 4096;
+
+static STATE: OnceLock<Mutex<WebState>> = OnceLock::new();
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WebState {
+    pub updated_at: Option<DateTime<Utc>>,
+    pub presence_enabled: bool,
+    pub project: Option<String>,
+    pub model: Option<String>,
+    pub presence: Option<PresencePresentation>,
+    pub limits: Option<RateLimits>,
+    pub metrics: Option<MetricsSnapshot>,
+    pub cost: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebResponse {
+    pub status: &'static str,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+pub fn start(bind: IpAddr, port: u16) -> Result<()> {
+    let listener = TcpListener::bind((bind, port))
+        .with_context(|| format!("failed to bind web dashboard on {bind}:{port}"))?;
+    STATE.get_or_init(|| Mutex::new(WebState::default()));
+    thread::Builder::new()
+        .name("codex-presence-web".to_string())
+        .spawn(move || serve(listener))
+        .context("failed to spawn web dashboard thread")?;
+    info!(%bind, port, "web dashboard listening");
+    Ok(())
+}
+
+pub fn is_enabled() -> bool {
+    STATE.get().is_some()
+}
+
+pub fn publish(state: WebState) {
+    if let Some(cell) = STATE.get()
+        && let Ok(mut current) = cell.lock()
+    {
+        *current = state;
+    }
+}
+
+pub fn route(path: &str, state: &WebState) -> WebResponse {
+    match path.split('?').next().unwrap_or_default() {
+        "/" | "/index.html" => WebResponse {
+            status: "200 OK",
+            content_type: "text/html; charset=utf-8",
+            body: DASHBOARD_HTML.to_string(),
+        },
+        "/api/state" => WebResponse {
+            status: "200 OK",
+            content_type: "application/json",
+            body: serde_json::to_string(state).unwrap_or_else(|_| "{}".to_string()),
+        },
+        _ => WebResponse {
+            status: "404 Not Found",
+            content_type: "text/plain; charset=utf-8",
+            body: "not found".to_string(),
+        },
+    }
+}
+
+fn serve(listener: TcpListener) {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(err) = handle_connection(stream) {
+                    debug!(error = %err, "web dashboard request failed");
+                }
+            }
+            Err(err) => debug!(error = %err, "web dashboard accept failed"),
+        }
+    }
+}
+
+fn handle_connection(mut stream: TcpStream) -> Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut buffer = [0_u8; MAX_REQUEST_BYTES];
+    let read = stream.read(&mut buffer)?;
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    let path = request_path(&request).unwrap_or("/");
+    let state = STATE
+        .get()
+        .and_then(|cell| cell.lock().ok().map(|state| state.clone()))
+        .unwrap_or_default();
+    let response = route(path, &state);
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn request_path(request: &str) -> Option<&str> {
+    let mut parts = request.lines().next()?.split_whitespace();
+    match parts.next()? {
+        "GET" | "HEAD" => parts.next(),
+        _ => None,
+    }
+}
+
+const DASHBOARD_HTML: &str = r#"<!doctype html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Codex Presence</title>
+<style>
+body { font-family: ui-monospace, monospace; background: #111; color: #f5f5f5; margin: 1.5rem; }
+h1 { font-size: 1.2rem; }
+section { border: 1px solid #525252; padding: 0.75rem; margin-bottom: 1rem; }
+.muted { color: #969696; }
+</style>
+</head>
+<body>
+<h1>Codex Presence</h1>
+<section id="active"><span class="muted">Waiting for state...</span></section>
+<section id="limits"></section>
+<section id="metrics"></section>
+<script>
+function text(value) { return value === null || value === undefined ? "-" : String(value); }
+function windowLine(name, w) { return w ? name + ": " + w.used_percent.toFixed(0) + "% used" : name + ": -"; }
+async function refresh() {
+  try {
+    const state = await (await fetch("/api/state", { cache: "no-store" })).json();
+    const p = state.presence;
+    document.getElementById("active").innerText = p
+      ? p.app_name + "\n" + p.details + "\n" + p.state + "\nproject: " + text(state.project) + "\nmodel: " + text(state.model)
+      : "No active session";
+    const l = state.limits;
+    document.getElementById("limits").innerText = l
+      ? windowLine("primary", l.primary) + "\n" + windowLine("secondary", l.secondary)
+      : "Limits unavailable";
+    const m = state.metrics;
+    document.getElementById("metrics").innerText = m
+      ? "cost: " + text(state.cost) + "\ntokens: " + text(m.totals.total_tokens) + "\nactive sessions: " + text(m.active_sessions)
+      : "Metrics warming up";
+  } catch (err) {
+    document.getElementById("active").innerText = "Disconnected";
+  }
+}
+refresh();
+setInterval(refresh, 3000);
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_state_returns_sample_state_as_json() {
+        let state = WebState {
+            updated_at: None,
+            presence_enabled: true,
+            project: Some("app".to_string()),
+            model: Some("gpt-5.2-codex".to_string()),
+            presence: Some(PresencePresentation {
+                app_name: "Codex CLI".to_string(),
+                details: "Editing main.rs".to_string(),
+                state: "GPT-5.2 Codex".to_string(),
+                large_image_key: "codex-logo".to_string(),
+                large_text: "Codex CLI".to_string(),
+                small_image_key: None,
+                small_text: None,
+            }),
+            limits: None,
+            metrics: None,
+            cost: Some("€1.80".to_string()),
+        };
+
+        let response = route("/api/state?ts=1", &state);
+        assert_eq!(response.status, "200 OK");
+        assert_eq!(response.content_type, "application/json");
+        let json: serde_json::Value = serde_json::from_str(&response.body).expect("json");
+        assert_eq!(json["presence_enabled"], true);
+        assert_eq!(json["project"], "app");
+        assert_eq!(json["presence"]["details"], "Editing main.rs");
+        assert!(json["limits"].is_null());
+        assert!(json["metrics"].is_null());
+        assert_eq!(json["cost"], "€1.80");
+        assert!(!DASHBOARD_HTML.contains("\"cost: $\""));
+
+        assert_eq!(route("/missing", &state).status, "404 Not Found");
+        assert_eq!(
+            request_path("GET /api/state HTTP/1.1\r\n"),
+            Some("/api/state")
+        );
+        assert_eq!(request_path("POST /api/state HTTP/1.1\r\n"), None);
+    }
+}
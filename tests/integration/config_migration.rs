@@ -22,6 +22,7 @@ fn non_codex_identity_is_rewritten_to_codex_identity() {
                 reading: Some("reading".to_string()),
                 editing: Some("editing".to_string()),
                 running: Some("running".to_string()),
+                archiving: Some("archiving".to_string()),
                 waiting: Some("waiting".to_string()),
                 idle: Some("idle".to_string()),
             },